@@ -33,8 +33,8 @@ use datafusion::logical_plan::plan::{
 };
 use datafusion::logical_plan::{
     source_as_provider, Column, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
-    CreateView, CrossJoin, Expr, JoinConstraint, Limit, LogicalPlan, LogicalPlanBuilder,
-    Repartition, TableScan, Values,
+    CreateView, CrossJoin, EstimatedRows, Expr, JoinConstraint, Limit, LogicalPlan,
+    LogicalPlanBuilder, Repartition, TableScan, Values,
 };
 use datafusion::prelude::SessionContext;
 
@@ -248,16 +248,30 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .with_listing_options(options)
                     .with_schema(Arc::new(schema));
 
+                let partition_filters = scan
+                    .partition_filters
+                    .iter()
+                    .map(|expr| parse_expr(expr, ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+
                 let provider = ListingTable::try_new(config)?;
 
-                LogicalPlanBuilder::scan_with_filters(
+                let plan = LogicalPlanBuilder::scan_with_filters(
                     &scan.table_name,
                     Arc::new(provider),
                     projection,
                     filters,
                 )?
-                .build()
-                .map_err(|e| e.into())
+                .build()?;
+                match plan {
+                    LogicalPlan::TableScan(scan) => {
+                        Ok(LogicalPlan::TableScan(TableScan {
+                            partition_filters,
+                            ..scan
+                        }))
+                    }
+                    _ => unreachable!("scan_with_filters always builds a TableScan"),
+                }
             }
             LogicalPlanType::Sort(sort) => {
                 let input: LogicalPlan =
@@ -405,6 +419,14 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .build()
                     .map_err(|e| e.into())
             }
+            LogicalPlanType::EstimatedRows(estimated_rows) => {
+                let input: LogicalPlan =
+                    into_logical_plan!(estimated_rows.input, ctx, extension_codec)?;
+                LogicalPlanBuilder::from(input)
+                    .with_estimated_rows(estimated_rows.rows as usize)
+                    .build()
+                    .map_err(|e| e.into())
+            }
             LogicalPlanType::Join(join) => {
                 let left_keys: Vec<Column> =
                     join.left_join_column.iter().map(|i| i.into()).collect();
@@ -426,6 +448,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                         join.join_constraint
                     ))
                 })?;
+                let hint = protobuf::JoinHint::from_i32(join.hint).ok_or_else(|| {
+                    proto_error(format!(
+                        "Received a JoinNode message with unknown JoinHint {}",
+                        join.hint
+                    ))
+                })?;
 
                 let builder = LogicalPlanBuilder::from(into_logical_plan!(
                     join.left,
@@ -445,7 +473,15 @@ impl AsLogicalPlan for LogicalPlanNode {
                     )?,
                 };
 
-                builder.build().map_err(|e| e.into())
+                match builder.build()? {
+                    LogicalPlan::Join(join) => Ok(LogicalPlan::Join(Join {
+                        hint: hint.into(),
+                        ..join
+                    })),
+                    plan => {
+                        Err(proto_error(format!("Expected a join plan, got {:?}", plan)))
+                    }
+                }
             }
             LogicalPlanType::Union(union) => {
                 let mut input_plans: Vec<LogicalPlan> = union
@@ -520,6 +556,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 table_name,
                 source,
                 filters,
+                partition_filters,
                 projection,
                 ..
             }) => {
@@ -545,6 +582,11 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .iter()
                     .map(|filter| filter.try_into())
                     .collect::<Result<Vec<_>, _>>()?;
+                let partition_filters: Vec<datafusion_proto::protobuf::LogicalExprNode> =
+                    partition_filters
+                        .iter()
+                        .map(|filter| filter.try_into())
+                        .collect::<Result<Vec<_>, _>>()?;
 
                 if let Some(listing_table) = source.downcast_ref::<ListingTable>() {
                     let any = listing_table.options().format.as_any();
@@ -585,6 +627,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                                 schema: Some(schema),
                                 projection,
                                 filters,
+                                partition_filters,
                                 target_partitions: listing_table
                                     .options()
                                     .target_partitions
@@ -690,6 +733,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 join_type,
                 join_constraint,
                 null_equals_null,
+                hint,
                 ..
             }) => {
                 let left: protobuf::LogicalPlanNode =
@@ -707,6 +751,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let join_type: protobuf::JoinType = join_type.to_owned().into();
                 let join_constraint: protobuf::JoinConstraint =
                     join_constraint.to_owned().into();
+                let hint: protobuf::JoinHint = hint.to_owned().into();
                 Ok(protobuf::LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Join(Box::new(
                         protobuf::JoinNode {
@@ -717,6 +762,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             left_join_column,
                             right_join_column,
                             null_equals_null: *null_equals_null,
+                            hint: hint.into(),
                         },
                     ))),
                 })
@@ -744,7 +790,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Limit(Limit { input, n }) => {
+            LogicalPlan::Limit(Limit { input, n, skip }) => {
+                if skip.is_some() {
+                    return Err(BallistaError::NotImplemented(
+                        "Serialization of a Limit with skip is not supported".to_string(),
+                    ));
+                }
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
                         input.as_ref(),
@@ -759,7 +810,27 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Sort(Sort { input, expr }) => {
+            LogicalPlan::EstimatedRows(EstimatedRows { input, rows }) => {
+                let input: protobuf::LogicalPlanNode =
+                    protobuf::LogicalPlanNode::try_from_logical_plan(
+                        input.as_ref(),
+                        extension_codec,
+                    )?;
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::EstimatedRows(Box::new(
+                        protobuf::EstimatedRowsNode {
+                            input: Some(Box::new(input)),
+                            rows: *rows as u64,
+                        },
+                    ))),
+                })
+            }
+            LogicalPlan::Sort(Sort { input, expr, fetch }) => {
+                if fetch.is_some() {
+                    return Err(BallistaError::NotImplemented(
+                        "Serialization of a Sort with fetch is not supported".to_string(),
+                    ));
+                }
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
                         input.as_ref(),
@@ -1030,8 +1101,8 @@ mod roundtrip_tests {
         },
         datasource::listing::ListingTable,
         logical_plan::{
-            col, CreateExternalTable, Expr, FileType, LogicalPlan, LogicalPlanBuilder,
-            Repartition, ToDFSchema,
+            col, CreateExternalTable, Expr, FileType, JoinHint, LogicalPlan,
+            LogicalPlanBuilder, Repartition, TableScan, ToDFSchema,
         },
         prelude::*,
     };
@@ -1267,6 +1338,26 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_join_hint() -> Result<()> {
+        let scan_plan = test_scan_csv("employee1", Some(vec![0, 3, 4]))
+            .await?
+            .build()?;
+
+        let plan = test_scan_csv("employee2", Some(vec![0, 3, 4]))
+            .await?
+            .join_with_hint(
+                &scan_plan,
+                JoinType::Left,
+                (vec!["id"], vec!["id"]),
+                JoinHint::BroadcastRight,
+            )?
+            .build()?;
+
+        roundtrip_test!(plan);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn roundtrip_sort() -> Result<()> {
         let plan = test_scan_csv("employee.csv", Some(vec![3, 4]))
@@ -1278,6 +1369,31 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_partition_filters() -> Result<()> {
+        let plan = match test_scan_csv("employee.csv", None).await?.build()? {
+            LogicalPlan::TableScan(scan) => LogicalPlan::TableScan(TableScan {
+                partition_filters: vec![col("state").eq(lit("CO"))],
+                ..scan
+            }),
+            _ => unimplemented!(),
+        };
+        roundtrip_test!(plan);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_estimated_rows() -> Result<()> {
+        let plan = test_scan_csv("employee.csv", Some(vec![3, 4]))
+            .await?
+            .with_estimated_rows(42)
+            .build()?;
+        roundtrip_test!(plan);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn roundtrip_empty_relation() -> Result<()> {
         let plan_false = LogicalPlanBuilder::empty(false).build()?;
@@ -25,7 +25,7 @@ use std::sync::Arc;
 use std::{convert::TryInto, io::Cursor};
 
 use datafusion::logical_plan::{
-    FunctionRegistry, JoinConstraint, JoinType, LogicalPlan, Operator,
+    FunctionRegistry, JoinConstraint, JoinHint, JoinType, LogicalPlan, Operator,
 };
 
 use crate::{error::BallistaError, serde::scheduler::Action as BallistaAction};
@@ -333,6 +333,26 @@ impl From<JoinConstraint> for protobuf::JoinConstraint {
     }
 }
 
+impl From<protobuf::JoinHint> for JoinHint {
+    fn from(t: protobuf::JoinHint) -> Self {
+        match t {
+            protobuf::JoinHint::None => JoinHint::None,
+            protobuf::JoinHint::BroadcastLeft => JoinHint::BroadcastLeft,
+            protobuf::JoinHint::BroadcastRight => JoinHint::BroadcastRight,
+        }
+    }
+}
+
+impl From<JoinHint> for protobuf::JoinHint {
+    fn from(t: JoinHint) -> Self {
+        match t {
+            JoinHint::None => protobuf::JoinHint::None,
+            JoinHint::BroadcastLeft => protobuf::JoinHint::BroadcastLeft,
+            JoinHint::BroadcastRight => protobuf::JoinHint::BroadcastRight,
+        }
+    }
+}
+
 fn byte_to_string(b: u8) -> Result<String, BallistaError> {
     let b = &[b];
     let b = std::str::from_utf8(b)
@@ -25,4 +25,9 @@ mod scalar;
 pub use column::Column;
 pub use dfschema::{DFField, DFSchema, DFSchemaRef, ExprSchema, ToDFSchema};
 pub use error::{field_not_found, DataFusionError, Result, SchemaError};
-pub use scalar::{ScalarType, ScalarValue};
+#[cfg(feature = "crypto_expressions")]
+pub use scalar::DigestAlgorithm;
+pub use scalar::{
+    ScalarArrayCache, ScalarColumnBuilder, ScalarType, ScalarValue,
+    MAX_NUMERIC_SERIES_LEN,
+};
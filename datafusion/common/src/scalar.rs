@@ -20,21 +20,28 @@
 use crate::error::{DataFusionError, Result};
 use arrow::{
     array::*,
-    compute::kernels::cast::cast,
+    compute::kernels::cast::{can_cast_types, cast, cast_with_options, CastOptions},
+    compute::kernels::cast_utils::string_to_timestamp_nanos,
     datatypes::{
-        ArrowDictionaryKeyType, ArrowNativeType, DataType, Field, Float32Type,
-        Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, IntervalUnit, TimeUnit,
-        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
-        TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
-        DECIMAL_MAX_PRECISION,
+        ArrowDictionaryKeyType, ArrowNativeType, DataType, Date32Type, Date64Type, Field,
+        Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+        IntervalUnit, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType,
+        TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type,
+        UInt8Type, DECIMAL_MAX_PRECISION,
     },
+    error::ArrowError,
 };
+use chrono::{Datelike, NaiveDate, NaiveTime};
 use ordered_float::OrderedFloat;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::convert::{Infallible, TryInto};
 use std::str::FromStr;
 use std::{convert::TryFrom, fmt, iter::repeat, sync::Arc};
 
+/// The maximum number of values [ScalarValue::numeric_series] generates
+pub const MAX_NUMERIC_SERIES_LEN: usize = 1_000_000;
+
 /// Represents a dynamically typed, nullable single value.
 /// This is the single-valued counter-part of arrow’s `Array`.
 #[derive(Clone)]
@@ -73,13 +80,32 @@ pub enum ScalarValue {
     Binary(Option<Vec<u8>>),
     /// large binary
     LargeBinary(Option<Vec<u8>>),
-    /// list of nested ScalarValue (boxed to reduce size_of(ScalarValue))
+    /// fixed size binary with the given byte width
+    FixedSizeBinary(i32, Option<Vec<u8>>),
+    /// list of nested ScalarValue (boxed to reduce size_of(ScalarValue)),
+    /// with the element type and whether the elements are nullable
+    #[allow(clippy::box_collection)]
+    List(Option<Box<Vec<ScalarValue>>>, Box<DataType>, bool),
+    /// fixed size list of nested ScalarValue with the element type, the list
+    /// length and whether the elements are nullable
     #[allow(clippy::box_collection)]
-    List(Option<Box<Vec<ScalarValue>>>, Box<DataType>),
+    FixedSizeList(Option<Box<Vec<ScalarValue>>>, Box<DataType>, i32, bool),
+    /// map of nested `Struct` entries with the entries field and whether the
+    /// keys are sorted
+    #[allow(clippy::box_collection)]
+    Map(Option<Box<Vec<ScalarValue>>>, Box<Field>, bool),
     /// Date stored as a signed 32bit int
     Date32(Option<i32>),
     /// Date stored as a signed 64bit int
     Date64(Option<i64>),
+    /// Time of day in seconds stored as a signed 32bit int
+    Time32Second(Option<i32>),
+    /// Time of day in milliseconds stored as a signed 32bit int
+    Time32Millisecond(Option<i32>),
+    /// Time of day in microseconds stored as a signed 64bit int
+    Time64Microsecond(Option<i64>),
+    /// Time of day in nanoseconds stored as a signed 64bit int
+    Time64Nanosecond(Option<i64>),
     /// Timestamp Second
     TimestampSecond(Option<i64>, Option<String>),
     /// Timestamp Milliseconds
@@ -94,9 +120,19 @@ pub enum ScalarValue {
     IntervalDayTime(Option<i64>),
     /// Interval with MonthDayNano unit
     IntervalMonthDayNano(Option<i128>),
+    /// Duration in seconds
+    DurationSecond(Option<i64>),
+    /// Duration in milliseconds
+    DurationMillisecond(Option<i64>),
+    /// Duration in microseconds
+    DurationMicrosecond(Option<i64>),
+    /// Duration in nanoseconds
+    DurationNanosecond(Option<i64>),
     /// struct of nested ScalarValue (boxed to reduce size_of(ScalarValue))
     #[allow(clippy::box_collection)]
     Struct(Option<Box<Vec<ScalarValue>>>, Box<Vec<Field>>),
+    /// dictionary-encoded value with the given key type
+    Dictionary(Box<DataType>, Box<ScalarValue>),
 }
 
 // manual implementation of `PartialEq` that uses OrderedFloat to
@@ -150,12 +186,30 @@ impl PartialEq for ScalarValue {
             (Binary(_), _) => false,
             (LargeBinary(v1), LargeBinary(v2)) => v1.eq(v2),
             (LargeBinary(_), _) => false,
-            (List(v1, t1), List(v2, t2)) => v1.eq(v2) && t1.eq(t2),
-            (List(_, _), _) => false,
+            (FixedSizeBinary(s1, v1), FixedSizeBinary(s2, v2)) => s1.eq(s2) && v1.eq(v2),
+            (FixedSizeBinary(_, _), _) => false,
+            // element nullability describes the column the value came from,
+            // not the value itself, so it does not take part in comparisons
+            (List(v1, t1, _), List(v2, t2, _)) => v1.eq(v2) && t1.eq(t2),
+            (List(_, _, _), _) => false,
+            (FixedSizeList(v1, t1, l1, _), FixedSizeList(v2, t2, l2, _)) => {
+                v1.eq(v2) && t1.eq(t2) && l1.eq(l2)
+            }
+            (FixedSizeList(_, _, _, _), _) => false,
+            (Map(v1, f1, s1), Map(v2, f2, s2)) => v1.eq(v2) && f1.eq(f2) && s1.eq(s2),
+            (Map(_, _, _), _) => false,
             (Date32(v1), Date32(v2)) => v1.eq(v2),
             (Date32(_), _) => false,
             (Date64(v1), Date64(v2)) => v1.eq(v2),
             (Date64(_), _) => false,
+            (Time32Second(v1), Time32Second(v2)) => v1.eq(v2),
+            (Time32Second(_), _) => false,
+            (Time32Millisecond(v1), Time32Millisecond(v2)) => v1.eq(v2),
+            (Time32Millisecond(_), _) => false,
+            (Time64Microsecond(v1), Time64Microsecond(v2)) => v1.eq(v2),
+            (Time64Microsecond(_), _) => false,
+            (Time64Nanosecond(v1), Time64Nanosecond(v2)) => v1.eq(v2),
+            (Time64Nanosecond(_), _) => false,
             (TimestampSecond(v1, _), TimestampSecond(v2, _)) => v1.eq(v2),
             (TimestampSecond(_, _), _) => false,
             (TimestampMillisecond(v1, _), TimestampMillisecond(v2, _)) => v1.eq(v2),
@@ -170,8 +224,18 @@ impl PartialEq for ScalarValue {
             (IntervalDayTime(_), _) => false,
             (IntervalMonthDayNano(v1), IntervalMonthDayNano(v2)) => v1.eq(v2),
             (IntervalMonthDayNano(_), _) => false,
+            (DurationSecond(v1), DurationSecond(v2)) => v1.eq(v2),
+            (DurationSecond(_), _) => false,
+            (DurationMillisecond(v1), DurationMillisecond(v2)) => v1.eq(v2),
+            (DurationMillisecond(_), _) => false,
+            (DurationMicrosecond(v1), DurationMicrosecond(v2)) => v1.eq(v2),
+            (DurationMicrosecond(_), _) => false,
+            (DurationNanosecond(v1), DurationNanosecond(v2)) => v1.eq(v2),
+            (DurationNanosecond(_), _) => false,
             (Struct(v1, t1), Struct(v2, t2)) => v1.eq(v2) && t1.eq(t2),
             (Struct(_, _), _) => false,
+            (Dictionary(k1, v1), Dictionary(k2, v2)) => k1.eq(k2) && v1.eq(v2),
+            (Dictionary(_, _), _) => false,
             (Null, Null) => true,
             (Null, _) => false,
         }
@@ -234,18 +298,50 @@ impl PartialOrd for ScalarValue {
             (Binary(_), _) => None,
             (LargeBinary(v1), LargeBinary(v2)) => v1.partial_cmp(v2),
             (LargeBinary(_), _) => None,
-            (List(v1, t1), List(v2, t2)) => {
+            (FixedSizeBinary(s1, v1), FixedSizeBinary(s2, v2)) => {
+                if s1.eq(s2) {
+                    v1.partial_cmp(v2)
+                } else {
+                    None
+                }
+            }
+            (FixedSizeBinary(_, _), _) => None,
+            (List(v1, t1, _), List(v2, t2, _)) => {
                 if t1.eq(t2) {
                     v1.partial_cmp(v2)
                 } else {
                     None
                 }
             }
-            (List(_, _), _) => None,
+            (List(_, _, _), _) => None,
+            (FixedSizeList(v1, t1, l1, _), FixedSizeList(v2, t2, l2, _)) => {
+                if t1.eq(t2) && l1.eq(l2) {
+                    v1.partial_cmp(v2)
+                } else {
+                    None
+                }
+            }
+            (FixedSizeList(_, _, _, _), _) => None,
+            (Map(v1, f1, s1), Map(v2, f2, s2)) => {
+                if f1.eq(f2) && s1.eq(s2) {
+                    v1.partial_cmp(v2)
+                } else {
+                    None
+                }
+            }
+            (Map(_, _, _), _) => None,
             (Date32(v1), Date32(v2)) => v1.partial_cmp(v2),
             (Date32(_), _) => None,
             (Date64(v1), Date64(v2)) => v1.partial_cmp(v2),
             (Date64(_), _) => None,
+            (Time32Second(v1), Time32Second(v2)) => v1.partial_cmp(v2),
+            (Time32Second(_), _) => None,
+            (Time32Millisecond(v1), Time32Millisecond(v2)) => v1.partial_cmp(v2),
+            (Time32Millisecond(_), _) => None,
+            (Time64Microsecond(v1), Time64Microsecond(v2)) => v1.partial_cmp(v2),
+            (Time64Microsecond(_), _) => None,
+            (Time64Nanosecond(v1), Time64Nanosecond(v2)) => v1.partial_cmp(v2),
+            (Time64Nanosecond(_), _) => None,
             (TimestampSecond(v1, _), TimestampSecond(v2, _)) => v1.partial_cmp(v2),
             (TimestampSecond(_, _), _) => None,
             (TimestampMillisecond(v1, _), TimestampMillisecond(v2, _)) => {
@@ -266,6 +362,14 @@ impl PartialOrd for ScalarValue {
             (IntervalDayTime(_), _) => None,
             (IntervalMonthDayNano(v1), IntervalMonthDayNano(v2)) => v1.partial_cmp(v2),
             (IntervalMonthDayNano(_), _) => None,
+            (DurationSecond(v1), DurationSecond(v2)) => v1.partial_cmp(v2),
+            (DurationSecond(_), _) => None,
+            (DurationMillisecond(v1), DurationMillisecond(v2)) => v1.partial_cmp(v2),
+            (DurationMillisecond(_), _) => None,
+            (DurationMicrosecond(v1), DurationMicrosecond(v2)) => v1.partial_cmp(v2),
+            (DurationMicrosecond(_), _) => None,
+            (DurationNanosecond(v1), DurationNanosecond(v2)) => v1.partial_cmp(v2),
+            (DurationNanosecond(_), _) => None,
             (Struct(v1, t1), Struct(v2, t2)) => {
                 if t1.eq(t2) {
                     v1.partial_cmp(v2)
@@ -274,6 +378,14 @@ impl PartialOrd for ScalarValue {
                 }
             }
             (Struct(_, _), _) => None,
+            (Dictionary(k1, v1), Dictionary(k2, v2)) => {
+                if k1.eq(k2) {
+                    v1.partial_cmp(v2)
+                } else {
+                    None
+                }
+            }
+            (Dictionary(_, _), _) => None,
             (Null, Null) => Some(Ordering::Equal),
             (Null, _) => None,
         }
@@ -314,12 +426,30 @@ impl std::hash::Hash for ScalarValue {
             LargeUtf8(v) => v.hash(state),
             Binary(v) => v.hash(state),
             LargeBinary(v) => v.hash(state),
-            List(v, t) => {
+            FixedSizeBinary(size, v) => {
+                size.hash(state);
+                v.hash(state);
+            }
+            List(v, t, _) => {
+                v.hash(state);
+                t.hash(state);
+            }
+            FixedSizeList(v, t, l, _) => {
                 v.hash(state);
                 t.hash(state);
+                l.hash(state);
+            }
+            Map(v, f, s) => {
+                v.hash(state);
+                f.hash(state);
+                s.hash(state);
             }
             Date32(v) => v.hash(state),
             Date64(v) => v.hash(state),
+            Time32Second(v) => v.hash(state),
+            Time32Millisecond(v) => v.hash(state),
+            Time64Microsecond(v) => v.hash(state),
+            Time64Nanosecond(v) => v.hash(state),
             TimestampSecond(v, _) => v.hash(state),
             TimestampMillisecond(v, _) => v.hash(state),
             TimestampMicrosecond(v, _) => v.hash(state),
@@ -327,10 +457,18 @@ impl std::hash::Hash for ScalarValue {
             IntervalYearMonth(v) => v.hash(state),
             IntervalDayTime(v) => v.hash(state),
             IntervalMonthDayNano(v) => v.hash(state),
+            DurationSecond(v) => v.hash(state),
+            DurationMillisecond(v) => v.hash(state),
+            DurationMicrosecond(v) => v.hash(state),
+            DurationNanosecond(v) => v.hash(state),
             Struct(v, t) => {
                 v.hash(state);
                 t.hash(state);
             }
+            Dictionary(k, v) => {
+                k.hash(state);
+                v.hash(state);
+            }
             // stable hash for Null value
             Null => 1.hash(state),
         }
@@ -362,6 +500,87 @@ fn get_dict_value<K: ArrowDictionaryKeyType>(
     Ok((dict_array.values(), Some(values_index)))
 }
 
+// build a dictionary array of `size` rows that all reference the single
+// entry of the values dictionary holding `value`
+fn dict_from_scalar<K: ArrowDictionaryKeyType>(
+    value: &ScalarValue,
+    size: usize,
+) -> Result<ArrayRef> {
+    let values = value.try_to_array_of_size(1)?;
+    let key = if value.is_null() {
+        None
+    } else {
+        K::Native::from_usize(0)
+    };
+    let keys = repeat(key).take(size).collect::<PrimitiveArray<K>>();
+    Ok(Arc::new(DictionaryArray::<K>::try_new(
+        &keys,
+        values.as_ref(),
+    )?))
+}
+
+// compare an integer with a float without rounding the integer, ordering
+// nulls first. Returns `None` if the float is NaN.
+fn cmp_integer_float(i: Option<i128>, f: Option<f64>) -> Option<Ordering> {
+    let (i, f) = match (i, f) {
+        (Some(i), Some(f)) => (i, f),
+        (i, f) => return Some(i.is_some().cmp(&f.is_some())),
+    };
+    // every i128 lies in [-2^127, 2^127)
+    let limit = 2f64.powi(127);
+    if f.is_nan() {
+        None
+    } else if f >= limit {
+        Some(Ordering::Less)
+    } else if f < -limit {
+        Some(Ordering::Greater)
+    } else {
+        // the truncated float fits in an i128, and compares equal to the
+        // integer only if the integer has no fractional part
+        let truncated = f.trunc();
+        match i.cmp(&(truncated as i128)) {
+            Ordering::Equal => truncated.partial_cmp(&f),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+// compare an integer with a decimal of the given scale without rescaling the
+// integer, ordering nulls first. Returns `None` if the scale is too large.
+fn cmp_integer_decimal(
+    i: Option<i128>,
+    d: Option<i128>,
+    scale: usize,
+) -> Option<Ordering> {
+    let (i, d) = match (i, d) {
+        (Some(i), Some(d)) => (i, d),
+        (i, d) => return Some(i.is_some().cmp(&d.is_some())),
+    };
+    let factor = 10_i128.checked_pow(scale as u32)?;
+    match i.cmp(&d.div_euclid(factor)) {
+        Ordering::Equal if d.rem_euclid(factor) > 0 => Some(Ordering::Less),
+        ordering => Some(ordering),
+    }
+}
+
+// replace the type of a list `array` by `data_type`, which may only differ
+// in the nullability of the list elements
+fn with_list_type(array: ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    if array.data_type() == data_type {
+        return Ok(array);
+    }
+    let data = array.data();
+    let mut builder = ArrayDataBuilder::new(data_type.clone())
+        .len(data.len())
+        .offset(data.offset())
+        .buffers(data.buffers().to_vec())
+        .child_data(data.child_data().to_vec());
+    if let Some(nulls) = data.null_buffer() {
+        builder = builder.null_bit_buffer(nulls.clone());
+    }
+    Ok(make_array(builder.build()?))
+}
+
 macro_rules! typed_cast_tz {
     ($array:expr, $index:expr, $ARRAYTYPE:ident, $SCALAR:ident, $TZ:expr) => {{
         let array = $array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
@@ -390,14 +609,14 @@ macro_rules! build_list {
         match $VALUES {
             // the return on the macro is necessary, to short-circuit and return ArrayRef
             None => {
-                return new_null_array(
+                return Ok(new_null_array(
                     &DataType::List(Box::new(Field::new(
                         "item",
                         DataType::$SCALAR_TY,
                         true,
                     ))),
                     $SIZE,
-                )
+                ))
             }
             Some(values) => {
                 build_values_list!($VALUE_BUILDER_TY, $SCALAR_TY, values.as_ref(), $SIZE)
@@ -411,14 +630,14 @@ macro_rules! build_timestamp_list {
         match $VALUES {
             // the return on the macro is necessary, to short-circuit and return ArrayRef
             None => {
-                return new_null_array(
+                return Ok(new_null_array(
                     &DataType::List(Box::new(Field::new(
                         "item",
                         DataType::Timestamp($TIME_UNIT, $TIME_ZONE),
                         true,
                     ))),
                     $SIZE,
-                )
+                ))
             }
             Some(values) => {
                 let values = values.as_ref();
@@ -527,6 +746,159 @@ macro_rules! build_array_from_option {
     }};
 }
 
+// Returns true if `size` repetitions of a value of `value_len` bytes do not
+// fit in the i32 offsets of a StringArray or BinaryArray
+fn exceeds_i32_offsets(value_len: usize, size: usize) -> bool {
+    value_len
+        .checked_mul(size)
+        .map_or(true, |total| total > i32::MAX as usize)
+}
+
+// Creates the error returned when `size` repetitions of a Utf8, Binary or
+// Map `value` do not fit in the i32 offsets of its array
+fn offsets_overflow_error(value: &ScalarValue, size: usize) -> DataFusionError {
+    DataFusionError::Execution(format!(
+        "Cannot repeat {:?} {} times: the array offsets would overflow i32",
+        value.get_datatype(),
+        size
+    ))
+}
+
+// Creates the error returned when a checked arithmetic operation overflows,
+// naming both operands so that the failing literal fold can be traced
+fn overflow_error(lhs: &ScalarValue, op: &str, rhs: &ScalarValue) -> DataFusionError {
+    DataFusionError::Execution(format!("Overflow evaluating {:?} {} {:?}", lhs, op, rhs))
+}
+
+// Adds or subtracts two decimals of the same precision and scale
+fn decimal_add_sub(
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    op: &str,
+    f: fn(i128, i128) -> Option<i128>,
+) -> Result<ScalarValue> {
+    match (lhs, rhs) {
+        (ScalarValue::Decimal128(l, p1, s1), ScalarValue::Decimal128(r, p2, s2))
+            if p1 == p2 && s1 == s2 =>
+        {
+            match (l, r) {
+                (Some(l), Some(r)) => f(*l, *r)
+                    // the result must still fit in the precision
+                    .filter(|v| v.unsigned_abs() < 10_u128.pow(*p1 as u32))
+                    .map(|v| ScalarValue::Decimal128(Some(v), *p1, *s1))
+                    .ok_or_else(|| overflow_error(lhs, op, rhs)),
+                _ => Ok(ScalarValue::Decimal128(None, *p1, *s1)),
+            }
+        }
+        _ => Err(DataFusionError::Internal(format!(
+            "Decimal precision and scale must match to evaluate {:?} {} {:?}",
+            lhs, op, rhs
+        ))),
+    }
+}
+
+// Multiplies two decimals. The scale of the result is the sum of the input
+// scales, which must not exceed the maximum decimal precision.
+fn decimal_mul(lhs: &ScalarValue, rhs: &ScalarValue) -> Result<ScalarValue> {
+    match (lhs, rhs) {
+        (ScalarValue::Decimal128(l, p1, s1), ScalarValue::Decimal128(r, p2, s2)) => {
+            let scale = s1 + s2;
+            if scale > DECIMAL_MAX_PRECISION {
+                return Err(DataFusionError::Internal(format!(
+                    "Scale {} of {:?} * {:?} exceeds the maximum decimal precision {}",
+                    scale, lhs, rhs, DECIMAL_MAX_PRECISION
+                )));
+            }
+            let precision = (p1 + p2 + 1).min(DECIMAL_MAX_PRECISION);
+            match (l, r) {
+                (Some(l), Some(r)) => l
+                    .checked_mul(*r)
+                    // the result must fit in the precision
+                    .filter(|v| v.unsigned_abs() < 10_u128.pow(precision as u32))
+                    .map(|v| ScalarValue::Decimal128(Some(v), precision, scale))
+                    .ok_or_else(|| overflow_error(lhs, "*", rhs)),
+                _ => Ok(ScalarValue::Decimal128(None, precision, scale)),
+            }
+        }
+        _ => Err(DataFusionError::Internal(format!(
+            "Expected decimals to evaluate {:?} * {:?}",
+            lhs, rhs
+        ))),
+    }
+}
+
+macro_rules! checked_arithmetic {
+    ($LHS:expr, $RHS:expr, $L:expr, $R:expr, $SCALAR:ident, $CHECKED_FN:ident, $OP:expr) => {{
+        match ($L, $R) {
+            (Some(l), Some(r)) => l
+                .$CHECKED_FN(*r)
+                .map(|v| ScalarValue::$SCALAR(Some(v)))
+                .ok_or_else(|| overflow_error($LHS, $OP, $RHS)),
+            _ => Ok(ScalarValue::$SCALAR(None)),
+        }
+    }};
+}
+
+macro_rules! float_arithmetic {
+    ($L:expr, $R:expr, $SCALAR:ident, $FLOAT_OP:tt) => {{
+        match ($L, $R) {
+            (Some(l), Some(r)) => Ok(ScalarValue::$SCALAR(Some(*l $FLOAT_OP *r))),
+            _ => Ok(ScalarValue::$SCALAR(None)),
+        }
+    }};
+}
+
+// Evaluates a binary arithmetic operation between two scalars of the same
+// primitive numeric type. Integer operations are checked and report an
+// overflow error, float operations follow IEEE semantics.
+macro_rules! primitive_arithmetic {
+    ($LHS:expr, $RHS:expr, $OP:expr, $CHECKED_FN:ident, $FLOAT_OP:tt) => {{
+        let lhs: &ScalarValue = $LHS;
+        let rhs: &ScalarValue = $RHS;
+        match (lhs, rhs) {
+            (ScalarValue::Null, other) | (other, ScalarValue::Null)
+                if matches!(other, ScalarValue::Null) || other.is_numeric() =>
+            {
+                ScalarValue::try_from(&other.get_datatype())
+            }
+            (ScalarValue::Float64(l), ScalarValue::Float64(r)) => {
+                float_arithmetic!(l, r, Float64, $FLOAT_OP)
+            }
+            (ScalarValue::Float32(l), ScalarValue::Float32(r)) => {
+                float_arithmetic!(l, r, Float32, $FLOAT_OP)
+            }
+            (ScalarValue::Int8(l), ScalarValue::Int8(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, Int8, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::Int16(l), ScalarValue::Int16(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, Int16, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::Int32(l), ScalarValue::Int32(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, Int32, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::Int64(l), ScalarValue::Int64(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, Int64, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::UInt8(l), ScalarValue::UInt8(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, UInt8, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::UInt16(l), ScalarValue::UInt16(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, UInt16, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::UInt32(l), ScalarValue::UInt32(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, UInt32, $CHECKED_FN, $OP)
+            }
+            (ScalarValue::UInt64(l), ScalarValue::UInt64(r)) => {
+                checked_arithmetic!(lhs, rhs, l, r, UInt64, $CHECKED_FN, $OP)
+            }
+            _ => Err(DataFusionError::Internal(format!(
+                "Unsupported arithmetic operation {:?} {} {:?}",
+                lhs, $OP, rhs
+            ))),
+        }
+    }};
+}
+
 macro_rules! eq_array_primitive {
     ($array:expr, $index:expr, $ARRAYTYPE:ident, $VALUE:expr) => {{
         let array = $array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
@@ -538,6 +910,17 @@ macro_rules! eq_array_primitive {
     }};
 }
 
+/// Hash algorithms supported by [`ScalarValue::digest`]
+#[cfg(feature = "crypto_expressions")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
 impl ScalarValue {
     /// Create a decimal Scalar from value/precision and scale.
     pub fn try_new_decimal128(
@@ -554,6 +937,357 @@ impl ScalarValue {
             precision, scale
         )));
     }
+
+    /// Create a list scalar of `values` of type `element_type`, or a null
+    /// list if `values` is `None`
+    pub fn new_list(values: Option<Vec<ScalarValue>>, element_type: DataType) -> Self {
+        if let Some(values) = &values {
+            debug_assert!(
+                values.iter().all(|v| v.get_datatype() == element_type),
+                "List elements {:?} must all be of type {:?}",
+                values,
+                element_type
+            );
+        }
+        ScalarValue::List(values.map(Box::new), Box::new(element_type), true)
+    }
+
+    /// Create a non-null list scalar of `values`, using the type of the
+    /// first value as the element type. All values must have the same type,
+    /// and there must be at least one value to infer the type from.
+    pub fn new_list_from_iter(
+        values: impl IntoIterator<Item = ScalarValue>,
+    ) -> Result<Self> {
+        let values = values.into_iter().collect::<Vec<_>>();
+        let element_type = match values.first() {
+            Some(value) => value.get_datatype(),
+            None => {
+                return Err(DataFusionError::Internal(
+                    "Cannot infer the element type of an empty list".to_string(),
+                ))
+            }
+        };
+        if let Some(value) = values.iter().find(|v| v.get_datatype() != element_type) {
+            return Err(DataFusionError::Internal(format!(
+                "Inconsistent types in list: expected {:?}, got {:?}",
+                element_type, value
+            )));
+        }
+        Ok(Self::new_list(Some(values), element_type))
+    }
+
+    /// Create the zero value of the numeric `data_type`
+    pub fn new_zero(data_type: &DataType) -> Result<Self> {
+        Self::new_numeric(data_type, 0)
+    }
+
+    /// Create the one value of the numeric `data_type`. For decimals, one
+    /// is represented as `10^scale`.
+    pub fn new_one(data_type: &DataType) -> Result<Self> {
+        Self::new_numeric(data_type, 1)
+    }
+
+    fn new_numeric(data_type: &DataType, value: u8) -> Result<Self> {
+        Ok(match data_type {
+            DataType::Int8 => ScalarValue::Int8(Some(value as i8)),
+            DataType::Int16 => ScalarValue::Int16(Some(value as i16)),
+            DataType::Int32 => ScalarValue::Int32(Some(value as i32)),
+            DataType::Int64 => ScalarValue::Int64(Some(value as i64)),
+            DataType::UInt8 => ScalarValue::UInt8(Some(value)),
+            DataType::UInt16 => ScalarValue::UInt16(Some(value as u16)),
+            DataType::UInt32 => ScalarValue::UInt32(Some(value as u32)),
+            DataType::UInt64 => ScalarValue::UInt64(Some(value as u64)),
+            DataType::Float32 => ScalarValue::Float32(Some(value as f32)),
+            DataType::Float64 => ScalarValue::Float64(Some(value as f64)),
+            DataType::Decimal(precision, scale) => {
+                // the integer part of a decimal has `precision - scale` digits
+                if value != 0 && scale >= precision {
+                    return Err(DataFusionError::Plan(format!(
+                        "Cannot represent {} as a decimal with precision {} and scale {}",
+                        value, precision, scale
+                    )));
+                }
+                ScalarValue::try_new_decimal128(
+                    value as i128 * 10_i128.pow(*scale as u32),
+                    *precision,
+                    *scale,
+                )?
+            }
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "Cannot create the number {} of non-numeric type {:?}",
+                    value, other
+                )))
+            }
+        })
+    }
+
+    /// Generate the values from `start` (inclusive) to `stop` (exclusive)
+    /// separated by `step`, which may be negative for a descending series.
+    /// All arguments must be non-null integers or floats of the same type,
+    /// and the series may not have more than [MAX_NUMERIC_SERIES_LEN] values.
+    pub fn numeric_series(
+        start: ScalarValue,
+        stop: ScalarValue,
+        step: ScalarValue,
+    ) -> Result<Vec<ScalarValue>> {
+        let check_len = |len: f64| {
+            if len > MAX_NUMERIC_SERIES_LEN as f64 {
+                Err(DataFusionError::Plan(format!(
+                    "numeric_series would generate {} values, more than the maximum of {}",
+                    len, MAX_NUMERIC_SERIES_LEN
+                )))
+            } else {
+                Ok(len as usize)
+            }
+        };
+
+        macro_rules! int_series {
+            ($SCALAR:ident, $NATIVE:ident, $START:expr, $STOP:expr, $STEP:expr) => {{
+                // i128 holds the difference of any two 64 bit integers
+                let (start, stop, step) = ($START as i128, $STOP as i128, $STEP as i128);
+                let len = if (step > 0 && start < stop) || (step < 0 && start > stop) {
+                    let (distance, step) = ((stop - start).abs(), step.abs());
+                    (distance + step - 1) / step
+                } else {
+                    0
+                };
+                let len = check_len(len as f64)? as i128;
+                (0..len)
+                    .map(|i| ScalarValue::$SCALAR(Some((start + i * step) as $NATIVE)))
+                    .collect()
+            }};
+        }
+
+        macro_rules! float_series {
+            ($SCALAR:ident, $NATIVE:ident, $START:expr, $STOP:expr, $STEP:expr) => {{
+                let (start, stop, step) = ($START, $STOP, $STEP);
+                if !(start.is_finite() && stop.is_finite() && step.is_finite()) {
+                    return Err(DataFusionError::Plan(format!(
+                        "numeric_series requires finite arguments, got {}, {} and {}",
+                        start, stop, step
+                    )));
+                }
+                check_len(((stop as f64 - start as f64) / step as f64).ceil())?;
+                // multiply instead of accumulating so errors do not add up
+                let mut values = vec![];
+                let mut i: u64 = 0;
+                loop {
+                    let current = start + i as $NATIVE * step;
+                    if !((step > 0.0 && current < stop) || (step < 0.0 && current > stop))
+                    {
+                        break;
+                    }
+                    values.push(ScalarValue::$SCALAR(Some(current)));
+                    i += 1;
+                }
+                values
+            }};
+        }
+
+        if start.get_datatype() != stop.get_datatype()
+            || start.get_datatype() != step.get_datatype()
+        {
+            return Err(DataFusionError::Plan(format!(
+                "numeric_series requires arguments of the same type, got {:?}, {:?} and {:?}",
+                start, stop, step
+            )));
+        }
+        if step.is_zero() {
+            return Err(DataFusionError::Plan(
+                "numeric_series step cannot be zero".to_string(),
+            ));
+        }
+
+        Ok(match (&start, &stop, &step) {
+            (
+                ScalarValue::Int8(Some(a)),
+                ScalarValue::Int8(Some(b)),
+                ScalarValue::Int8(Some(s)),
+            ) => int_series!(Int8, i8, *a, *b, *s),
+            (
+                ScalarValue::Int16(Some(a)),
+                ScalarValue::Int16(Some(b)),
+                ScalarValue::Int16(Some(s)),
+            ) => int_series!(Int16, i16, *a, *b, *s),
+            (
+                ScalarValue::Int32(Some(a)),
+                ScalarValue::Int32(Some(b)),
+                ScalarValue::Int32(Some(s)),
+            ) => int_series!(Int32, i32, *a, *b, *s),
+            (
+                ScalarValue::Int64(Some(a)),
+                ScalarValue::Int64(Some(b)),
+                ScalarValue::Int64(Some(s)),
+            ) => int_series!(Int64, i64, *a, *b, *s),
+            (
+                ScalarValue::UInt8(Some(a)),
+                ScalarValue::UInt8(Some(b)),
+                ScalarValue::UInt8(Some(s)),
+            ) => int_series!(UInt8, u8, *a, *b, *s),
+            (
+                ScalarValue::UInt16(Some(a)),
+                ScalarValue::UInt16(Some(b)),
+                ScalarValue::UInt16(Some(s)),
+            ) => int_series!(UInt16, u16, *a, *b, *s),
+            (
+                ScalarValue::UInt32(Some(a)),
+                ScalarValue::UInt32(Some(b)),
+                ScalarValue::UInt32(Some(s)),
+            ) => int_series!(UInt32, u32, *a, *b, *s),
+            (
+                ScalarValue::UInt64(Some(a)),
+                ScalarValue::UInt64(Some(b)),
+                ScalarValue::UInt64(Some(s)),
+            ) => int_series!(UInt64, u64, *a, *b, *s),
+            (
+                ScalarValue::Float32(Some(a)),
+                ScalarValue::Float32(Some(b)),
+                ScalarValue::Float32(Some(s)),
+            ) => float_series!(Float32, f32, *a, *b, *s),
+            (
+                ScalarValue::Float64(Some(a)),
+                ScalarValue::Float64(Some(b)),
+                ScalarValue::Float64(Some(s)),
+            ) => float_series!(Float64, f64, *a, *b, *s),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "numeric_series requires non-null integer or float arguments, got {:?}, {:?} and {:?}",
+                    start, stop, step
+                )))
+            }
+        })
+    }
+
+    /// Parse `value` as a scalar of `target_type`. Numbers are parsed with
+    /// their `FromStr` implementation, booleans from `true` or `false`
+    /// (ignoring case), dates from `YYYY-MM-DD` and timestamps from
+    /// ISO-8601 strings. Other types go through the arrow cast kernel.
+    pub fn try_from_string(value: String, target_type: &DataType) -> Result<Self> {
+        let parse_error = |e: &dyn fmt::Display| {
+            DataFusionError::Plan(format!(
+                "Cannot parse '{}' as {:?}: {}",
+                value, target_type, e
+            ))
+        };
+        macro_rules! parse {
+            ($SCALAR:ident) => {
+                ScalarValue::$SCALAR(Some(value.parse().map_err(|e| parse_error(&e))?))
+            };
+        }
+        let parse_date =
+            || NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|e| parse_error(&e));
+        // days from 0001-01-01 to 1970-01-01
+        const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+        Ok(match target_type {
+            DataType::Boolean => match value.to_ascii_lowercase().as_str() {
+                "true" => ScalarValue::Boolean(Some(true)),
+                "false" => ScalarValue::Boolean(Some(false)),
+                _ => return Err(parse_error(&"expected 'true' or 'false'")),
+            },
+            DataType::Int8 => parse!(Int8),
+            DataType::Int16 => parse!(Int16),
+            DataType::Int32 => parse!(Int32),
+            DataType::Int64 => parse!(Int64),
+            DataType::UInt8 => parse!(UInt8),
+            DataType::UInt16 => parse!(UInt16),
+            DataType::UInt32 => parse!(UInt32),
+            DataType::UInt64 => parse!(UInt64),
+            DataType::Float32 => parse!(Float32),
+            DataType::Float64 => parse!(Float64),
+            DataType::Date32 => {
+                let days = parse_date()?.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE;
+                ScalarValue::Date32(Some(days))
+            }
+            DataType::Date64 => {
+                let days = parse_date()?.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE;
+                ScalarValue::Date64(Some(days as i64 * 86_400_000))
+            }
+            DataType::Timestamp(unit, tz) => {
+                let nanos =
+                    string_to_timestamp_nanos(&value).map_err(|e| parse_error(&e))?;
+                let tz = tz.clone();
+                match unit {
+                    TimeUnit::Second => ScalarValue::TimestampSecond(
+                        Some(nanos.div_euclid(1_000_000_000)),
+                        tz,
+                    ),
+                    TimeUnit::Millisecond => ScalarValue::TimestampMillisecond(
+                        Some(nanos.div_euclid(1_000_000)),
+                        tz,
+                    ),
+                    TimeUnit::Microsecond => ScalarValue::TimestampMicrosecond(
+                        Some(nanos.div_euclid(1_000)),
+                        tz,
+                    ),
+                    TimeUnit::Nanosecond => {
+                        ScalarValue::TimestampNanosecond(Some(nanos), tz)
+                    }
+                }
+            }
+            DataType::Utf8 => ScalarValue::Utf8(Some(value)),
+            DataType::LargeUtf8 => ScalarValue::LargeUtf8(Some(value)),
+            _ => ScalarValue::Utf8(Some(value.clone()))
+                .cast_to(target_type)
+                .map_err(|e| parse_error(&e))?,
+        })
+    }
+
+    /// Estimate the memory used by this value in bytes: the size of the
+    /// enum itself plus the heap memory it owns, such as the bytes of
+    /// strings and binary values and, recursively, the elements of lists
+    /// and structs.
+    pub fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self {
+                ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => {
+                    v.as_ref().map(|s| s.capacity()).unwrap_or_default()
+                }
+                ScalarValue::Binary(v)
+                | ScalarValue::LargeBinary(v)
+                | ScalarValue::FixedSizeBinary(_, v) => {
+                    v.as_ref().map(|b| b.capacity()).unwrap_or_default()
+                }
+                ScalarValue::TimestampSecond(_, tz)
+                | ScalarValue::TimestampMillisecond(_, tz)
+                | ScalarValue::TimestampMicrosecond(_, tz)
+                | ScalarValue::TimestampNanosecond(_, tz) => {
+                    tz.as_ref().map(|tz| tz.capacity()).unwrap_or_default()
+                }
+                ScalarValue::List(values, _, _)
+                | ScalarValue::FixedSizeList(values, _, _, _) => {
+                    Self::size_of_boxed_vec(values) + std::mem::size_of::<DataType>()
+                }
+                ScalarValue::Map(values, _, _) => {
+                    Self::size_of_boxed_vec(values) + std::mem::size_of::<Field>()
+                }
+                ScalarValue::Struct(values, fields) => {
+                    Self::size_of_boxed_vec(values)
+                        + std::mem::size_of::<Vec<Field>>()
+                        + fields.capacity() * std::mem::size_of::<Field>()
+                }
+                ScalarValue::Dictionary(key_type, value) => {
+                    std::mem::size_of_val(key_type.as_ref()) + value.size()
+                }
+                _ => 0,
+            }
+    }
+
+    /// Heap memory held by the boxed values of a list or struct
+    #[allow(clippy::box_collection)]
+    fn size_of_boxed_vec(values: &Option<Box<Vec<ScalarValue>>>) -> usize {
+        values
+            .as_ref()
+            .map(|values| {
+                std::mem::size_of::<Vec<ScalarValue>>()
+                    + (values.capacity() - values.len())
+                        * std::mem::size_of::<ScalarValue>()
+                    + values.iter().map(|v| v.size()).sum::<usize>()
+            })
+            .unwrap_or_default()
+    }
+
     /// Getter for the `DataType` of the value
     pub fn get_datatype(&self) -> DataType {
         match self {
@@ -587,13 +1321,25 @@ impl ScalarValue {
             ScalarValue::LargeUtf8(_) => DataType::LargeUtf8,
             ScalarValue::Binary(_) => DataType::Binary,
             ScalarValue::LargeBinary(_) => DataType::LargeBinary,
-            ScalarValue::List(_, data_type) => DataType::List(Box::new(Field::new(
-                "item",
-                data_type.as_ref().clone(),
-                true,
-            ))),
+            ScalarValue::FixedSizeBinary(size, _) => DataType::FixedSizeBinary(*size),
+            ScalarValue::List(_, data_type, nullable) => DataType::List(Box::new(
+                Field::new("item", data_type.as_ref().clone(), *nullable),
+            )),
+            ScalarValue::FixedSizeList(_, data_type, list_size, nullable) => {
+                DataType::FixedSizeList(
+                    Box::new(Field::new("item", data_type.as_ref().clone(), *nullable)),
+                    *list_size,
+                )
+            }
+            ScalarValue::Map(_, field, keys_sorted) => {
+                DataType::Map(field.clone(), *keys_sorted)
+            }
             ScalarValue::Date32(_) => DataType::Date32,
             ScalarValue::Date64(_) => DataType::Date64,
+            ScalarValue::Time32Second(_) => DataType::Time32(TimeUnit::Second),
+            ScalarValue::Time32Millisecond(_) => DataType::Time32(TimeUnit::Millisecond),
+            ScalarValue::Time64Microsecond(_) => DataType::Time64(TimeUnit::Microsecond),
+            ScalarValue::Time64Nanosecond(_) => DataType::Time64(TimeUnit::Nanosecond),
             ScalarValue::IntervalYearMonth(_) => {
                 DataType::Interval(IntervalUnit::YearMonth)
             }
@@ -601,7 +1347,20 @@ impl ScalarValue {
             ScalarValue::IntervalMonthDayNano(_) => {
                 DataType::Interval(IntervalUnit::MonthDayNano)
             }
+            ScalarValue::DurationSecond(_) => DataType::Duration(TimeUnit::Second),
+            ScalarValue::DurationMillisecond(_) => {
+                DataType::Duration(TimeUnit::Millisecond)
+            }
+            ScalarValue::DurationMicrosecond(_) => {
+                DataType::Duration(TimeUnit::Microsecond)
+            }
+            ScalarValue::DurationNanosecond(_) => {
+                DataType::Duration(TimeUnit::Nanosecond)
+            }
             ScalarValue::Struct(_, fields) => DataType::Struct(fields.as_ref().clone()),
+            ScalarValue::Dictionary(key_type, value) => {
+                DataType::Dictionary(key_type.clone(), Box::new(value.get_datatype()))
+            }
             ScalarValue::Null => DataType::Null,
         }
     }
@@ -624,65 +1383,1244 @@ impl ScalarValue {
             ScalarValue::Decimal128(Some(v), precision, scale) => {
                 ScalarValue::Decimal128(Some(-v), *precision, *scale)
             }
+            ScalarValue::IntervalYearMonth(v) => {
+                ScalarValue::IntervalYearMonth(v.map(|v| -v))
+            }
+            // the components of packed intervals are negated separately
+            ScalarValue::IntervalDayTime(v) => ScalarValue::IntervalDayTime(v.map(|v| {
+                let days = (v >> 32) as i32;
+                let millis = v as i32;
+                ((-days as i64) << 32) | (-millis as u32 as i64)
+            })),
+            ScalarValue::IntervalMonthDayNano(v) => {
+                ScalarValue::IntervalMonthDayNano(v.map(|v| {
+                    let months = (v >> 96) as i32;
+                    let days = (v >> 64) as i32;
+                    let nanos = v as i64;
+                    ((-months as u32 as i128) << 96)
+                        | ((-days as u32 as i128) << 64)
+                        | (-nanos as u64 as i128)
+                }))
+            }
             _ => panic!("Cannot run arithmetic negate on scalar value: {:?}", self),
         }
     }
 
-    /// whether this value is null or not.
-    pub fn is_null(&self) -> bool {
-        matches!(
-            *self,
-            ScalarValue::Null
-                | ScalarValue::Boolean(None)
-                | ScalarValue::UInt8(None)
-                | ScalarValue::UInt16(None)
-                | ScalarValue::UInt32(None)
-                | ScalarValue::UInt64(None)
-                | ScalarValue::Int8(None)
-                | ScalarValue::Int16(None)
-                | ScalarValue::Int32(None)
-                | ScalarValue::Int64(None)
-                | ScalarValue::Float32(None)
-                | ScalarValue::Float64(None)
-                | ScalarValue::Date32(None)
-                | ScalarValue::Date64(None)
-                | ScalarValue::Utf8(None)
-                | ScalarValue::LargeUtf8(None)
-                | ScalarValue::List(None, _)
-                | ScalarValue::TimestampSecond(None, _)
-                | ScalarValue::TimestampMillisecond(None, _)
-                | ScalarValue::TimestampMicrosecond(None, _)
-                | ScalarValue::TimestampNanosecond(None, _)
-                | ScalarValue::Struct(None, _)
-                | ScalarValue::Decimal128(None, _, _) // For decimal type, the value is null means ScalarValue::Decimal128 is null.
-        )
+    /// Add `other` to this value, returning an error on integer overflow.
+    /// Decimals must have the same precision and scale.
+    pub fn add(&self, other: &ScalarValue) -> Result<ScalarValue> {
+        match (self, other) {
+            (ScalarValue::Decimal128(..), ScalarValue::Decimal128(..)) => {
+                decimal_add_sub(self, other, "+", i128::checked_add)
+            }
+            _ => primitive_arithmetic!(self, other, "+", checked_add, +),
+        }
     }
 
-    /// Converts a scalar value into an 1-row array.
-    pub fn to_array(&self) -> ArrayRef {
-        self.to_array_of_size(1)
+    /// Like [`Self::add`], but returns `None` on integer overflow or if the
+    /// values cannot be added, which avoids formatting an error message in
+    /// tight loops. Adding a null yields a null of the same type.
+    pub fn checked_add(&self, other: &ScalarValue) -> Option<ScalarValue> {
+        macro_rules! checked_add {
+            ($L:expr, $R:expr, $SCALAR:ident) => {
+                match ($L, $R) {
+                    (Some(l), Some(r)) => {
+                        l.checked_add(*r).map(|v| ScalarValue::$SCALAR(Some(v)))
+                    }
+                    _ => Some(ScalarValue::$SCALAR(None)),
+                }
+            };
+        }
+        match (self, other) {
+            (ScalarValue::Float64(l), ScalarValue::Float64(r)) => {
+                Some(ScalarValue::Float64(l.zip(*r).map(|(l, r)| l + r)))
+            }
+            (ScalarValue::Float32(l), ScalarValue::Float32(r)) => {
+                Some(ScalarValue::Float32(l.zip(*r).map(|(l, r)| l + r)))
+            }
+            (ScalarValue::Int8(l), ScalarValue::Int8(r)) => checked_add!(l, r, Int8),
+            (ScalarValue::Int16(l), ScalarValue::Int16(r)) => checked_add!(l, r, Int16),
+            (ScalarValue::Int32(l), ScalarValue::Int32(r)) => checked_add!(l, r, Int32),
+            (ScalarValue::Int64(l), ScalarValue::Int64(r)) => checked_add!(l, r, Int64),
+            (ScalarValue::UInt8(l), ScalarValue::UInt8(r)) => checked_add!(l, r, UInt8),
+            (ScalarValue::UInt16(l), ScalarValue::UInt16(r)) => {
+                checked_add!(l, r, UInt16)
+            }
+            (ScalarValue::UInt32(l), ScalarValue::UInt32(r)) => {
+                checked_add!(l, r, UInt32)
+            }
+            (ScalarValue::UInt64(l), ScalarValue::UInt64(r)) => {
+                checked_add!(l, r, UInt64)
+            }
+            (ScalarValue::Decimal128(l, p1, s1), ScalarValue::Decimal128(r, p2, s2))
+                if p1 == p2 && s1 == s2 =>
+            {
+                match (l, r) {
+                    (Some(l), Some(r)) => l
+                        .checked_add(*r)
+                        // the result must still fit in the precision
+                        .filter(|v| v.unsigned_abs() < 10_u128.pow(*p1 as u32))
+                        .map(|v| ScalarValue::Decimal128(Some(v), *p1, *s1)),
+                    _ => Some(ScalarValue::Decimal128(None, *p1, *s1)),
+                }
+            }
+            _ => None,
+        }
     }
 
-    /// Converts an iterator of references [`ScalarValue`] into an [`ArrayRef`]
-    /// corresponding to those values. For example,
-    ///
-    /// Returns an error if the iterator is empty or if the
-    /// [`ScalarValue`]s are not all the same type
-    ///
-    /// Example
-    /// ```
-    /// use datafusion_common::ScalarValue;
-    /// use arrow::array::{ArrayRef, BooleanArray};
-    ///
-    /// let scalars = vec![
-    ///   ScalarValue::Boolean(Some(true)),
-    ///   ScalarValue::Boolean(None),
-    ///   ScalarValue::Boolean(Some(false)),
-    /// ];
-    ///
-    /// // Build an Array from the list of ScalarValues
-    /// let array = ScalarValue::iter_to_array(scalars.into_iter())
-    ///   .unwrap();
+    /// Subtract `other` from this value, returning an error on integer
+    /// overflow. Decimals must have the same precision and scale.
+    pub fn sub(&self, other: &ScalarValue) -> Result<ScalarValue> {
+        match (self, other) {
+            (ScalarValue::Decimal128(..), ScalarValue::Decimal128(..)) => {
+                decimal_add_sub(self, other, "-", i128::checked_sub)
+            }
+            _ => primitive_arithmetic!(self, other, "-", checked_sub, -),
+        }
+    }
+
+    /// Multiply this value by `other`, returning an error on integer
+    /// overflow. The scale of the product of two decimals is the sum of
+    /// their scales.
+    pub fn mul(&self, other: &ScalarValue) -> Result<ScalarValue> {
+        match (self, other) {
+            (ScalarValue::Decimal128(..), ScalarValue::Decimal128(..)) => {
+                decimal_mul(self, other)
+            }
+            _ => primitive_arithmetic!(self, other, "*", checked_mul, *),
+        }
+    }
+
+    /// Divide this value by `other`. Integer division by zero and overflow
+    /// are errors, float division follows IEEE semantics and may produce
+    /// infinity or NaN.
+    pub fn div(&self, other: &ScalarValue) -> Result<ScalarValue> {
+        if !self.is_null() && other.is_zero() && !other.is_floating() {
+            return Err(DataFusionError::ArrowError(ArrowError::DivideByZero));
+        }
+        primitive_arithmetic!(self, other, "/", checked_div, /)
+    }
+
+    /// Return the remainder of dividing this value by `other`, which has the
+    /// sign of this value like Rust's `%`. Integer division by zero is an
+    /// error, the float remainder of a division by zero is NaN.
+    pub fn rem(&self, other: &ScalarValue) -> Result<ScalarValue> {
+        if !self.is_null() && other.is_zero() && !other.is_floating() {
+            return Err(DataFusionError::ArrowError(ArrowError::DivideByZero));
+        }
+        primitive_arithmetic!(self, other, "%", checked_rem, %)
+    }
+
+    /// Return the absolute value of a numeric value. The absolute value of
+    /// the minimum value of a signed integer does not fit in its type and is
+    /// an error.
+    pub fn abs(&self) -> Result<ScalarValue> {
+        macro_rules! checked_abs {
+            ($V:expr, $SCALAR:ident) => {
+                match $V {
+                    Some(v) => v
+                        .checked_abs()
+                        .map(|v| ScalarValue::$SCALAR(Some(v)))
+                        .ok_or_else(|| {
+                            DataFusionError::Internal(format!(
+                                "Overflow evaluating abs({:?})",
+                                self
+                            ))
+                        }),
+                    None => Ok(ScalarValue::$SCALAR(None)),
+                }
+            };
+        }
+        match self {
+            ScalarValue::Int8(v) => checked_abs!(v, Int8),
+            ScalarValue::Int16(v) => checked_abs!(v, Int16),
+            ScalarValue::Int32(v) => checked_abs!(v, Int32),
+            ScalarValue::Int64(v) => checked_abs!(v, Int64),
+            ScalarValue::Float32(v) => Ok(ScalarValue::Float32(v.map(f32::abs))),
+            ScalarValue::Float64(v) => Ok(ScalarValue::Float64(v.map(f64::abs))),
+            ScalarValue::Decimal128(v, precision, scale) => Ok(ScalarValue::Decimal128(
+                v.map(i128::abs),
+                *precision,
+                *scale,
+            )),
+            ScalarValue::UInt8(_)
+            | ScalarValue::UInt16(_)
+            | ScalarValue::UInt32(_)
+            | ScalarValue::UInt64(_) => Ok(self.clone()),
+            other => Err(DataFusionError::Plan(format!(
+                "abs expects a numeric argument, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Replace all non-overlapping occurrences of `from` with `to` in a
+    /// Utf8 or LargeUtf8 value. If any operand is null the result is null,
+    /// and an empty `from` leaves the string unchanged.
+    pub fn string_replace(
+        &self,
+        from: &ScalarValue,
+        to: &ScalarValue,
+    ) -> Result<ScalarValue> {
+        let as_str = |value: &ScalarValue| -> Result<Option<String>> {
+            match value {
+                ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => Ok(v.clone()),
+                other => Err(DataFusionError::Plan(format!(
+                    "string_replace expects string arguments, got {:?}",
+                    other
+                ))),
+            }
+        };
+        let (from, to) = (as_str(from)?, as_str(to)?);
+        let replace = |s: &Option<String>| match (s, &from, &to) {
+            (Some(s), Some(from), Some(_)) if from.is_empty() => Some(s.clone()),
+            (Some(s), Some(from), Some(to)) => Some(s.replace(from.as_str(), to)),
+            _ => None,
+        };
+        match self {
+            ScalarValue::Utf8(v) => Ok(ScalarValue::Utf8(replace(v))),
+            ScalarValue::LargeUtf8(v) => Ok(ScalarValue::LargeUtf8(replace(v))),
+            other => Err(DataFusionError::Plan(format!(
+                "string_replace expects string arguments, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Return the 1-based character position of the first occurrence of
+    /// `substr` in a Utf8 or LargeUtf8 value as an `Int32`, or 0 if it does
+    /// not occur. An empty `substr` is found at position 1.
+    pub fn string_position(&self, substr: &ScalarValue) -> Result<ScalarValue> {
+        let as_str = |value: &ScalarValue| -> Result<Option<String>> {
+            match value {
+                ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => Ok(v.clone()),
+                other => Err(DataFusionError::Plan(format!(
+                    "string_position expects string arguments, got {:?}",
+                    other
+                ))),
+            }
+        };
+        let position = match (as_str(self)?, as_str(substr)?) {
+            (Some(s), Some(substr)) => Some(
+                s.find(&substr)
+                    .map(|byte_index| s[..byte_index].chars().count() as i32 + 1)
+                    .unwrap_or(0),
+            ),
+            _ => None,
+        };
+        Ok(ScalarValue::Int32(position))
+    }
+
+    /// Return the first `n` characters of a Utf8 or LargeUtf8 value. A
+    /// negative `n` returns all but the last `|n|` characters.
+    pub fn string_left(&self, n: i64) -> Result<ScalarValue> {
+        self.map_string("string_left", |s| {
+            let len = s.chars().count() as i64;
+            let take = if n < 0 { (len + n).max(0) } else { n };
+            s.chars().take(take as usize).collect()
+        })
+    }
+
+    /// Return the last `n` characters of a Utf8 or LargeUtf8 value. A
+    /// negative `n` returns all but the first `|n|` characters.
+    pub fn string_right(&self, n: i64) -> Result<ScalarValue> {
+        self.map_string("string_right", |s| {
+            let len = s.chars().count();
+            let count = usize::try_from(n.unsigned_abs()).unwrap_or(usize::MAX);
+            let skip = if n < 0 {
+                count
+            } else {
+                len.saturating_sub(count)
+            };
+            s.chars().skip(skip).collect()
+        })
+    }
+
+    /// Return a `Boolean` that is true if a Utf8 or LargeUtf8 value matches
+    /// the regular expression `pattern`. `flags` may contain `i` (case
+    /// insensitive), `m` (multi-line) and `s` (`.` matches new lines). If
+    /// the value or pattern is null the result is null.
+    #[cfg(feature = "regex_expressions")]
+    pub fn regexp_is_match(
+        &self,
+        pattern: &ScalarValue,
+        flags: Option<&str>,
+    ) -> Result<ScalarValue> {
+        let value = match self {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => v,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "regexp_is_match expects a string argument, got {:?}",
+                    other
+                )))
+            }
+        };
+        let pattern = match pattern {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => v,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "regexp_is_match expects a string pattern, got {:?}",
+                    other
+                )))
+            }
+        };
+        let (value, pattern) = match (value, pattern) {
+            (Some(value), Some(pattern)) => (value, pattern),
+            _ => return Ok(ScalarValue::Boolean(None)),
+        };
+
+        let mut builder = regex::RegexBuilder::new(pattern);
+        for flag in flags.unwrap_or_default().chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "Unsupported regexp_is_match flag '{}'",
+                        other
+                    )))
+                }
+            };
+        }
+        let regex = builder.build().map_err(|e| {
+            DataFusionError::Plan(format!(
+                "Invalid regular expression '{}': {}",
+                pattern, e
+            ))
+        })?;
+        Ok(ScalarValue::Boolean(Some(regex.is_match(value))))
+    }
+
+    /// Hash a Utf8, LargeUtf8, Binary or LargeBinary value with `algorithm`
+    /// and return the digest as a lowercase hex encoded Utf8 value. A null
+    /// value produces a null Utf8.
+    #[cfg(feature = "crypto_expressions")]
+    pub fn digest(&self, algorithm: DigestAlgorithm) -> Result<ScalarValue> {
+        use md5::Md5;
+        use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+
+        let bytes = match self {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => {
+                v.as_ref().map(|s| s.as_bytes())
+            }
+            ScalarValue::Binary(v) | ScalarValue::LargeBinary(v) => v.as_deref(),
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "digest expects a string or binary argument, got {:?}",
+                    other
+                )))
+            }
+        };
+        Ok(ScalarValue::Utf8(bytes.map(|bytes| {
+            let digest = match algorithm {
+                DigestAlgorithm::Md5 => Md5::digest(bytes).to_vec(),
+                DigestAlgorithm::Sha224 => Sha224::digest(bytes).to_vec(),
+                DigestAlgorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+                DigestAlgorithm::Sha384 => Sha384::digest(bytes).to_vec(),
+                DigestAlgorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+            };
+            digest.iter().map(|b| format!("{:02x}", b)).collect()
+        })))
+    }
+
+    /// Split a Utf8 or LargeUtf8 value on `delimiter` and return the
+    /// 1-based `field`-th part, or an empty string if there are fewer parts.
+    /// An empty `delimiter` returns the whole string as the first part. If
+    /// either string is null the result is null.
+    pub fn split_part(&self, delimiter: &ScalarValue, field: i64) -> Result<ScalarValue> {
+        if field <= 0 {
+            return Err(DataFusionError::Plan(format!(
+                "split_part field position must be greater than zero, got {}",
+                field
+            )));
+        }
+        let delimiter = match delimiter {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => v,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "split_part expects a string delimiter, got {:?}",
+                    other
+                )))
+            }
+        };
+        let delimiter = match delimiter {
+            Some(delimiter) => delimiter,
+            None => {
+                return self
+                    .map_string("split_part", str::to_string)
+                    .and_then(|v| ScalarValue::try_from(&v.get_datatype()))
+            }
+        };
+        let index = (field - 1) as usize;
+        self.map_string("split_part", |s| {
+            if delimiter.is_empty() {
+                return if index == 0 {
+                    s.to_string()
+                } else {
+                    "".to_string()
+                };
+            }
+            s.split(delimiter.as_str())
+                .nth(index)
+                .unwrap_or_default()
+                .to_string()
+        })
+    }
+
+    /// Reverse the characters of a Utf8 or LargeUtf8 value. This reverses
+    /// Unicode code points, not grapheme clusters, so a combining character
+    /// ends up before the character it modified.
+    pub fn string_reverse(&self) -> Result<ScalarValue> {
+        self.map_string("string_reverse", |s| s.chars().rev().collect())
+    }
+
+    /// Uppercase the first character of every word of a Utf8 or LargeUtf8
+    /// value and lowercase the rest, where a word is a run of alphanumeric
+    /// characters. Uses Unicode case mapping, so a character may map to
+    /// more than one.
+    pub fn initcap(&self) -> Result<ScalarValue> {
+        self.map_string("initcap", |s| {
+            let mut result = String::with_capacity(s.len());
+            let mut in_word = false;
+            for c in s.chars() {
+                if in_word {
+                    result.extend(c.to_lowercase());
+                } else {
+                    result.extend(c.to_uppercase());
+                }
+                in_word = c.is_alphanumeric();
+            }
+            result
+        })
+    }
+
+    /// Left-pad a Utf8 or LargeUtf8 value to `length` characters with
+    /// `fill` (a space if `None`), truncating it if it is longer than
+    /// `length`. A null value or fill produces null.
+    pub fn lpad(&self, length: i64, fill: Option<&ScalarValue>) -> Result<ScalarValue> {
+        self.pad("lpad", length, fill, true)
+    }
+
+    /// Right-pad a Utf8 or LargeUtf8 value to `length` characters with
+    /// `fill` (a space if `None`), truncating it if it is longer than
+    /// `length`. A null value or fill produces null.
+    pub fn rpad(&self, length: i64, fill: Option<&ScalarValue>) -> Result<ScalarValue> {
+        self.pad("rpad", length, fill, false)
+    }
+
+    /// Shared implementation of [`Self::lpad`] and [`Self::rpad`]
+    fn pad(
+        &self,
+        name: &str,
+        length: i64,
+        fill: Option<&ScalarValue>,
+        left: bool,
+    ) -> Result<ScalarValue> {
+        let fill = match fill {
+            None => Some(" ".to_string()),
+            Some(ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v)) => v.clone(),
+            Some(other) => {
+                return Err(DataFusionError::Plan(format!(
+                    "{} expects a string fill, got {:?}",
+                    name, other
+                )))
+            }
+        };
+        let fill = match fill {
+            Some(fill) => fill,
+            None => {
+                return self
+                    .map_string(name, str::to_string)
+                    .and_then(|v| ScalarValue::try_from(&v.get_datatype()))
+            }
+        };
+        let length = length.max(0) as usize;
+        self.map_string(name, |s| {
+            let len = s.chars().count();
+            if len >= length || fill.is_empty() {
+                return s.chars().take(length).collect();
+            }
+            let padding = fill.chars().cycle().take(length - len);
+            if left {
+                padding.chain(s.chars()).collect()
+            } else {
+                s.chars().chain(padding).collect()
+            }
+        })
+    }
+
+    /// Return the Unicode code point of the first character of a Utf8 or
+    /// LargeUtf8 value as an `Int32`, or 0 for the empty string.
+    pub fn ascii(&self) -> Result<ScalarValue> {
+        match self {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => Ok(ScalarValue::Int32(
+                v.as_ref()
+                    .map(|s| s.chars().next().map(|c| c as i32).unwrap_or(0)),
+            )),
+            other => Err(DataFusionError::Plan(format!(
+                "ascii expects a string argument, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Return the character with the integer Unicode code point of this
+    /// value as a single character Utf8 value.
+    pub fn chr(&self) -> Result<ScalarValue> {
+        let code_point = match self {
+            ScalarValue::Int8(v) => v.map(i64::from),
+            ScalarValue::Int16(v) => v.map(i64::from),
+            ScalarValue::Int32(v) => v.map(i64::from),
+            ScalarValue::Int64(v) => *v,
+            ScalarValue::UInt8(v) => v.map(i64::from),
+            ScalarValue::UInt16(v) => v.map(i64::from),
+            ScalarValue::UInt32(v) => v.map(i64::from),
+            ScalarValue::UInt64(v) => v.map(|v| i64::try_from(v).unwrap_or(i64::MAX)),
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "chr expects an integer argument, got {:?}",
+                    other
+                )))
+            }
+        };
+        code_point
+            .map(|code_point| {
+                u32::try_from(code_point)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(|c| c.to_string())
+                    .ok_or_else(|| {
+                        DataFusionError::Plan(format!(
+                            "Invalid Unicode code point {} for chr",
+                            code_point
+                        ))
+                    })
+            })
+            .transpose()
+            .map(ScalarValue::Utf8)
+    }
+
+    /// Apply `f` to the value of a Utf8 or LargeUtf8 scalar, keeping the
+    /// string type and propagating nulls
+    fn map_string(&self, name: &str, f: impl Fn(&str) -> String) -> Result<ScalarValue> {
+        match self {
+            ScalarValue::Utf8(v) => Ok(ScalarValue::Utf8(v.as_deref().map(f))),
+            ScalarValue::LargeUtf8(v) => Ok(ScalarValue::LargeUtf8(v.as_deref().map(f))),
+            other => Err(DataFusionError::Plan(format!(
+                "{} expects a string argument, got {:?}",
+                name, other
+            ))),
+        }
+    }
+
+    /// Cast this value to `target`. Lossless numeric widening (e.g. `Int32`
+    /// to `Int64` or `Float64`) is done directly on the value, other casts
+    /// go through the arrow cast kernel. Unlike [`Self::cast_to_checked`],
+    /// a value the cast kernel cannot convert (e.g. a string that is not a
+    /// number) is an error.
+    pub fn cast_to(&self, target: &DataType) -> Result<ScalarValue> {
+        self.cast_with_options(target, &CastOptions { safe: false })
+    }
+
+    // cast this value to `target` with the arrow cast kernel, skipping the
+    // kernel for values of the target type and lossless numeric widening
+    fn cast_with_options(
+        &self,
+        target: &DataType,
+        cast_options: &CastOptions,
+    ) -> Result<ScalarValue> {
+        if &self.get_datatype() == target {
+            return Ok(self.clone());
+        }
+        if let Some(widened) = self.widen_numeric(target) {
+            return Ok(widened);
+        }
+        if !can_cast_types(&self.get_datatype(), target) {
+            return Err(DataFusionError::Plan(format!(
+                "Cannot cast {:?} to {:?}",
+                self, target
+            )));
+        }
+        let array =
+            cast_with_options(&self.try_to_array_of_size(1)?, target, cast_options)?;
+        ScalarValue::try_from_array(&array, 0)
+    }
+
+    /// Compare this value with `other`, which may have a different numeric
+    /// type. Integers are compared exactly with integers, decimals and
+    /// floats, other values are first promoted to a common type (decimals to
+    /// the larger scale). Nulls order before any other value.
+    pub fn try_cmp(&self, other: &ScalarValue) -> Result<Ordering> {
+        let incomparable = || {
+            DataFusionError::Plan(format!("Cannot compare {:?} with {:?}", self, other))
+        };
+        match (self.integer_value(), other.integer_value()) {
+            (Some(v1), Some(v2)) => return Ok(v1.cmp(&v2)),
+            (Some(v1), None) => {
+                if let ScalarValue::Decimal128(v2, _, s2) = other {
+                    return cmp_integer_decimal(v1, *v2, *s2).ok_or_else(incomparable);
+                }
+                if let Some(v2) = other.float_value() {
+                    return cmp_integer_float(v1, v2).ok_or_else(incomparable);
+                }
+            }
+            (None, Some(v2)) => {
+                if let ScalarValue::Decimal128(v1, _, s1) = self {
+                    return cmp_integer_decimal(v2, *v1, *s1)
+                        .map(Ordering::reverse)
+                        .ok_or_else(incomparable);
+                }
+                if let Some(v1) = self.float_value() {
+                    return cmp_integer_float(v2, v1)
+                        .map(Ordering::reverse)
+                        .ok_or_else(incomparable);
+                }
+            }
+            (None, None) => {}
+        }
+        if let (ScalarValue::Decimal128(v1, _, s1), ScalarValue::Decimal128(v2, _, s2)) =
+            (self, other)
+        {
+            let scale = *s1.max(s2);
+            let rescale = |v: &Option<i128>, s: usize| match v {
+                Some(v) => 10_i128
+                    .checked_pow((scale - s) as u32)
+                    .and_then(|factor| v.checked_mul(factor))
+                    .map(Some)
+                    .ok_or_else(incomparable),
+                None => Ok(None),
+            };
+            return Ok(rescale(v1, *s1)?.cmp(&rescale(v2, *s2)?));
+        }
+
+        let (left, right) =
+            Self::coerce_pair(self.clone(), other.clone()).map_err(|_| incomparable())?;
+        left.partial_cmp(&right).ok_or_else(incomparable)
+    }
+
+    // the value of an integer as an i128, or `None` if this is not an integer
+    fn integer_value(&self) -> Option<Option<i128>> {
+        use ScalarValue::*;
+        Some(match self {
+            Int8(v) => v.map(Into::into),
+            Int16(v) => v.map(Into::into),
+            Int32(v) => v.map(Into::into),
+            Int64(v) => v.map(Into::into),
+            UInt8(v) => v.map(Into::into),
+            UInt16(v) => v.map(Into::into),
+            UInt32(v) => v.map(Into::into),
+            UInt64(v) => v.map(Into::into),
+            _ => return None,
+        })
+    }
+
+    // the value of a float as an f64, or `None` if this is not a float
+    fn float_value(&self) -> Option<Option<f64>> {
+        match self {
+            ScalarValue::Float32(v) => Some(v.map(Into::into)),
+            ScalarValue::Float64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Cast `a` and `b` to a common type. Values of the same type are
+    /// returned unchanged, a numeric value is widened losslessly to the
+    /// type of the other where possible, and any other pair of numeric
+    /// values is cast to `Float64`. Returns an error if the types have no
+    /// common type.
+    pub fn coerce_pair(
+        a: ScalarValue,
+        b: ScalarValue,
+    ) -> Result<(ScalarValue, ScalarValue)> {
+        let is_numeric = |v: &ScalarValue| {
+            v.is_signed_integer()
+                || v.is_unsigned_integer()
+                || v.is_floating()
+                || matches!(v, ScalarValue::Decimal128(_, _, _))
+        };
+        let (a_type, b_type) = (a.get_datatype(), b.get_datatype());
+        if a_type == b_type {
+            Ok((a, b))
+        } else if let Some(a) = a.widen_numeric(&b_type) {
+            Ok((a, b))
+        } else if let Some(b) = b.widen_numeric(&a_type) {
+            Ok((a, b))
+        } else if is_numeric(&a) && is_numeric(&b) {
+            Ok((
+                a.cast_to(&DataType::Float64)?,
+                b.cast_to(&DataType::Float64)?,
+            ))
+        } else {
+            Err(DataFusionError::Plan(format!(
+                "No common type for {:?} and {:?}",
+                a_type, b_type
+            )))
+        }
+    }
+
+    /// Compare two lists element by element, promoting the elements to a
+    /// common type like [`Self::try_cmp`]. A null list orders before any
+    /// other list and a list orders before the longer lists it is a prefix
+    /// of. Errors if either value is not a list or if the element types have
+    /// no common type.
+    pub fn try_cmp_list(&self, other: &ScalarValue) -> Result<Ordering> {
+        let (v1, t1, v2, t2) = match (self, other) {
+            (ScalarValue::List(v1, t1, _), ScalarValue::List(v2, t2, _)) => {
+                (v1, t1, v2, t2)
+            }
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "try_cmp_list expects two lists, got {:?} and {:?}",
+                    self, other
+                )))
+            }
+        };
+        let cmp_elements = |left: &ScalarValue, right: &ScalarValue| match (left, right) {
+            (ScalarValue::List(_, _, _), ScalarValue::List(_, _, _)) => {
+                left.try_cmp_list(right)
+            }
+            _ => left.try_cmp(right),
+        };
+
+        // check the element types even if there are no elements to compare
+        cmp_elements(
+            &ScalarValue::try_from(t1.as_ref())?,
+            &ScalarValue::try_from(t2.as_ref())?,
+        )?;
+        match (v1, v2) {
+            (None, None) => Ok(Ordering::Equal),
+            (None, Some(_)) => Ok(Ordering::Less),
+            (Some(_), None) => Ok(Ordering::Greater),
+            (Some(v1), Some(v2)) => {
+                for (left, right) in v1.iter().zip(v2.iter()) {
+                    match cmp_elements(left, right)? {
+                        Ordering::Equal => {}
+                        ordering => return Ok(ordering),
+                    }
+                }
+                Ok(v1.len().cmp(&v2.len()))
+            }
+        }
+    }
+
+    /// Widen an integer or float to a type that can represent all of its
+    /// values, or return `None` if this is not such a cast
+    fn widen_numeric(&self, target: &DataType) -> Option<ScalarValue> {
+        use ScalarValue::*;
+        Some(match (self, target) {
+            (Int8(v), DataType::Int16) => Int16(v.map(Into::into)),
+            (Int8(v), DataType::Int32) => Int32(v.map(Into::into)),
+            (Int8(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (Int8(v), DataType::Float32) => Float32(v.map(Into::into)),
+            (Int8(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (Int16(v), DataType::Int32) => Int32(v.map(Into::into)),
+            (Int16(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (Int16(v), DataType::Float32) => Float32(v.map(Into::into)),
+            (Int16(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (Int32(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (Int32(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (UInt8(v), DataType::UInt16) => UInt16(v.map(Into::into)),
+            (UInt8(v), DataType::UInt32) => UInt32(v.map(Into::into)),
+            (UInt8(v), DataType::UInt64) => UInt64(v.map(Into::into)),
+            (UInt8(v), DataType::Int16) => Int16(v.map(Into::into)),
+            (UInt8(v), DataType::Int32) => Int32(v.map(Into::into)),
+            (UInt8(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (UInt8(v), DataType::Float32) => Float32(v.map(Into::into)),
+            (UInt8(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (UInt16(v), DataType::UInt32) => UInt32(v.map(Into::into)),
+            (UInt16(v), DataType::UInt64) => UInt64(v.map(Into::into)),
+            (UInt16(v), DataType::Int32) => Int32(v.map(Into::into)),
+            (UInt16(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (UInt16(v), DataType::Float32) => Float32(v.map(Into::into)),
+            (UInt16(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (UInt32(v), DataType::UInt64) => UInt64(v.map(Into::into)),
+            (UInt32(v), DataType::Int64) => Int64(v.map(Into::into)),
+            (UInt32(v), DataType::Float64) => Float64(v.map(Into::into)),
+            (Float32(v), DataType::Float64) => Float64(v.map(Into::into)),
+            _ => return None,
+        })
+    }
+
+    /// Cast this value to `target`, returning a null of the target type if
+    /// the value cannot be represented without loss (e.g. out of range
+    /// narrowing, a float with a fractional part cast to an integer, or a
+    /// string that is not a number). Returns an error only if values of this
+    /// type can never be cast to `target`.
+    pub fn cast_to_checked(&self, target: &DataType) -> Result<ScalarValue> {
+        let casted = self.cast_with_options(target, &CastOptions { safe: true })?;
+        // the cast kernel truncates fractions and rounds to the nearest
+        // float, so numeric casts must compare equal to the original value
+        let is_numeric = |v: &ScalarValue| {
+            v.is_signed_integer()
+                || v.is_unsigned_integer()
+                || v.is_floating()
+                || matches!(v, ScalarValue::Decimal128(_, _, _))
+        };
+        if is_numeric(self)
+            && is_numeric(&casted)
+            && !casted.is_null()
+            && matches!(casted.try_cmp(self), Ok(ordering) if ordering != Ordering::Equal)
+        {
+            return ScalarValue::try_from(target);
+        }
+        Ok(casted)
+    }
+
+    /// Return the total number of nanoseconds of an interval without a
+    /// month component. Months are not a fixed duration, so intervals with
+    /// a non-zero number of months are an error.
+    pub fn interval_as_nanos(&self) -> Result<Option<i128>> {
+        const NANOS_PER_MILLI: i128 = 1_000_000;
+        const NANOS_PER_DAY: i128 = 86_400 * 1_000_000_000;
+        let months_error = || {
+            DataFusionError::Plan(format!(
+                "Cannot convert interval {:?} with months to nanoseconds",
+                self
+            ))
+        };
+        match self {
+            ScalarValue::IntervalDayTime(v) => Ok(v.map(|v| {
+                let days = (v >> 32) as i32;
+                let millis = v as i32;
+                days as i128 * NANOS_PER_DAY + millis as i128 * NANOS_PER_MILLI
+            })),
+            ScalarValue::IntervalMonthDayNano(Some(v)) => {
+                let months = (v >> 96) as i32;
+                let days = (v >> 64) as i32;
+                let nanos = *v as i64;
+                if months != 0 {
+                    return Err(months_error());
+                }
+                Ok(Some(days as i128 * NANOS_PER_DAY + nanos as i128))
+            }
+            ScalarValue::IntervalYearMonth(Some(0)) => Ok(Some(0)),
+            ScalarValue::IntervalYearMonth(Some(_)) => Err(months_error()),
+            ScalarValue::IntervalMonthDayNano(None)
+            | ScalarValue::IntervalYearMonth(None) => Ok(None),
+            other => Err(DataFusionError::Plan(format!(
+                "Expected an interval, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Create an `IntervalMonthDayNano` without months from a total number
+    /// of nanoseconds, returning an error if the interval spans more than
+    /// `i32::MAX` days.
+    pub fn interval_from_nanos(nanos: i128) -> Result<ScalarValue> {
+        const NANOS_PER_DAY: i128 = 86_400 * 1_000_000_000;
+        let days = i32::try_from(nanos / NANOS_PER_DAY).map_err(|_| {
+            DataFusionError::Execution(format!(
+                "Overflow creating an interval of {} nanoseconds",
+                nanos
+            ))
+        })?;
+        let nanos = (nanos % NANOS_PER_DAY) as i64;
+        Ok(ScalarValue::IntervalMonthDayNano(Some(
+            ((days as u32 as i128) << 64) | (nanos as u64 as i128),
+        )))
+    }
+
+    /// Borrows the string of a non-null `Utf8` or `LargeUtf8` value,
+    /// returns `None` for any other value
+    pub fn try_as_str(&self) -> Option<&str> {
+        match self {
+            ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+                Some(v.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Hashes this value with SipHash-2-4 keyed by `seed`. The hasher is fed
+    /// a tag for the type of the value followed by a fixed-width little
+    /// endian encoding of the value, so unlike the [`Hash`](std::hash::Hash)
+    /// implementation the result does not depend on the platform. As for
+    /// `Hash`, floats are hashed as [`OrderedFloat`]s, and only the values
+    /// (not the element types) of nested values are hashed.
+    ///
+    /// Unlike hashing with a `RandomState`, the result does not change across
+    /// process runs and can be persisted, e.g. in distinct count sketches.
+    /// The algorithm will not change without a major version bump.
+    pub fn hash_value(&self, seed: u64) -> u64 {
+        use std::hash::Hasher;
+
+        // `SipHasher` is deprecated in favor of `DefaultHasher`, whose
+        // algorithm is unspecified and may change between Rust releases
+        #[allow(deprecated)]
+        let mut hasher = std::hash::SipHasher::new_with_keys(seed, 0);
+        self.hash_stable(&mut hasher);
+        hasher.finish()
+    }
+
+    // feed `hasher` the stable encoding of this value used by `hash_value`
+    fn hash_stable<H: std::hash::Hasher>(&self, hasher: &mut H) {
+        use ScalarValue::*;
+
+        // a tag and the fixed-width bytes of a possibly null value
+        fn fixed<H: std::hash::Hasher, B: AsRef<[u8]>>(
+            hasher: &mut H,
+            tag: u8,
+            bytes: Option<B>,
+        ) {
+            match bytes {
+                Some(bytes) => {
+                    hasher.write(&[tag, 1]);
+                    hasher.write(bytes.as_ref());
+                }
+                None => hasher.write(&[tag, 0]),
+            }
+        }
+        // a tag and the length prefixed bytes of a possibly null value
+        fn variable<H: std::hash::Hasher>(hasher: &mut H, tag: u8, bytes: Option<&[u8]>) {
+            fixed(hasher, tag, bytes.map(|b| (b.len() as u64).to_le_bytes()));
+            if let Some(bytes) = bytes {
+                hasher.write(bytes);
+            }
+        }
+        // a tag and the length prefixed values of a possibly null nested value
+        fn nested<H: std::hash::Hasher>(
+            hasher: &mut H,
+            tag: u8,
+            values: &Option<Box<Vec<ScalarValue>>>,
+        ) {
+            fixed(
+                hasher,
+                tag,
+                values.as_ref().map(|v| (v.len() as u64).to_le_bytes()),
+            );
+            for value in values.iter().flat_map(|v| v.iter()) {
+                value.hash_stable(hasher);
+            }
+        }
+        // floats equal as `OrderedFloat`s have the same bits
+        let f32_bits = |v: f32| {
+            if v.is_nan() {
+                f32::NAN.to_bits()
+            } else if v == 0.0 {
+                0
+            } else {
+                v.to_bits()
+            }
+        };
+        let f64_bits = |v: f64| {
+            if v.is_nan() {
+                f64::NAN.to_bits()
+            } else if v == 0.0 {
+                0
+            } else {
+                v.to_bits()
+            }
+        };
+
+        match self {
+            Null => hasher.write(&[0]),
+            Boolean(v) => fixed(hasher, 1, v.map(|v| [v as u8])),
+            Float32(v) => fixed(hasher, 2, v.map(|v| f32_bits(v).to_le_bytes())),
+            Float64(v) => fixed(hasher, 3, v.map(|v| f64_bits(v).to_le_bytes())),
+            Decimal128(v, p, s) => {
+                fixed(hasher, 4, v.map(i128::to_le_bytes));
+                hasher.write(&(*p as u64).to_le_bytes());
+                hasher.write(&(*s as u64).to_le_bytes());
+            }
+            Int8(v) => fixed(hasher, 5, v.map(i8::to_le_bytes)),
+            Int16(v) => fixed(hasher, 6, v.map(i16::to_le_bytes)),
+            Int32(v) => fixed(hasher, 7, v.map(i32::to_le_bytes)),
+            Int64(v) => fixed(hasher, 8, v.map(i64::to_le_bytes)),
+            UInt8(v) => fixed(hasher, 9, v.map(u8::to_le_bytes)),
+            UInt16(v) => fixed(hasher, 10, v.map(u16::to_le_bytes)),
+            UInt32(v) => fixed(hasher, 11, v.map(u32::to_le_bytes)),
+            UInt64(v) => fixed(hasher, 12, v.map(u64::to_le_bytes)),
+            Utf8(v) => variable(hasher, 13, v.as_deref().map(str::as_bytes)),
+            LargeUtf8(v) => variable(hasher, 14, v.as_deref().map(str::as_bytes)),
+            Binary(v) => variable(hasher, 15, v.as_deref()),
+            LargeBinary(v) => variable(hasher, 16, v.as_deref()),
+            FixedSizeBinary(size, v) => {
+                variable(hasher, 17, v.as_deref());
+                hasher.write(&size.to_le_bytes());
+            }
+            List(v, _, _) => nested(hasher, 18, v),
+            FixedSizeList(v, _, _, _) => nested(hasher, 19, v),
+            Map(v, _, _) => nested(hasher, 20, v),
+            Date32(v) => fixed(hasher, 21, v.map(i32::to_le_bytes)),
+            Date64(v) => fixed(hasher, 22, v.map(i64::to_le_bytes)),
+            Time32Second(v) => fixed(hasher, 23, v.map(i32::to_le_bytes)),
+            Time32Millisecond(v) => fixed(hasher, 24, v.map(i32::to_le_bytes)),
+            Time64Microsecond(v) => fixed(hasher, 25, v.map(i64::to_le_bytes)),
+            Time64Nanosecond(v) => fixed(hasher, 26, v.map(i64::to_le_bytes)),
+            TimestampSecond(v, _) => fixed(hasher, 27, v.map(i64::to_le_bytes)),
+            TimestampMillisecond(v, _) => fixed(hasher, 28, v.map(i64::to_le_bytes)),
+            TimestampMicrosecond(v, _) => fixed(hasher, 29, v.map(i64::to_le_bytes)),
+            TimestampNanosecond(v, _) => fixed(hasher, 30, v.map(i64::to_le_bytes)),
+            IntervalYearMonth(v) => fixed(hasher, 31, v.map(i32::to_le_bytes)),
+            IntervalDayTime(v) => fixed(hasher, 32, v.map(i64::to_le_bytes)),
+            IntervalMonthDayNano(v) => fixed(hasher, 33, v.map(i128::to_le_bytes)),
+            DurationSecond(v) => fixed(hasher, 34, v.map(i64::to_le_bytes)),
+            DurationMillisecond(v) => fixed(hasher, 35, v.map(i64::to_le_bytes)),
+            DurationMicrosecond(v) => fixed(hasher, 36, v.map(i64::to_le_bytes)),
+            DurationNanosecond(v) => fixed(hasher, 37, v.map(i64::to_le_bytes)),
+            Struct(v, fields) => {
+                nested(hasher, 38, v);
+                hasher.write(&(fields.len() as u64).to_le_bytes());
+                for field in fields.iter() {
+                    variable(hasher, 13, Some(field.name().as_bytes()));
+                }
+            }
+            Dictionary(_, v) => {
+                hasher.write(&[39]);
+                v.hash_stable(hasher)
+            }
+        }
+    }
+
+    /// Converts a non-null numeric value to an `f64`, possibly losing
+    /// precision. Decimals are divided by `10^scale`. Returns `None` for null
+    /// and non-numeric values.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ScalarValue::Float64(v) => *v,
+            ScalarValue::Float32(v) => v.map(|v| v as f64),
+            ScalarValue::Int8(v) => v.map(|v| v as f64),
+            ScalarValue::Int16(v) => v.map(|v| v as f64),
+            ScalarValue::Int32(v) => v.map(|v| v as f64),
+            ScalarValue::Int64(v) => v.map(|v| v as f64),
+            ScalarValue::UInt8(v) => v.map(|v| v as f64),
+            ScalarValue::UInt16(v) => v.map(|v| v as f64),
+            ScalarValue::UInt32(v) => v.map(|v| v as f64),
+            ScalarValue::UInt64(v) => v.map(|v| v as f64),
+            ScalarValue::Decimal128(v, _, scale) => {
+                v.map(|v| v as f64 / 10_f64.powi(*scale as i32))
+            }
+            _ => None,
+        }
+    }
+
+    /// Renders this value as a SQL literal: strings are single-quoted with
+    /// embedded quotes doubled, binary values are hex literals (`X'..'`) and
+    /// dates and timestamps are typed (`DATE '..'`, `TIMESTAMP '..'`).
+    /// Nested, time, duration and interval values, and timestamps in named
+    /// timezones, are not supported.
+    pub fn to_sql_string(&self) -> Result<String> {
+        let not_supported = || {
+            DataFusionError::NotImplemented(format!(
+                "Cannot render {:?} as a SQL literal",
+                self
+            ))
+        };
+        let date = |days: i64| {
+            i32::try_from(days + 719_163)
+                .ok()
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .map(|date| format!("DATE '{}'", date.format("%Y-%m-%d")))
+                .ok_or_else(not_supported)
+        };
+        let timestamp = |v: i64, unit: TimeUnit, tz: &Option<String>| {
+            if let Some(tz) = tz {
+                parse_fixed_offset(tz).ok_or_else(not_supported)?;
+            }
+            format_timestamp(v, &unit, tz)
+                .map(|s| format!("TIMESTAMP '{}'", s))
+                .ok_or_else(not_supported)
+        };
+        let float = |v: f64| {
+            if v.is_finite() {
+                Ok(v.to_string())
+            } else {
+                Err(not_supported())
+            }
+        };
+
+        match self {
+            ScalarValue::Dictionary(_, value) => value.to_sql_string(),
+            ScalarValue::Binary(None)
+            | ScalarValue::LargeBinary(None)
+            | ScalarValue::FixedSizeBinary(_, None) => Ok("NULL".to_string()),
+            value if value.is_null() => Ok("NULL".to_string()),
+            ScalarValue::Boolean(Some(v)) => {
+                Ok(if *v { "TRUE" } else { "FALSE" }.to_string())
+            }
+            ScalarValue::Float32(Some(v)) => float(*v as f64),
+            ScalarValue::Float64(Some(v)) => float(*v),
+            ScalarValue::Decimal128(Some(v), _, scale) => Ok(format_decimal(*v, *scale)),
+            ScalarValue::Int8(Some(v)) => Ok(v.to_string()),
+            ScalarValue::Int16(Some(v)) => Ok(v.to_string()),
+            ScalarValue::Int32(Some(v)) => Ok(v.to_string()),
+            ScalarValue::Int64(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt8(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt16(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt32(Some(v)) => Ok(v.to_string()),
+            ScalarValue::UInt64(Some(v)) => Ok(v.to_string()),
+            ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+                Ok(format!("'{}'", v.replace('\'', "''")))
+            }
+            ScalarValue::Binary(Some(v))
+            | ScalarValue::LargeBinary(Some(v))
+            | ScalarValue::FixedSizeBinary(_, Some(v)) => Ok(format!(
+                "X'{}'",
+                v.iter().map(|b| format!("{:02X}", b)).collect::<String>()
+            )),
+            ScalarValue::Date32(Some(v)) => date(*v as i64),
+            ScalarValue::Date64(Some(v)) => date(v.div_euclid(86_400_000)),
+            ScalarValue::TimestampSecond(Some(v), tz) => {
+                timestamp(*v, TimeUnit::Second, tz)
+            }
+            ScalarValue::TimestampMillisecond(Some(v), tz) => {
+                timestamp(*v, TimeUnit::Millisecond, tz)
+            }
+            ScalarValue::TimestampMicrosecond(Some(v), tz) => {
+                timestamp(*v, TimeUnit::Microsecond, tz)
+            }
+            ScalarValue::TimestampNanosecond(Some(v), tz) => {
+                timestamp(*v, TimeUnit::Nanosecond, tz)
+            }
+            _ => Err(not_supported()),
+        }
+    }
+
+    /// whether this value is null or not.
+    pub fn is_null(&self) -> bool {
+        if let ScalarValue::Dictionary(_, value) = self {
+            return value.is_null();
+        }
+        matches!(
+            *self,
+            ScalarValue::Null
+                | ScalarValue::Boolean(None)
+                | ScalarValue::UInt8(None)
+                | ScalarValue::UInt16(None)
+                | ScalarValue::UInt32(None)
+                | ScalarValue::UInt64(None)
+                | ScalarValue::Int8(None)
+                | ScalarValue::Int16(None)
+                | ScalarValue::Int32(None)
+                | ScalarValue::Int64(None)
+                | ScalarValue::Float32(None)
+                | ScalarValue::Float64(None)
+                | ScalarValue::Date32(None)
+                | ScalarValue::Date64(None)
+                | ScalarValue::Time32Second(None)
+                | ScalarValue::Time32Millisecond(None)
+                | ScalarValue::Time64Microsecond(None)
+                | ScalarValue::Time64Nanosecond(None)
+                | ScalarValue::DurationSecond(None)
+                | ScalarValue::DurationMillisecond(None)
+                | ScalarValue::DurationMicrosecond(None)
+                | ScalarValue::DurationNanosecond(None)
+                | ScalarValue::FixedSizeBinary(_, None)
+                | ScalarValue::Utf8(None)
+                | ScalarValue::LargeUtf8(None)
+                | ScalarValue::List(None, _, _)
+                | ScalarValue::FixedSizeList(None, _, _, _)
+                | ScalarValue::Map(None, _, _)
+                | ScalarValue::TimestampSecond(None, _)
+                | ScalarValue::TimestampMillisecond(None, _)
+                | ScalarValue::TimestampMicrosecond(None, _)
+                | ScalarValue::TimestampNanosecond(None, _)
+                | ScalarValue::Struct(None, _)
+                | ScalarValue::Decimal128(None, _, _) // For decimal type, the value is null means ScalarValue::Decimal128 is null.
+        )
+    }
+
+    /// Returns true if this is a signed integer, including a typed null
+    pub fn is_signed_integer(&self) -> bool {
+        matches!(
+            self,
+            ScalarValue::Int8(_)
+                | ScalarValue::Int16(_)
+                | ScalarValue::Int32(_)
+                | ScalarValue::Int64(_)
+        )
+    }
+
+    /// Returns true if this is an unsigned integer, including a typed null
+    pub fn is_unsigned_integer(&self) -> bool {
+        matches!(
+            self,
+            ScalarValue::UInt8(_)
+                | ScalarValue::UInt16(_)
+                | ScalarValue::UInt32(_)
+                | ScalarValue::UInt64(_)
+        )
+    }
+
+    /// Returns true if this is a floating point number, including a typed null
+    pub fn is_floating(&self) -> bool {
+        matches!(self, ScalarValue::Float32(_) | ScalarValue::Float64(_))
+    }
+
+    /// Returns true if this is an integer, floating point or decimal number,
+    /// including a typed null
+    pub fn is_numeric(&self) -> bool {
+        self.is_signed_integer()
+            || self.is_unsigned_integer()
+            || self.is_floating()
+            || matches!(self, ScalarValue::Decimal128(..))
+    }
+
+    /// Returns true if this is a non-null numeric zero (including `-0.0`)
+    pub fn is_zero(&self) -> bool {
+        match self {
+            ScalarValue::Float32(Some(v)) => *v == 0.0,
+            ScalarValue::Float64(Some(v)) => *v == 0.0,
+            ScalarValue::Decimal128(Some(v), _, _) => *v == 0,
+            ScalarValue::Int8(Some(v)) => *v == 0,
+            ScalarValue::Int16(Some(v)) => *v == 0,
+            ScalarValue::Int32(Some(v)) => *v == 0,
+            ScalarValue::Int64(Some(v)) => *v == 0,
+            ScalarValue::UInt8(Some(v)) => *v == 0,
+            ScalarValue::UInt16(Some(v)) => *v == 0,
+            ScalarValue::UInt32(Some(v)) => *v == 0,
+            ScalarValue::UInt64(Some(v)) => *v == 0,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this is a non-null numeric one
+    pub fn is_one(&self) -> bool {
+        match self {
+            ScalarValue::Float32(Some(v)) => *v == 1.0,
+            ScalarValue::Float64(Some(v)) => *v == 1.0,
+            ScalarValue::Decimal128(Some(v), _, scale) => {
+                10_i128.checked_pow(*scale as u32) == Some(*v)
+            }
+            ScalarValue::Int8(Some(v)) => *v == 1,
+            ScalarValue::Int16(Some(v)) => *v == 1,
+            ScalarValue::Int32(Some(v)) => *v == 1,
+            ScalarValue::Int64(Some(v)) => *v == 1,
+            ScalarValue::UInt8(Some(v)) => *v == 1,
+            ScalarValue::UInt16(Some(v)) => *v == 1,
+            ScalarValue::UInt32(Some(v)) => *v == 1,
+            ScalarValue::UInt64(Some(v)) => *v == 1,
+            _ => false,
+        }
+    }
+
+    /// Converts a scalar value into an 1-row array.
+    pub fn to_array(&self) -> ArrayRef {
+        self.to_array_of_size(1)
+    }
+
+    /// Converts an iterator of references [`ScalarValue`] into an [`ArrayRef`]
+    /// corresponding to those values. For example,
+    ///
+    /// Returns an error if the iterator is empty or if the
+    /// [`ScalarValue`]s are not all the same type
+    ///
+    /// Example
+    /// ```
+    /// use datafusion_common::ScalarValue;
+    /// use arrow::array::{ArrayRef, BooleanArray};
+    ///
+    /// let scalars = vec![
+    ///   ScalarValue::Boolean(Some(true)),
+    ///   ScalarValue::Boolean(None),
+    ///   ScalarValue::Boolean(Some(false)),
+    /// ];
+    ///
+    /// // Build an Array from the list of ScalarValues
+    /// let array = ScalarValue::iter_to_array(scalars.into_iter())
+    ///   .unwrap();
     ///
     /// let expected: ArrayRef = std::sync::Arc::new(
     ///   BooleanArray::from(vec![
@@ -777,7 +2715,7 @@ impl ScalarValue {
             ($ARRAY_TY:ident, $SCALAR_TY:ident, $NATIVE_TYPE:ident) => {{
                 Arc::new(ListArray::from_iter_primitive::<$ARRAY_TY, _, _>(
                     scalars.into_iter().map(|x| match x {
-                        ScalarValue::List(xs, _) => xs.map(|x| {
+                        ScalarValue::List(xs, _, _) => xs.map(|x| {
                             x.iter().map(|x| match x {
                                 ScalarValue::$SCALAR_TY(i) => *i,
                                 sv => panic!(
@@ -803,7 +2741,7 @@ impl ScalarValue {
                 let mut builder = ListBuilder::new($BUILDER::new(0));
                 for scalar in scalars.into_iter() {
                     match scalar {
-                        ScalarValue::List(Some(xs), _) => {
+                        ScalarValue::List(Some(xs), _, _) => {
                             let xs = *xs;
                             for s in xs {
                                 match s {
@@ -824,7 +2762,7 @@ impl ScalarValue {
                             }
                             builder.append(true)?;
                         }
-                        ScalarValue::List(None, _) => {
+                        ScalarValue::List(None, _, _) => {
                             builder.append(false)?;
                         }
                         sv => {
@@ -864,6 +2802,49 @@ impl ScalarValue {
             DataType::LargeBinary => build_array_string!(LargeBinaryArray, LargeBinary),
             DataType::Date32 => build_array_primitive!(Date32Array, Date32),
             DataType::Date64 => build_array_primitive!(Date64Array, Date64),
+            DataType::Time32(TimeUnit::Second) => {
+                build_array_primitive!(Time32SecondArray, Time32Second)
+            }
+            DataType::Time32(TimeUnit::Millisecond) => {
+                build_array_primitive!(Time32MillisecondArray, Time32Millisecond)
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                build_array_primitive!(Time64MicrosecondArray, Time64Microsecond)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                build_array_primitive!(Time64NanosecondArray, Time64Nanosecond)
+            }
+            DataType::Duration(TimeUnit::Second) => {
+                build_array_primitive!(DurationSecondArray, DurationSecond)
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                build_array_primitive!(DurationMillisecondArray, DurationMillisecond)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                build_array_primitive!(DurationMicrosecondArray, DurationMicrosecond)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                build_array_primitive!(DurationNanosecondArray, DurationNanosecond)
+            }
+            DataType::FixedSizeBinary(byte_width) => {
+                let mut builder = FixedSizeBinaryBuilder::new(0, *byte_width);
+                for scalar in scalars {
+                    match scalar {
+                        ScalarValue::FixedSizeBinary(_, Some(v)) => {
+                            builder.append_value(v)?
+                        }
+                        ScalarValue::FixedSizeBinary(_, None) => builder.append_null()?,
+                        sv => {
+                            return Err(DataFusionError::Internal(format!(
+                                "Inconsistent types in ScalarValue::iter_to_array. \
+                                    Expected {:?}, got {:?}",
+                                data_type, sv
+                            )))
+                        }
+                    }
+                }
+                Arc::new(builder.finish())
+            }
             DataType::Timestamp(TimeUnit::Second, _) => {
                 build_array_primitive_tz!(TimestampSecondArray, TimestampSecond)
             }
@@ -918,19 +2899,96 @@ impl ScalarValue {
             DataType::List(fields) if fields.data_type() == &DataType::LargeUtf8 => {
                 build_array_list_string!(LargeStringBuilder, LargeUtf8)
             }
+            DataType::List(fields) if fields.data_type() == &DataType::Date32 => {
+                build_array_list_primitive!(Date32Type, Date32, i32)
+            }
+            DataType::List(fields) if fields.data_type() == &DataType::Date64 => {
+                build_array_list_primitive!(Date64Type, Date64, i64)
+            }
+            DataType::List(fields)
+                if matches!(fields.data_type(), DataType::Decimal(_, _)) =>
+            {
+                let (precision, scale) = match fields.data_type() {
+                    DataType::Decimal(precision, scale) => (*precision, *scale),
+                    _ => unreachable!(),
+                };
+                let scalars = scalars.collect::<Vec<_>>();
+                let rows = scalars
+                    .iter()
+                    .map(|scalar| match scalar {
+                        ScalarValue::List(values, _, _) => {
+                            Ok(values.as_ref().map(|values| values.as_slice()))
+                        }
+                        sv => Err(DataFusionError::Internal(format!(
+                            "Inconsistent types in ScalarValue::iter_to_array. \
+                                Expected List, got {:?}",
+                            sv
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Arc::new(ScalarValue::build_decimal_list(rows, precision, scale)?)
+            }
             DataType::List(_) => {
                 // Fallback case handling homogeneous lists with any ScalarValue element type
                 let list_array = ScalarValue::iter_to_array_list(scalars, &data_type)?;
                 Arc::new(list_array)
             }
+            DataType::FixedSizeList(field, list_size) => {
+                let mut values = Vec::new();
+                let mut valid = BooleanBufferBuilder::new(0);
+                let mut len = 0;
+                for scalar in scalars {
+                    match scalar {
+                        ScalarValue::FixedSizeList(Some(row), _, size, _)
+                            if size == *list_size && row.len() == size as usize =>
+                        {
+                            valid.append(true);
+                            values.extend(*row);
+                        }
+                        ScalarValue::FixedSizeList(None, _, size, _)
+                            if size == *list_size =>
+                        {
+                            // Push NULL of the element type to keep the size fixed
+                            valid.append(false);
+                            for _ in 0..*list_size {
+                                values.push(ScalarValue::try_from(field.data_type())?);
+                            }
+                        }
+                        sv => {
+                            return Err(DataFusionError::Internal(format!(
+                                "Inconsistent types in ScalarValue::iter_to_array. \
+                                 Expected {:?}, got {:?}",
+                                data_type, sv
+                            )))
+                        }
+                    }
+                    len += 1;
+                }
+
+                let child = if values.is_empty() {
+                    new_empty_array(field.data_type())
+                } else {
+                    ScalarValue::iter_to_array(values)?
+                };
+                let array_data = ArrayDataBuilder::new(data_type.clone())
+                    .len(len)
+                    .null_bit_buffer(valid.finish())
+                    .add_child_data(child.data().clone())
+                    .build()?;
+                Arc::new(FixedSizeListArray::from(array_data))
+            }
             DataType::Struct(fields) => {
                 // Initialize a Vector to store the ScalarValues for each column
                 let mut columns: Vec<Vec<ScalarValue>> =
                     (0..fields.len()).map(|_| Vec::new()).collect();
+                let mut valid = BooleanBufferBuilder::new(0);
+                let mut has_nulls = false;
 
                 // Iterate over scalars to populate the column scalars for each row
                 for scalar in scalars {
                     if let ScalarValue::Struct(values, fields) = scalar {
+                        valid.append(values.is_some());
+                        has_nulls |= values.is_none();
                         match values {
                             Some(values) => {
                                 // Push value for each field
@@ -965,7 +3023,11 @@ impl ScalarValue {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                Arc::new(StructArray::from(field_values))
+                if has_nulls {
+                    Arc::new(StructArray::from((field_values, valid.finish())))
+                } else {
+                    Arc::new(StructArray::from(field_values))
+                }
             }
             _ => {
                 return Err(DataFusionError::Internal(format!(
@@ -976,7 +3038,11 @@ impl ScalarValue {
             }
         };
 
-        Ok(array)
+        match data_type {
+            // the list builders always produce nullable elements
+            DataType::List(_) => with_list_type(array, &data_type),
+            _ => Ok(array),
+        }
     }
 
     fn iter_to_null_array(scalars: impl IntoIterator<Item = ScalarValue>) -> ArrayRef {
@@ -1015,23 +3081,35 @@ impl ScalarValue {
             return Err(DataFusionError::ArrowError(err));
         }
 
+        let element_type = match data_type {
+            DataType::List(field) => field.data_type(),
+            other => {
+                return Err(DataFusionError::Internal(format!(
+                    "Expected a List data type, got {:?}",
+                    other
+                )))
+            }
+        };
+
         let mut elements: Vec<ArrayRef> = Vec::new();
         let mut valid = BooleanBufferBuilder::new(0);
         let mut flat_len = 0i32;
         for scalar in scalars {
-            if let ScalarValue::List(values, _) = scalar {
+            if let ScalarValue::List(values, _, _) = scalar {
                 match values {
                     Some(values) => {
-                        let element_array = ScalarValue::iter_to_array(*values)?;
+                        // an empty list adds no elements
+                        if !values.is_empty() {
+                            let element_array = ScalarValue::iter_to_array(*values)?;
+                            flat_len += element_array.len() as i32;
+                            elements.push(element_array);
+                        }
 
                         // Add new offset index
-                        flat_len += element_array.len() as i32;
                         if let Err(err) = offsets.append_value(flat_len) {
                             return Err(DataFusionError::ArrowError(err));
                         }
 
-                        elements.push(element_array);
-
                         // Element is valid
                         valid.append(true);
                     }
@@ -1056,9 +3134,13 @@ impl ScalarValue {
         // Concatenate element arrays to create single flat array
         let element_arrays: Vec<&dyn Array> =
             elements.iter().map(|a| a.as_ref()).collect();
-        let flat_array = match arrow::compute::concat(&element_arrays) {
-            Ok(flat_array) => flat_array,
-            Err(err) => return Err(DataFusionError::ArrowError(err)),
+        let flat_array = if element_arrays.is_empty() {
+            new_empty_array(element_type)
+        } else {
+            match arrow::compute::concat(&element_arrays) {
+                Ok(flat_array) => flat_array,
+                Err(err) => return Err(DataFusionError::ArrowError(err)),
+            }
         };
 
         // Build ListArray using ArrayData so we can specify a flat inner array, and offset indices
@@ -1086,9 +3168,68 @@ impl ScalarValue {
             .unwrap()
     }
 
+    fn build_decimal_list<'a>(
+        rows: impl IntoIterator<Item = Option<&'a [ScalarValue]>>,
+        precision: usize,
+        scale: usize,
+    ) -> Result<ListArray> {
+        let mut builder = ListBuilder::new(DecimalBuilder::new(0, precision, scale));
+
+        for row in rows {
+            match row {
+                Some(values) => {
+                    for scalar_value in values {
+                        match scalar_value {
+                            ScalarValue::Decimal128(Some(v), _, _) => {
+                                builder.values().append_value(*v)?
+                            }
+                            ScalarValue::Decimal128(None, _, _) => {
+                                builder.values().append_null()?
+                            }
+                            sv => {
+                                return Err(DataFusionError::Internal(format!(
+                                    "Inconsistent types in a Decimal list. \
+                                        Expected Decimal128, got {:?}",
+                                    sv
+                                )))
+                            }
+                        };
+                    }
+                    builder.append(true)?;
+                }
+                None => builder.append(false)?,
+            }
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// Like [`Self::to_array_of_size`], but returns the array stored in
+    /// `cache` if this value was already materialized at `size` rows with
+    /// the same cache, so that repeated calls share one array.
+    pub fn to_array_cached(&self, size: usize, cache: &mut ScalarArrayCache) -> ArrayRef {
+        cache
+            .arrays
+            .entry((self.clone(), size))
+            .or_insert_with(|| self.to_array_of_size(size))
+            .clone()
+    }
+
     /// Converts a scalar value into an array of `size` rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array cannot be built, e.g. if repeating a Utf8 or
+    /// Binary value would overflow the 32-bit offsets of the array. Use
+    /// [`Self::try_to_array_of_size`] to handle these cases.
     pub fn to_array_of_size(&self, size: usize) -> ArrayRef {
-        match self {
+        self.try_to_array_of_size(size).unwrap()
+    }
+
+    /// Like [`Self::to_array_of_size`], but returns an error instead of
+    /// panicking if the array cannot be built.
+    pub fn try_to_array_of_size(&self, size: usize) -> Result<ArrayRef> {
+        Ok(match self {
             ScalarValue::Decimal128(e, precision, scale) => {
                 Arc::new(ScalarValue::build_decimal_array(e, precision, scale, size))
             }
@@ -1149,6 +3290,9 @@ impl ScalarValue {
                 size
             ),
             ScalarValue::Utf8(e) => match e {
+                Some(value) if exceeds_i32_offsets(value.len(), size) => {
+                    return Err(offsets_overflow_error(self, size))
+                }
                 Some(value) => {
                     Arc::new(StringArray::from_iter_values(repeat(value).take(size)))
                 }
@@ -1161,6 +3305,9 @@ impl ScalarValue {
                 None => new_null_array(&DataType::LargeUtf8, size),
             },
             ScalarValue::Binary(e) => match e {
+                Some(value) if exceeds_i32_offsets(value.len(), size) => {
+                    return Err(offsets_overflow_error(self, size))
+                }
                 Some(value) => Arc::new(
                     repeat(Some(value.as_slice()))
                         .take(size)
@@ -1182,41 +3329,185 @@ impl ScalarValue {
                         .collect::<LargeBinaryArray>(),
                 ),
             },
-            ScalarValue::List(values, data_type) => Arc::new(match data_type.as_ref() {
-                DataType::Boolean => build_list!(BooleanBuilder, Boolean, values, size),
-                DataType::Int8 => build_list!(Int8Builder, Int8, values, size),
-                DataType::Int16 => build_list!(Int16Builder, Int16, values, size),
-                DataType::Int32 => build_list!(Int32Builder, Int32, values, size),
-                DataType::Int64 => build_list!(Int64Builder, Int64, values, size),
-                DataType::UInt8 => build_list!(UInt8Builder, UInt8, values, size),
-                DataType::UInt16 => build_list!(UInt16Builder, UInt16, values, size),
-                DataType::UInt32 => build_list!(UInt32Builder, UInt32, values, size),
-                DataType::UInt64 => build_list!(UInt64Builder, UInt64, values, size),
-                DataType::Utf8 => build_list!(StringBuilder, Utf8, values, size),
-                DataType::Float32 => build_list!(Float32Builder, Float32, values, size),
-                DataType::Float64 => build_list!(Float64Builder, Float64, values, size),
-                DataType::Timestamp(unit, tz) => {
-                    build_timestamp_list!(unit.clone(), tz.clone(), values, size)
+            ScalarValue::FixedSizeBinary(byte_width, e) => match e {
+                Some(value) => {
+                    if value.len() != *byte_width as usize {
+                        return Err(DataFusionError::Internal(format!(
+                            "FixedSizeBinary of width {} has {} bytes",
+                            byte_width,
+                            value.len()
+                        )));
+                    }
+                    let mut builder = FixedSizeBinaryBuilder::new(size, *byte_width);
+                    for _ in 0..size {
+                        builder.append_value(value)?;
+                    }
+                    Arc::new(builder.finish())
                 }
-                &DataType::LargeUtf8 => {
-                    build_list!(LargeStringBuilder, LargeUtf8, values, size)
+                None => new_null_array(&DataType::FixedSizeBinary(*byte_width), size),
+            },
+            ScalarValue::List(values, data_type, false) => {
+                // build the list with nullable elements, then mark them as
+                // non-nullable
+                let array = ScalarValue::List(values.clone(), data_type.clone(), true)
+                    .try_to_array_of_size(size)?;
+                with_list_type(array, &self.get_datatype())?
+            }
+            ScalarValue::List(values, data_type, true) => {
+                Arc::new(match data_type.as_ref() {
+                    DataType::Boolean => {
+                        build_list!(BooleanBuilder, Boolean, values, size)
+                    }
+                    DataType::Int8 => build_list!(Int8Builder, Int8, values, size),
+                    DataType::Int16 => build_list!(Int16Builder, Int16, values, size),
+                    DataType::Int32 => build_list!(Int32Builder, Int32, values, size),
+                    DataType::Int64 => build_list!(Int64Builder, Int64, values, size),
+                    DataType::UInt8 => build_list!(UInt8Builder, UInt8, values, size),
+                    DataType::UInt16 => build_list!(UInt16Builder, UInt16, values, size),
+                    DataType::UInt32 => build_list!(UInt32Builder, UInt32, values, size),
+                    DataType::UInt64 => build_list!(UInt64Builder, UInt64, values, size),
+                    DataType::Utf8 => build_list!(StringBuilder, Utf8, values, size),
+                    DataType::Float32 => {
+                        build_list!(Float32Builder, Float32, values, size)
+                    }
+                    DataType::Float64 => {
+                        build_list!(Float64Builder, Float64, values, size)
+                    }
+                    DataType::Timestamp(unit, tz) => {
+                        build_timestamp_list!(unit.clone(), tz.clone(), values, size)
+                    }
+                    &DataType::LargeUtf8 => {
+                        build_list!(LargeStringBuilder, LargeUtf8, values, size)
+                    }
+                    DataType::Interval(IntervalUnit::MonthDayNano) => match values {
+                        Some(values) => build_values_list!(
+                            IntervalMonthDayNanoBuilder,
+                            IntervalMonthDayNano,
+                            values.as_ref(),
+                            size
+                        ),
+                        None => {
+                            return Ok(new_null_array(
+                                &DataType::List(Box::new(Field::new(
+                                    "item",
+                                    data_type.as_ref().clone(),
+                                    true,
+                                ))),
+                                size,
+                            ))
+                        }
+                    },
+                    DataType::Date32 => build_list!(Date32Builder, Date32, values, size),
+                    DataType::Date64 => build_list!(Date64Builder, Date64, values, size),
+                    DataType::Decimal(precision, scale) => match values {
+                        Some(values) => ScalarValue::build_decimal_list(
+                            repeat(Some(values.as_slice())).take(size),
+                            *precision,
+                            *scale,
+                        )?,
+                        None => return Ok(new_null_array(&self.get_datatype(), size)),
+                    },
+                    _ => ScalarValue::iter_to_array_list(
+                        repeat(self.clone()).take(size),
+                        &self.get_datatype(),
+                    )?,
+                })
+            }
+            ScalarValue::FixedSizeList(values, data_type, list_size, _) => match values {
+                Some(values) => {
+                    if values.len() != *list_size as usize {
+                        return Err(DataFusionError::Internal(format!(
+                            "FixedSizeList of size {} has {} values",
+                            list_size,
+                            values.len()
+                        )));
+                    }
+                    let child = if values.is_empty() {
+                        new_empty_array(data_type)
+                    } else {
+                        ScalarValue::iter_to_array(
+                            repeat(values.iter()).take(size).flatten().cloned(),
+                        )?
+                    };
+                    let array_data = ArrayDataBuilder::new(self.get_datatype())
+                        .len(size)
+                        .add_child_data(child.data().clone());
+                    Arc::new(FixedSizeListArray::from(array_data.build()?))
                 }
-                _ => ScalarValue::iter_to_array_list(
-                    repeat(self.clone()).take(size),
-                    &DataType::List(Box::new(Field::new(
-                        "item",
-                        data_type.as_ref().clone(),
-                        true,
-                    ))),
-                )
-                .unwrap(),
-            }),
+                None => new_null_array(&self.get_datatype(), size),
+            },
+            ScalarValue::Map(values, field, _) => {
+                let (offsets, entries, nulls) = match values {
+                    Some(values) => {
+                        if exceeds_i32_offsets(values.len(), size) {
+                            return Err(offsets_overflow_error(self, size));
+                        }
+                        let entries = if values.is_empty() {
+                            new_empty_array(field.data_type())
+                        } else {
+                            ScalarValue::iter_to_array(
+                                repeat(values.iter()).take(size).flatten().cloned(),
+                            )?
+                        };
+                        let offsets = (0..=size)
+                            .map(|i| (i * values.len()) as i32)
+                            .collect::<Vec<_>>();
+                        (offsets, entries, None)
+                    }
+                    None => {
+                        let mut valid = BooleanBufferBuilder::new(size);
+                        valid.append_n(size, false);
+                        (
+                            vec![0i32; size + 1],
+                            new_empty_array(field.data_type()),
+                            Some(valid.finish()),
+                        )
+                    }
+                };
+                let offsets = Int32Array::from(offsets);
+                let mut builder = ArrayDataBuilder::new(self.get_datatype())
+                    .len(size)
+                    .add_buffer(offsets.data().buffers()[0].clone())
+                    .add_child_data(entries.data().clone());
+                if let Some(nulls) = nulls {
+                    builder = builder.null_bit_buffer(nulls);
+                }
+                make_array(builder.build()?)
+            }
             ScalarValue::Date32(e) => {
                 build_array_from_option!(Date32, Date32Array, e, size)
             }
             ScalarValue::Date64(e) => {
                 build_array_from_option!(Date64, Date64Array, e, size)
             }
+            ScalarValue::Time32Second(e) => build_array_from_option!(
+                Time32,
+                TimeUnit::Second,
+                Time32SecondArray,
+                e,
+                size
+            ),
+            ScalarValue::Time32Millisecond(e) => build_array_from_option!(
+                Time32,
+                TimeUnit::Millisecond,
+                Time32MillisecondArray,
+                e,
+                size
+            ),
+            ScalarValue::Time64Microsecond(e) => build_array_from_option!(
+                Time64,
+                TimeUnit::Microsecond,
+                Time64MicrosecondArray,
+                e,
+                size
+            ),
+            ScalarValue::Time64Nanosecond(e) => build_array_from_option!(
+                Time64,
+                TimeUnit::Nanosecond,
+                Time64NanosecondArray,
+                e,
+                size
+            ),
             ScalarValue::IntervalDayTime(e) => build_array_from_option!(
                 Interval,
                 IntervalUnit::DayTime,
@@ -1238,33 +3529,66 @@ impl ScalarValue {
                 e,
                 size
             ),
+            ScalarValue::DurationSecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Second,
+                DurationSecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationMillisecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Millisecond,
+                DurationMillisecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationMicrosecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Microsecond,
+                DurationMicrosecondArray,
+                e,
+                size
+            ),
+            ScalarValue::DurationNanosecond(e) => build_array_from_option!(
+                Duration,
+                TimeUnit::Nanosecond,
+                DurationNanosecondArray,
+                e,
+                size
+            ),
             ScalarValue::Struct(values, fields) => match values {
                 Some(values) => {
-                    let field_values: Vec<_> = fields
+                    let field_values = fields
                         .iter()
                         .zip(values.iter())
                         .map(|(field, value)| {
-                            (field.clone(), value.to_array_of_size(size))
+                            Ok((field.clone(), value.try_to_array_of_size(size)?))
                         })
-                        .collect();
+                        .collect::<Result<Vec<_>>>()?;
 
                     Arc::new(StructArray::from(field_values))
                 }
-                None => {
-                    let field_values: Vec<_> = fields
-                        .iter()
-                        .map(|field| {
-                            let none_field = Self::try_from(field.data_type())
-                .expect("Failed to construct null ScalarValue from Struct field type");
-                            (field.clone(), none_field.to_array_of_size(size))
-                        })
-                        .collect();
-
-                    Arc::new(StructArray::from(field_values))
+                None => new_null_array(&self.get_datatype(), size),
+            },
+            ScalarValue::Dictionary(key_type, value) => match key_type.as_ref() {
+                DataType::Int8 => dict_from_scalar::<Int8Type>(value, size)?,
+                DataType::Int16 => dict_from_scalar::<Int16Type>(value, size)?,
+                DataType::Int32 => dict_from_scalar::<Int32Type>(value, size)?,
+                DataType::Int64 => dict_from_scalar::<Int64Type>(value, size)?,
+                DataType::UInt8 => dict_from_scalar::<UInt8Type>(value, size)?,
+                DataType::UInt16 => dict_from_scalar::<UInt16Type>(value, size)?,
+                DataType::UInt32 => dict_from_scalar::<UInt32Type>(value, size)?,
+                DataType::UInt64 => dict_from_scalar::<UInt64Type>(value, size)?,
+                _ => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Invalid dictionary keys type: {:?}",
+                        key_type
+                    )))
                 }
             },
             ScalarValue::Null => new_null_array(&DataType::Null, size),
-        }
+        })
     }
 
     fn get_decimal_value_from_array(
@@ -1281,6 +3605,22 @@ impl ScalarValue {
         }
     }
 
+    /// Converts a value in `array` at `index` into a ScalarValue like
+    /// [`Self::try_from_array`], but a value of a dictionary array is returned
+    /// as a [`ScalarValue::Dictionary`] that keeps the key type
+    pub fn try_from_array_preserve_dictionary(
+        array: &ArrayRef,
+        index: usize,
+    ) -> Result<Self> {
+        match array.data_type() {
+            DataType::Dictionary(key_type, _) => Ok(ScalarValue::Dictionary(
+                key_type.clone(),
+                Box::new(Self::try_from_array(array, index)?),
+            )),
+            _ => Self::try_from_array(array, index),
+        }
+    }
+
     /// Converts a value in `array` at `index` into a ScalarValue
     pub fn try_from_array(array: &ArrayRef, index: usize) -> Result<Self> {
         // handle NULL value
@@ -1308,6 +3648,16 @@ impl ScalarValue {
             DataType::LargeBinary => {
                 typed_cast!(array, index, LargeBinaryArray, LargeBinary)
             }
+            DataType::FixedSizeBinary(byte_width) => {
+                let array = array
+                    .as_any()
+                    .downcast_ref::<FixedSizeBinaryArray>()
+                    .unwrap();
+                ScalarValue::FixedSizeBinary(
+                    *byte_width,
+                    Some(array.value(index).to_vec()),
+                )
+            }
             DataType::Utf8 => typed_cast!(array, index, StringArray, Utf8),
             DataType::LargeUtf8 => typed_cast!(array, index, LargeStringArray, LargeUtf8),
             DataType::List(nested_type) => {
@@ -1329,7 +3679,7 @@ impl ScalarValue {
                 };
                 let value = value.map(Box::new);
                 let data_type = Box::new(nested_type.data_type().clone());
-                ScalarValue::List(value, data_type)
+                ScalarValue::List(value, data_type, nested_type.is_nullable())
             }
             DataType::Date32 => {
                 typed_cast!(array, index, Date32Array, Date32)
@@ -1337,6 +3687,30 @@ impl ScalarValue {
             DataType::Date64 => {
                 typed_cast!(array, index, Date64Array, Date64)
             }
+            DataType::Time32(TimeUnit::Second) => {
+                typed_cast!(array, index, Time32SecondArray, Time32Second)
+            }
+            DataType::Time32(TimeUnit::Millisecond) => {
+                typed_cast!(array, index, Time32MillisecondArray, Time32Millisecond)
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                typed_cast!(array, index, Time64MicrosecondArray, Time64Microsecond)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                typed_cast!(array, index, Time64NanosecondArray, Time64Nanosecond)
+            }
+            DataType::Duration(TimeUnit::Second) => {
+                typed_cast!(array, index, DurationSecondArray, DurationSecond)
+            }
+            DataType::Duration(TimeUnit::Millisecond) => {
+                typed_cast!(array, index, DurationMillisecondArray, DurationMillisecond)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                typed_cast!(array, index, DurationMicrosecondArray, DurationMicrosecond)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                typed_cast!(array, index, DurationNanosecondArray, DurationNanosecond)
+            }
             DataType::Timestamp(TimeUnit::Second, tz_opt) => {
                 typed_cast_tz!(
                     array,
@@ -1415,22 +3789,35 @@ impl ScalarValue {
                 }
                 Self::Struct(Some(Box::new(field_values)), Box::new(fields.clone()))
             }
-            DataType::FixedSizeList(nested_type, _len) => {
+            DataType::FixedSizeList(nested_type, list_size) => {
+                // null rows were handled above
                 let list_array =
                     array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
-                let value = match list_array.is_null(index) {
+                let nested_array = list_array.value(index);
+                let value = (0..nested_array.len())
+                    .map(|i| ScalarValue::try_from_array(&nested_array, i))
+                    .collect::<Result<Vec<_>>>()?;
+                let data_type = Box::new(nested_type.data_type().clone());
+                ScalarValue::FixedSizeList(
+                    Some(Box::new(value)),
+                    data_type,
+                    *list_size,
+                    nested_type.is_nullable(),
+                )
+            }
+            DataType::Map(field, keys_sorted) => {
+                let map_array = array.as_any().downcast_ref::<MapArray>().unwrap();
+                let value = match map_array.is_null(index) {
                     true => None,
                     false => {
-                        let nested_array = list_array.value(index);
-                        let scalar_vec = (0..nested_array.len())
-                            .map(|i| ScalarValue::try_from_array(&nested_array, i))
+                        let entries = map_array.value(index);
+                        let scalar_vec = (0..entries.len())
+                            .map(|i| ScalarValue::try_from_array(&entries, i))
                             .collect::<Result<Vec<_>>>()?;
                         Some(scalar_vec)
                     }
                 };
-                let value = value.map(Box::new);
-                let data_type = Box::new(nested_type.data_type().clone());
-                ScalarValue::List(value, data_type)
+                ScalarValue::Map(value.map(Box::new), field.clone(), *keys_sorted)
             }
             other => {
                 return Err(DataFusionError::NotImplemented(format!(
@@ -1441,6 +3828,46 @@ impl ScalarValue {
         })
     }
 
+    /// Converts every row of `array` to a [`ScalarValue`]. Unlike calling
+    /// [`Self::try_from_array`] for each index, arrays of primitive, boolean,
+    /// string and binary types are downcast only once.
+    pub fn try_from_array_all(array: &ArrayRef) -> Result<Vec<Self>> {
+        macro_rules! typed_cast_all {
+            ($ARRAYTYPE:ident, $SCALAR:ident) => {{
+                let array = array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
+                array
+                    .iter()
+                    .map(|v| ScalarValue::$SCALAR(v.map(|v| v.into())))
+                    .collect()
+            }};
+        }
+
+        Ok(match array.data_type() {
+            DataType::Boolean => typed_cast_all!(BooleanArray, Boolean),
+            DataType::Float64 => typed_cast_all!(Float64Array, Float64),
+            DataType::Float32 => typed_cast_all!(Float32Array, Float32),
+            DataType::UInt64 => typed_cast_all!(UInt64Array, UInt64),
+            DataType::UInt32 => typed_cast_all!(UInt32Array, UInt32),
+            DataType::UInt16 => typed_cast_all!(UInt16Array, UInt16),
+            DataType::UInt8 => typed_cast_all!(UInt8Array, UInt8),
+            DataType::Int64 => typed_cast_all!(Int64Array, Int64),
+            DataType::Int32 => typed_cast_all!(Int32Array, Int32),
+            DataType::Int16 => typed_cast_all!(Int16Array, Int16),
+            DataType::Int8 => typed_cast_all!(Int8Array, Int8),
+            DataType::Binary => typed_cast_all!(BinaryArray, Binary),
+            DataType::LargeBinary => typed_cast_all!(LargeBinaryArray, LargeBinary),
+            DataType::Utf8 => typed_cast_all!(StringArray, Utf8),
+            DataType::LargeUtf8 => typed_cast_all!(LargeStringArray, LargeUtf8),
+            DataType::Date32 => typed_cast_all!(Date32Array, Date32),
+            DataType::Date64 => typed_cast_all!(Date64Array, Date64),
+            _ => {
+                return (0..array.len())
+                    .map(|index| Self::try_from_array(array, index))
+                    .collect()
+            }
+        })
+    }
+
     fn eq_array_decimal(
         array: &ArrayRef,
         index: usize,
@@ -1517,13 +3944,76 @@ impl ScalarValue {
             ScalarValue::LargeBinary(val) => {
                 eq_array_primitive!(array, index, LargeBinaryArray, val)
             }
-            ScalarValue::List(_, _) => unimplemented!(),
+            ScalarValue::FixedSizeBinary(_, val) => {
+                eq_array_primitive!(array, index, FixedSizeBinaryArray, val)
+            }
+            ScalarValue::List(values, _, _) => {
+                let array = array.as_any().downcast_ref::<ListArray>().unwrap();
+                match values {
+                    Some(values) => {
+                        array.is_valid(index) && {
+                            // a slice of the child array, the values are not copied
+                            let elements = array.value(index);
+                            elements.len() == values.len()
+                                && values
+                                    .iter()
+                                    .enumerate()
+                                    .all(|(i, value)| value.eq_array(&elements, i))
+                        }
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
+            ScalarValue::FixedSizeList(values, _, _, _) => {
+                let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                match values {
+                    Some(values) => {
+                        array.is_valid(index) && {
+                            let elements = array.value(index);
+                            elements.len() == values.len()
+                                && values
+                                    .iter()
+                                    .enumerate()
+                                    .all(|(i, value)| value.eq_array(&elements, i))
+                        }
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
+            ScalarValue::Map(values, _, _) => {
+                let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+                match values {
+                    Some(values) => {
+                        array.is_valid(index) && {
+                            let entries = array.value(index);
+                            entries.len() == values.len()
+                                && values
+                                    .iter()
+                                    .enumerate()
+                                    .all(|(i, value)| value.eq_array(&entries, i))
+                        }
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
             ScalarValue::Date32(val) => {
                 eq_array_primitive!(array, index, Date32Array, val)
             }
             ScalarValue::Date64(val) => {
                 eq_array_primitive!(array, index, Date64Array, val)
             }
+            ScalarValue::Time32Second(val) => {
+                eq_array_primitive!(array, index, Time32SecondArray, val)
+            }
+            ScalarValue::Time32Millisecond(val) => {
+                eq_array_primitive!(array, index, Time32MillisecondArray, val)
+            }
+            ScalarValue::Time64Microsecond(val) => {
+                eq_array_primitive!(array, index, Time64MicrosecondArray, val)
+            }
+            ScalarValue::Time64Nanosecond(val) => {
+                eq_array_primitive!(array, index, Time64NanosecondArray, val)
+            }
             ScalarValue::TimestampSecond(val, _) => {
                 eq_array_primitive!(array, index, TimestampSecondArray, val)
             }
@@ -1545,7 +4035,33 @@ impl ScalarValue {
             ScalarValue::IntervalMonthDayNano(val) => {
                 eq_array_primitive!(array, index, IntervalMonthDayNanoArray, val)
             }
-            ScalarValue::Struct(_, _) => unimplemented!(),
+            ScalarValue::DurationSecond(val) => {
+                eq_array_primitive!(array, index, DurationSecondArray, val)
+            }
+            ScalarValue::DurationMillisecond(val) => {
+                eq_array_primitive!(array, index, DurationMillisecondArray, val)
+            }
+            ScalarValue::DurationMicrosecond(val) => {
+                eq_array_primitive!(array, index, DurationMicrosecondArray, val)
+            }
+            ScalarValue::DurationNanosecond(val) => {
+                eq_array_primitive!(array, index, DurationNanosecondArray, val)
+            }
+            ScalarValue::Struct(values, _) => {
+                let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+                match values {
+                    Some(values) => {
+                        array.is_valid(index)
+                            && values.len() == array.num_columns()
+                            && values
+                                .iter()
+                                .zip(array.columns())
+                                .all(|(value, column)| value.eq_array(column, index))
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
+            ScalarValue::Dictionary(_, value) => value.eq_array(array, index),
             ScalarValue::Null => array.data().is_null(index),
         }
     }
@@ -1747,6 +4263,27 @@ impl TryFrom<&DataType> for ScalarValue {
             DataType::LargeUtf8 => ScalarValue::LargeUtf8(None),
             DataType::Date32 => ScalarValue::Date32(None),
             DataType::Date64 => ScalarValue::Date64(None),
+            DataType::Time32(TimeUnit::Second) => ScalarValue::Time32Second(None),
+            DataType::Time32(TimeUnit::Millisecond) => {
+                ScalarValue::Time32Millisecond(None)
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                ScalarValue::Time64Microsecond(None)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => ScalarValue::Time64Nanosecond(None),
+            DataType::Duration(TimeUnit::Second) => ScalarValue::DurationSecond(None),
+            DataType::Duration(TimeUnit::Millisecond) => {
+                ScalarValue::DurationMillisecond(None)
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                ScalarValue::DurationMicrosecond(None)
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                ScalarValue::DurationNanosecond(None)
+            }
+            DataType::FixedSizeBinary(byte_width) => {
+                ScalarValue::FixedSizeBinary(*byte_width, None)
+            }
             DataType::Timestamp(TimeUnit::Second, tz_opt) => {
                 ScalarValue::TimestampSecond(None, tz_opt.clone())
             }
@@ -1762,8 +4299,21 @@ impl TryFrom<&DataType> for ScalarValue {
             DataType::Dictionary(_index_type, value_type) => {
                 value_type.as_ref().try_into()?
             }
-            DataType::List(ref nested_type) => {
-                ScalarValue::List(None, Box::new(nested_type.data_type().clone()))
+            DataType::List(ref nested_type) => ScalarValue::List(
+                None,
+                Box::new(nested_type.data_type().clone()),
+                nested_type.is_nullable(),
+            ),
+            DataType::FixedSizeList(nested_type, list_size) => {
+                ScalarValue::FixedSizeList(
+                    None,
+                    Box::new(nested_type.data_type().clone()),
+                    *list_size,
+                    nested_type.is_nullable(),
+                )
+            }
+            DataType::Map(field, keys_sorted) => {
+                ScalarValue::Map(None, field.clone(), *keys_sorted)
             }
             DataType::Struct(fields) => {
                 ScalarValue::Struct(None, Box::new(fields.clone()))
@@ -1779,6 +4329,150 @@ impl TryFrom<&DataType> for ScalarValue {
     }
 }
 
+/// Parse a timezone that is `UTC` or a fixed offset such as `+08:00`,
+/// `-0530` or `+02` into a number of seconds east of UTC
+fn parse_fixed_offset(tz: &str) -> Option<i64> {
+    if tz.eq_ignore_ascii_case("utc") || tz == "Z" {
+        return Some(0);
+    }
+    let sign = match tz.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = tz[1..].replace(':', "");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i64>().ok()?, 0),
+        4 => (
+            digits[..2].parse::<i64>().ok()?,
+            digits[2..].parse::<i64>().ok()?,
+        ),
+        _ => return None,
+    };
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Format the unscaled value of a decimal with `scale` fractional digits
+fn format_decimal(value: i128, scale: usize) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    if frac.is_empty() {
+        format!("{}{}", sign, int)
+    } else {
+        format!("{}{}.{}", sign, int, frac)
+    }
+}
+
+/// Format a timestamp as an ISO-8601 string. Timestamps without a timezone
+/// are formatted without an offset, timestamps in `UTC` or a fixed offset
+/// timezone in that offset. Named timezones such as `America/New_York` are
+/// not resolved, so these are formatted as the equivalent UTC time followed
+/// by the timezone name. Returns `None` if the value is out of range.
+fn format_timestamp(value: i64, unit: &TimeUnit, tz: &Option<String>) -> Option<String> {
+    let nanos_per_unit = match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    };
+    let units_per_second = 1_000_000_000 / nanos_per_unit;
+    let fixed_offset = tz.as_deref().and_then(parse_fixed_offset);
+    let offset = fixed_offset.unwrap_or(0);
+
+    let seconds = value.div_euclid(units_per_second).checked_add(offset)?;
+    let nanos = (value.rem_euclid(units_per_second) * nanos_per_unit) as u32;
+    // 1970-01-01 is day 719_163 counting from 0001-01-01 as day 1
+    let days = i32::try_from(seconds.div_euclid(86_400).checked_add(719_163)?).ok()?;
+    let date = NaiveDate::from_num_days_from_ce_opt(days)?;
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(
+        seconds.rem_euclid(86_400) as u32,
+        nanos,
+    )?;
+
+    let suffix = match (tz, fixed_offset) {
+        (None, _) => "".to_string(),
+        (Some(tz), None) => format!(" UTC ({})", tz),
+        (Some(_), Some(0)) => "Z".to_string(),
+        (Some(_), Some(_)) => format!(
+            "{}{:02}:{:02}",
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 3600,
+            offset.abs() % 3600 / 60
+        ),
+    };
+    Some(format!(
+        "{}{}",
+        date.and_time(time).format("%Y-%m-%dT%H:%M:%S%.f"),
+        suffix
+    ))
+}
+
+macro_rules! format_timestamp_option {
+    ($F:expr, $EXPR:expr, $UNIT:expr, $TZ:expr) => {{
+        match $EXPR {
+            Some(e) => match format_timestamp(*e, &$UNIT, $TZ) {
+                Some(s) => write!($F, "{}", s),
+                None => write!($F, "{}", e),
+            },
+            None => write!($F, "NULL"),
+        }
+    }};
+}
+
+/// Format a time of day as `HH:MM:SS` followed by any fractional seconds.
+/// Returns `None` if the value is not within a day.
+fn format_time(value: impl Into<i64>, unit: &TimeUnit) -> Option<String> {
+    let value = value.into();
+    let nanos_per_unit = match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    };
+    let units_per_second = 1_000_000_000 / nanos_per_unit;
+    let seconds = u32::try_from(value.checked_div(units_per_second)?).ok()?;
+    let nanos = (value % units_per_second * nanos_per_unit) as u32;
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)?;
+    Some(time.format("%H:%M:%S%.f").to_string())
+}
+
+/// Format a duration as an ISO-8601 duration such as `PT1.5S`. Returns
+/// `None` if the value is out of range.
+fn format_duration(value: impl Into<i64>, unit: &TimeUnit) -> Option<String> {
+    let value = value.into();
+    let duration = match unit {
+        // `Duration` panics outside of `-i64::MAX..=i64::MAX` milliseconds
+        TimeUnit::Second => {
+            value.checked_mul(1_000)?;
+            chrono::Duration::seconds(value)
+        }
+        TimeUnit::Millisecond => {
+            value.checked_neg()?;
+            chrono::Duration::milliseconds(value)
+        }
+        TimeUnit::Microsecond => chrono::Duration::microseconds(value),
+        TimeUnit::Nanosecond => chrono::Duration::nanoseconds(value),
+    };
+    Some(duration.to_string())
+}
+
+macro_rules! format_time_option {
+    ($F:expr, $EXPR:expr, $FORMAT:ident, $UNIT:expr) => {{
+        match $EXPR {
+            Some(e) => match $FORMAT(*e, &$UNIT) {
+                Some(s) => write!($F, "{}", s),
+                None => write!($F, "{}", e),
+            },
+            None => write!($F, "NULL"),
+        }
+    }};
+}
+
 macro_rules! format_option {
     ($F:expr, $EXPR:expr) => {{
         match $EXPR {
@@ -1788,6 +4482,112 @@ macro_rules! format_option {
     }};
 }
 
+#[cfg(feature = "json")]
+impl ScalarValue {
+    /// Convert this value to a [`serde_json::Value`]. Numbers map to JSON
+    /// numbers, strings to strings, binary values to base64 encoded strings,
+    /// dates and timestamps to ISO-8601 strings, lists to arrays and structs
+    /// to objects keyed by field name. Nulls of any type map to `null`.
+    pub fn to_json_value(&self) -> Result<serde_json::Value> {
+        use serde_json::Value;
+
+        let or_null = |v: Option<Value>| v.unwrap_or(Value::Null);
+        let timestamp = |v: &Option<i64>, unit: TimeUnit, tz: &Option<String>| {
+            or_null(v.map(|v| match format_timestamp(v, &unit, tz) {
+                Some(s) => Value::String(s),
+                None => Value::from(v),
+            }))
+        };
+        let date = |days: i64| {
+            i32::try_from(days + 719_163)
+                .ok()
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .map(|date| Value::String(date.format("%Y-%m-%d").to_string()))
+                .unwrap_or_else(|| Value::from(days))
+        };
+
+        Ok(match self {
+            ScalarValue::Dictionary(_, value) => value.to_json_value()?,
+            ScalarValue::Null => Value::Null,
+            ScalarValue::Boolean(v) => or_null(v.map(Value::Bool)),
+            ScalarValue::Float32(v) => or_null(v.map(Value::from)),
+            ScalarValue::Float64(v) => or_null(v.map(Value::from)),
+            ScalarValue::Decimal128(v, _, scale) => {
+                or_null(v.map(|v| Value::String(format_decimal(v, *scale))))
+            }
+            ScalarValue::Int8(v) => or_null(v.map(Value::from)),
+            ScalarValue::Int16(v) => or_null(v.map(Value::from)),
+            ScalarValue::Int32(v) => or_null(v.map(Value::from)),
+            ScalarValue::Int64(v) => or_null(v.map(Value::from)),
+            ScalarValue::UInt8(v) => or_null(v.map(Value::from)),
+            ScalarValue::UInt16(v) => or_null(v.map(Value::from)),
+            ScalarValue::UInt32(v) => or_null(v.map(Value::from)),
+            ScalarValue::UInt64(v) => or_null(v.map(Value::from)),
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => {
+                or_null(v.clone().map(Value::String))
+            }
+            ScalarValue::Binary(v)
+            | ScalarValue::LargeBinary(v)
+            | ScalarValue::FixedSizeBinary(_, v) => {
+                or_null(v.as_ref().map(|v| Value::String(base64::encode(v))))
+            }
+            ScalarValue::List(v, _, _)
+            | ScalarValue::FixedSizeList(v, _, _, _)
+            | ScalarValue::Map(v, _, _) => match v {
+                Some(values) => Value::Array(
+                    values
+                        .iter()
+                        .map(|v| v.to_json_value())
+                        .collect::<Result<Vec<_>>>()?,
+                ),
+                None => Value::Null,
+            },
+            ScalarValue::Struct(v, fields) => match v {
+                Some(values) => Value::Object(
+                    fields
+                        .iter()
+                        .zip(values.iter())
+                        .map(|(field, v)| Ok((field.name().clone(), v.to_json_value()?)))
+                        .collect::<Result<serde_json::Map<_, _>>>()?,
+                ),
+                None => Value::Null,
+            },
+            ScalarValue::Date32(v) => or_null(v.map(|v| date(v as i64))),
+            ScalarValue::Date64(v) => or_null(v.map(|v| date(v.div_euclid(86_400_000)))),
+            ScalarValue::TimestampSecond(v, tz) => timestamp(v, TimeUnit::Second, tz),
+            ScalarValue::TimestampMillisecond(v, tz) => {
+                timestamp(v, TimeUnit::Millisecond, tz)
+            }
+            ScalarValue::TimestampMicrosecond(v, tz) => {
+                timestamp(v, TimeUnit::Microsecond, tz)
+            }
+            ScalarValue::TimestampNanosecond(v, tz) => {
+                timestamp(v, TimeUnit::Nanosecond, tz)
+            }
+            ScalarValue::Time32Second(v) | ScalarValue::Time32Millisecond(v) => {
+                or_null(v.map(Value::from))
+            }
+            ScalarValue::Time64Microsecond(v)
+            | ScalarValue::Time64Nanosecond(v)
+            | ScalarValue::DurationSecond(v)
+            | ScalarValue::DurationMillisecond(v)
+            | ScalarValue::DurationMicrosecond(v)
+            | ScalarValue::DurationNanosecond(v) => or_null(v.map(Value::from)),
+            ScalarValue::IntervalYearMonth(None)
+            | ScalarValue::IntervalDayTime(None)
+            | ScalarValue::IntervalMonthDayNano(None) => Value::Null,
+            ScalarValue::IntervalYearMonth(Some(_))
+            | ScalarValue::IntervalDayTime(Some(_))
+            | ScalarValue::IntervalMonthDayNano(Some(_)) => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Converting {:?} to JSON",
+                    self
+                )))
+            }
+        })
+    }
+}
+
 impl fmt::Display for ScalarValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -1805,10 +4605,18 @@ impl fmt::Display for ScalarValue {
             ScalarValue::UInt16(e) => format_option!(f, e)?,
             ScalarValue::UInt32(e) => format_option!(f, e)?,
             ScalarValue::UInt64(e) => format_option!(f, e)?,
-            ScalarValue::TimestampSecond(e, _) => format_option!(f, e)?,
-            ScalarValue::TimestampMillisecond(e, _) => format_option!(f, e)?,
-            ScalarValue::TimestampMicrosecond(e, _) => format_option!(f, e)?,
-            ScalarValue::TimestampNanosecond(e, _) => format_option!(f, e)?,
+            ScalarValue::TimestampSecond(e, tz) => {
+                format_timestamp_option!(f, e, TimeUnit::Second, tz)?
+            }
+            ScalarValue::TimestampMillisecond(e, tz) => {
+                format_timestamp_option!(f, e, TimeUnit::Millisecond, tz)?
+            }
+            ScalarValue::TimestampMicrosecond(e, tz) => {
+                format_timestamp_option!(f, e, TimeUnit::Microsecond, tz)?
+            }
+            ScalarValue::TimestampNanosecond(e, tz) => {
+                format_timestamp_option!(f, e, TimeUnit::Nanosecond, tz)?
+            }
             ScalarValue::Utf8(e) => format_option!(f, e)?,
             ScalarValue::LargeUtf8(e) => format_option!(f, e)?,
             ScalarValue::Binary(e) => match e {
@@ -1833,7 +4641,20 @@ impl fmt::Display for ScalarValue {
                 )?,
                 None => write!(f, "NULL")?,
             },
-            ScalarValue::List(e, _) => match e {
+            ScalarValue::FixedSizeBinary(_, e) => match e {
+                Some(l) => write!(
+                    f,
+                    "{}",
+                    l.iter()
+                        .map(|v| format!("{}", v))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?,
+                None => write!(f, "NULL")?,
+            },
+            ScalarValue::List(e, _, _)
+            | ScalarValue::FixedSizeList(e, _, _, _)
+            | ScalarValue::Map(e, _, _) => match e {
                 Some(l) => write!(
                     f,
                     "{}",
@@ -1846,9 +4667,33 @@ impl fmt::Display for ScalarValue {
             },
             ScalarValue::Date32(e) => format_option!(f, e)?,
             ScalarValue::Date64(e) => format_option!(f, e)?,
+            ScalarValue::Time32Second(e) => {
+                format_time_option!(f, e, format_time, TimeUnit::Second)?
+            }
+            ScalarValue::Time32Millisecond(e) => {
+                format_time_option!(f, e, format_time, TimeUnit::Millisecond)?
+            }
+            ScalarValue::Time64Microsecond(e) => {
+                format_time_option!(f, e, format_time, TimeUnit::Microsecond)?
+            }
+            ScalarValue::Time64Nanosecond(e) => {
+                format_time_option!(f, e, format_time, TimeUnit::Nanosecond)?
+            }
             ScalarValue::IntervalDayTime(e) => format_option!(f, e)?,
             ScalarValue::IntervalYearMonth(e) => format_option!(f, e)?,
             ScalarValue::IntervalMonthDayNano(e) => format_option!(f, e)?,
+            ScalarValue::DurationSecond(e) => {
+                format_time_option!(f, e, format_duration, TimeUnit::Second)?
+            }
+            ScalarValue::DurationMillisecond(e) => {
+                format_time_option!(f, e, format_duration, TimeUnit::Millisecond)?
+            }
+            ScalarValue::DurationMicrosecond(e) => {
+                format_time_option!(f, e, format_duration, TimeUnit::Microsecond)?
+            }
+            ScalarValue::DurationNanosecond(e) => {
+                format_time_option!(f, e, format_duration, TimeUnit::Nanosecond)?
+            }
             ScalarValue::Struct(e, fields) => match e {
                 Some(l) => write!(
                     f,
@@ -1861,6 +4706,7 @@ impl fmt::Display for ScalarValue {
                 )?,
                 None => write!(f, "NULL")?,
             },
+            ScalarValue::Dictionary(_, value) => write!(f, "{}", value)?,
             ScalarValue::Null => write!(f, "NULL")?,
         };
         Ok(())
@@ -1882,17 +4728,26 @@ impl fmt::Debug for ScalarValue {
             ScalarValue::UInt16(_) => write!(f, "UInt16({})", self),
             ScalarValue::UInt32(_) => write!(f, "UInt32({})", self),
             ScalarValue::UInt64(_) => write!(f, "UInt64({})", self),
-            ScalarValue::TimestampSecond(_, tz_opt) => {
-                write!(f, "TimestampSecond({}, {:?})", self, tz_opt)
+            // the raw value, as Display formats timestamps as ISO-8601
+            ScalarValue::TimestampSecond(e, tz_opt) => {
+                write!(f, "TimestampSecond(")?;
+                format_option!(f, e)?;
+                write!(f, ", {:?})", tz_opt)
             }
-            ScalarValue::TimestampMillisecond(_, tz_opt) => {
-                write!(f, "TimestampMillisecond({}, {:?})", self, tz_opt)
+            ScalarValue::TimestampMillisecond(e, tz_opt) => {
+                write!(f, "TimestampMillisecond(")?;
+                format_option!(f, e)?;
+                write!(f, ", {:?})", tz_opt)
             }
-            ScalarValue::TimestampMicrosecond(_, tz_opt) => {
-                write!(f, "TimestampMicrosecond({}, {:?})", self, tz_opt)
+            ScalarValue::TimestampMicrosecond(e, tz_opt) => {
+                write!(f, "TimestampMicrosecond(")?;
+                format_option!(f, e)?;
+                write!(f, ", {:?})", tz_opt)
             }
-            ScalarValue::TimestampNanosecond(_, tz_opt) => {
-                write!(f, "TimestampNanosecond({}, {:?})", self, tz_opt)
+            ScalarValue::TimestampNanosecond(e, tz_opt) => {
+                write!(f, "TimestampNanosecond(")?;
+                format_option!(f, e)?;
+                write!(f, ", {:?})", tz_opt)
             }
             ScalarValue::Utf8(None) => write!(f, "Utf8({})", self),
             ScalarValue::Utf8(Some(_)) => write!(f, "Utf8(\"{}\")", self),
@@ -1902,9 +4757,29 @@ impl fmt::Debug for ScalarValue {
             ScalarValue::Binary(Some(_)) => write!(f, "Binary(\"{}\")", self),
             ScalarValue::LargeBinary(None) => write!(f, "LargeBinary({})", self),
             ScalarValue::LargeBinary(Some(_)) => write!(f, "LargeBinary(\"{}\")", self),
-            ScalarValue::List(_, _) => write!(f, "List([{}])", self),
+            ScalarValue::FixedSizeBinary(size, None) => {
+                write!(f, "FixedSizeBinary({}, {})", size, self)
+            }
+            ScalarValue::FixedSizeBinary(size, Some(_)) => {
+                write!(f, "FixedSizeBinary({}, \"{}\")", size, self)
+            }
+            ScalarValue::List(_, _, _) => write!(f, "List([{}])", self),
+            ScalarValue::FixedSizeList(_, _, list_size, _) => {
+                write!(f, "FixedSizeList({}, [{}])", list_size, self)
+            }
+            ScalarValue::Map(_, _, _) => write!(f, "Map([{}])", self),
             ScalarValue::Date32(_) => write!(f, "Date32(\"{}\")", self),
             ScalarValue::Date64(_) => write!(f, "Date64(\"{}\")", self),
+            ScalarValue::Time32Second(_) => write!(f, "Time32Second(\"{}\")", self),
+            ScalarValue::Time32Millisecond(_) => {
+                write!(f, "Time32Millisecond(\"{}\")", self)
+            }
+            ScalarValue::Time64Microsecond(_) => {
+                write!(f, "Time64Microsecond(\"{}\")", self)
+            }
+            ScalarValue::Time64Nanosecond(_) => {
+                write!(f, "Time64Nanosecond(\"{}\")", self)
+            }
             ScalarValue::IntervalDayTime(_) => {
                 write!(f, "IntervalDayTime(\"{}\")", self)
             }
@@ -1914,6 +4789,16 @@ impl fmt::Debug for ScalarValue {
             ScalarValue::IntervalMonthDayNano(_) => {
                 write!(f, "IntervalMonthDayNano(\"{}\")", self)
             }
+            ScalarValue::DurationSecond(_) => write!(f, "DurationSecond(\"{}\")", self),
+            ScalarValue::DurationMillisecond(_) => {
+                write!(f, "DurationMillisecond(\"{}\")", self)
+            }
+            ScalarValue::DurationMicrosecond(_) => {
+                write!(f, "DurationMicrosecond(\"{}\")", self)
+            }
+            ScalarValue::DurationNanosecond(_) => {
+                write!(f, "DurationNanosecond(\"{}\")", self)
+            }
             ScalarValue::Struct(e, fields) => {
                 // Use Debug representation of field values
                 match e {
@@ -1929,11 +4814,101 @@ impl fmt::Debug for ScalarValue {
                     None => write!(f, "Struct(NULL)"),
                 }
             }
+            ScalarValue::Dictionary(key_type, value) => {
+                write!(f, "Dictionary({:?}, {:?})", key_type, value)
+            }
             ScalarValue::Null => write!(f, "NULL"),
         }
     }
 }
 
+macro_rules! constant_primitive {
+    ($ARRAY_TY:ident, $VALUE:expr) => {{
+        let value = $VALUE;
+        Box::new(move |size| Arc::new($ARRAY_TY::from_value(value, size)) as ArrayRef)
+    }};
+}
+
+/// Materializes a [`ScalarValue`] as constant arrays of any number of rows.
+///
+/// The variant dispatch happens once when the builder is created, so calling
+/// [`ScalarColumnBuilder::build`] for every batch is cheaper than calling
+/// [`ScalarValue::to_array_of_size`] each time.
+pub struct ScalarColumnBuilder {
+    build_fn: Box<dyn Fn(usize) -> ArrayRef + Send + Sync>,
+}
+
+impl ScalarColumnBuilder {
+    /// Create a builder producing arrays filled with `value`
+    pub fn new(value: ScalarValue) -> Self {
+        let build_fn: Box<dyn Fn(usize) -> ArrayRef + Send + Sync> = match value {
+            ScalarValue::Boolean(Some(v)) => Box::new(move |size| {
+                Arc::new(BooleanArray::from(vec![Some(v); size])) as ArrayRef
+            }),
+            ScalarValue::Float32(Some(v)) => constant_primitive!(Float32Array, v),
+            ScalarValue::Float64(Some(v)) => constant_primitive!(Float64Array, v),
+            ScalarValue::Int8(Some(v)) => constant_primitive!(Int8Array, v),
+            ScalarValue::Int16(Some(v)) => constant_primitive!(Int16Array, v),
+            ScalarValue::Int32(Some(v)) => constant_primitive!(Int32Array, v),
+            ScalarValue::Int64(Some(v)) => constant_primitive!(Int64Array, v),
+            ScalarValue::UInt8(Some(v)) => constant_primitive!(UInt8Array, v),
+            ScalarValue::UInt16(Some(v)) => constant_primitive!(UInt16Array, v),
+            ScalarValue::UInt32(Some(v)) => constant_primitive!(UInt32Array, v),
+            ScalarValue::UInt64(Some(v)) => constant_primitive!(UInt64Array, v),
+            ScalarValue::Date32(Some(v)) => constant_primitive!(Date32Array, v),
+            ScalarValue::Date64(Some(v)) => constant_primitive!(Date64Array, v),
+            ScalarValue::Utf8(Some(v)) => Box::new(move |size| {
+                if exceeds_i32_offsets(v.len(), size) {
+                    // panics with the error of `try_to_array_of_size`
+                    return ScalarValue::Utf8(Some(v.clone())).to_array_of_size(size);
+                }
+                Arc::new(StringArray::from_iter_values(repeat(&v).take(size)))
+            }),
+            ScalarValue::LargeUtf8(Some(v)) => Box::new(move |size| {
+                Arc::new(LargeStringArray::from_iter_values(repeat(&v).take(size)))
+                    as ArrayRef
+            }),
+            other => Box::new(move |size| other.to_array_of_size(size)),
+        };
+        Self { build_fn }
+    }
+
+    /// Build an array of `size` rows, all equal to the builder's value
+    pub fn build(&self, size: usize) -> ArrayRef {
+        (self.build_fn)(size)
+    }
+}
+
+/// Arrays materialized by [`ScalarValue::to_array_cached`], keyed by value
+/// and number of rows. The cache is held by the caller and only grows, so
+/// it should be dropped or cleared once the values are no longer needed.
+#[derive(Debug, Default)]
+pub struct ScalarArrayCache {
+    arrays: HashMap<(ScalarValue, usize), ArrayRef>,
+}
+
+impl ScalarArrayCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached arrays
+    pub fn len(&self) -> usize {
+        self.arrays.len()
+    }
+
+    /// Whether the cache holds no arrays
+    pub fn is_empty(&self) -> bool {
+        self.arrays.is_empty()
+    }
+
+    /// Remove all cached arrays
+    pub fn clear(&mut self) {
+        self.arrays.clear()
+    }
+}
+
 /// Trait used to map a NativeTime to a ScalarType.
 pub trait ScalarType<T: ArrowNativeType> {
     /// returns a scalar from an optional T
@@ -1969,3 +4944,97 @@ impl ScalarType<i64> for TimestampNanosecondType {
         ScalarValue::TimestampNanosecond(r, None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeds_i32_offsets() {
+        assert!(!exceeds_i32_offsets(0, usize::MAX));
+        assert!(!exceeds_i32_offsets(10, 1024));
+        assert!(!exceeds_i32_offsets(1, i32::MAX as usize));
+        assert!(exceeds_i32_offsets(1, i32::MAX as usize + 1));
+        assert!(exceeds_i32_offsets(1 << 16, 1 << 16));
+        assert!(exceeds_i32_offsets(usize::MAX, 2));
+
+        // the check happens before anything is allocated
+        let size = i32::MAX as usize;
+        let err = ScalarValue::Utf8(Some("ab".to_string()))
+            .try_to_array_of_size(size)
+            .unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{}", err);
+        assert!(ScalarValue::Binary(Some(vec![1, 2]))
+            .try_to_array_of_size(size)
+            .is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_json_value() {
+        use serde_json::json;
+
+        let fields = vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("payload", DataType::Binary, true),
+        ];
+        let item = |id: i32, name: Option<&str>, payload: Option<&[u8]>| {
+            ScalarValue::Struct(
+                Some(Box::new(vec![
+                    ScalarValue::Int32(Some(id)),
+                    ScalarValue::Utf8(name.map(|s| s.to_string())),
+                    ScalarValue::Binary(payload.map(|p| p.to_vec())),
+                ])),
+                Box::new(fields.clone()),
+            )
+        };
+        let list = ScalarValue::List(
+            Some(Box::new(vec![
+                item(1, Some("a"), Some(b"hi")),
+                item(2, None, None),
+                ScalarValue::Struct(None, Box::new(fields.clone())),
+            ])),
+            Box::new(DataType::Struct(fields.clone())),
+            true,
+        );
+        assert_eq!(
+            list.to_json_value().unwrap(),
+            json!([
+                {"id": 1, "name": "a", "payload": "aGk="},
+                {"id": 2, "name": null, "payload": null},
+                null
+            ])
+        );
+
+        let cases = vec![
+            (ScalarValue::Boolean(Some(true)), json!(true)),
+            (ScalarValue::UInt64(Some(u64::MAX)), json!(u64::MAX)),
+            (ScalarValue::Float64(Some(1.5)), json!(1.5)),
+            (
+                ScalarValue::Decimal128(Some(-12345), 10, 2),
+                json!("-123.45"),
+            ),
+            (ScalarValue::Decimal128(Some(5), 10, 3), json!("0.005")),
+            (ScalarValue::LargeUtf8(Some("x".to_string())), json!("x")),
+            (ScalarValue::Date32(Some(18513)), json!("2020-09-08")),
+            (
+                ScalarValue::TimestampMillisecond(
+                    Some(1_599_566_400_123),
+                    Some("UTC".to_string()),
+                ),
+                json!("2020-09-08T12:00:00.123Z"),
+            ),
+            (ScalarValue::Int64(None), json!(null)),
+            (ScalarValue::IntervalDayTime(None), json!(null)),
+            (ScalarValue::Null, json!(null)),
+        ];
+        for (scalar, expected) in cases {
+            assert_eq!(scalar.to_json_value().unwrap(), expected, "{:?}", scalar);
+        }
+
+        assert!(ScalarValue::IntervalYearMonth(Some(1))
+            .to_json_value()
+            .is_err());
+    }
+}
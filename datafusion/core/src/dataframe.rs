@@ -702,6 +702,7 @@ mod tests {
             partition_by: vec![col("aggregate_test_100.c2")],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let t2 = t.select(vec![col("c1"), first_row])?;
         let plan = t2.plan.clone();
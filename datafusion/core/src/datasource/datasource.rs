@@ -64,4 +64,10 @@ pub trait TableProvider: Sync + Send {
     ) -> Result<TableProviderFilterPushDown> {
         Ok(TableProviderFilterPushDown::Unsupported)
     }
+
+    /// Returns the names of the columns the table is partitioned by, if
+    /// the table provider exposes them.
+    fn table_partition_cols(&self) -> Option<Vec<String>> {
+        None
+    }
 }
@@ -32,12 +32,22 @@ use crate::physical_plan::{empty::EmptyExec, ExecutionPlan};
 /// A table with a schema but no data.
 pub struct EmptyTable {
     schema: SchemaRef,
+    partition_cols: Option<Vec<String>>,
 }
 
 impl EmptyTable {
     /// Initialize a new `EmptyTable` from a schema.
     pub fn new(schema: SchemaRef) -> Self {
-        Self { schema }
+        Self {
+            schema,
+            partition_cols: None,
+        }
+    }
+
+    /// Report `partition_cols` as the columns the table is partitioned by
+    pub fn with_partition_cols(mut self, partition_cols: Vec<String>) -> Self {
+        self.partition_cols = Some(partition_cols);
+        self
     }
 }
 
@@ -65,4 +75,8 @@ impl TableProvider for EmptyTable {
         let projected_schema = project_schema(&self.schema, projection.as_ref())?;
         Ok(Arc::new(EmptyExec::new(false, projected_schema)))
     }
+
+    fn table_partition_cols(&self) -> Option<Vec<String>> {
+        self.partition_cols.clone()
+    }
 }
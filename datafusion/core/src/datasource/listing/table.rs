@@ -349,6 +349,10 @@ impl TableProvider for ListingTable {
             Ok(TableProviderFilterPushDown::Inexact)
         }
     }
+
+    fn table_partition_cols(&self) -> Option<Vec<String>> {
+        Some(self.options.table_partition_cols.clone())
+    }
 }
 
 impl ListingTable {
@@ -61,9 +61,9 @@ use crate::datasource::listing::ListingTableConfig;
 use crate::datasource::TableProvider;
 use crate::error::{DataFusionError, Result};
 use crate::logical_plan::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
-    CreateView, DropTable, FileType, FunctionRegistry, LogicalPlan, LogicalPlanBuilder,
-    UNNAMED_TABLE,
+    assert_unique_udf, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+    CreateMemoryTable, CreateView, DropTable, FileType, FunctionRegistry, LogicalPlan,
+    LogicalPlanBuilder, UNNAMED_TABLE,
 };
 use crate::optimizer::common_subexpr_eliminate::CommonSubexprEliminate;
 use crate::optimizer::filter_push_down::FilterPushDown;
@@ -1249,6 +1249,11 @@ impl SessionState {
                 .register_catalog(config.default_catalog.clone(), default_catalog);
         }
 
+        // functions used by plans built with LogicalPlanBuilder
+        let mut scalar_functions = HashMap::new();
+        let assert_unique = assert_unique_udf();
+        scalar_functions.insert(assert_unique.name.clone(), Arc::new(assert_unique));
+
         SessionState {
             session_id,
             optimizers: vec![
@@ -1273,7 +1278,7 @@ impl SessionState {
             ],
             query_planner: Arc::new(DefaultQueryPlanner {}),
             catalog_list,
-            scalar_functions: HashMap::new(),
+            scalar_functions,
             aggregate_functions: HashMap::new(),
             config,
             execution_props: ExecutionProps::new(),
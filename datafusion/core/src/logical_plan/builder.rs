@@ -21,12 +21,14 @@ use crate::datasource::{empty::EmptyTable, TableProvider};
 use crate::error::{DataFusionError, Result};
 use crate::logical_expr::ExprSchemable;
 use crate::logical_plan::plan::{
-    Aggregate, Analyze, EmptyRelation, Explain, Filter, Join, Projection, Sort,
-    SubqueryAlias, TableScan, ToStringifiedPlan, Union, Window,
+    Aggregate, Analyze, EmptyRelation, EstimatedRows, Explain, Filter, Join, Projection,
+    Sort, Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union, Window,
 };
 use crate::optimizer::utils;
 use crate::scalar::ScalarValue;
+use arrow::array::{Array, BooleanArray, Int64Array};
 use arrow::datatypes::{DataType, Schema};
+use datafusion_expr::binary_rule::coerce_types;
 use datafusion_expr::utils::{
     expand_qualified_wildcard, expand_wildcard, expr_to_columns,
 };
@@ -37,19 +39,39 @@ use std::{
     sync::Arc,
 };
 
-use super::{Expr, JoinConstraint, JoinType, LogicalPlan, PlanType};
+use super::{Expr, JoinConstraint, JoinHint, JoinType, LogicalPlan, PlanType};
 use crate::logical_plan::expr::exprlist_to_fields;
 use crate::logical_plan::{
-    columnize_expr, normalize_col, normalize_cols, provider_as_source,
-    rewrite_sort_cols_by_aggs, Column, CrossJoin, DFField, DFSchema, DFSchemaRef, Limit,
-    Partitioning, Repartition, Values,
+    and, columnize_expr, create_udf, lit, normalize_col, normalize_cols,
+    provider_as_source, rewrite_sort_cols_by_aggs, when, Column, CrossJoin, DFField,
+    DFSchema, DFSchemaRef, Limit, Operator, Partitioning, Repartition, Values,
 };
 use crate::sql::utils::group_window_expr_by_sort_keys;
 use datafusion_common::ToDFSchema;
+use datafusion_expr::{
+    AggregateFunction, BuiltInWindowFunction, ColumnarValue,
+    ScalarFunctionImplementation, ScalarUDF, Volatility, WindowFrame, WindowFunction,
+};
 
 /// Default table name for unnamed table
 pub const UNNAMED_TABLE: &str = "?table?";
 
+/// Name of the column numbering duplicate rows when planning `EXCEPT ALL`
+const EXCEPT_ROW_NUMBER: &str = "__datafusion_except_row_number";
+
+/// Names of the columns used to compare adjacent rows in `dedup_consecutive`
+const DEDUP_ROW_NUMBER: &str = "__datafusion_dedup_row_number";
+const DEDUP_PREVIOUS_KEY: &str = "__datafusion_dedup_previous_key";
+
+/// Name of the column ranking rows within a group in `top_n_per_group`
+const TOP_N_ROW_NUMBER: &str = "__datafusion_top_n_row_number";
+
+/// Name of the column numbering the rows of every group in `distinct_on`
+const DISTINCT_ON_ROW_NUMBER: &str = "__datafusion_distinct_on_row_number";
+
+/// Name of the column counting the rows per key in `assert_unique`
+const ASSERT_UNIQUE_COUNT: &str = "__datafusion_assert_unique_count";
+
 /// Builder for logical plans
 ///
 /// ```
@@ -98,6 +120,16 @@ impl LogicalPlanBuilder {
         Self { plan }
     }
 
+    /// Annotate the plan with an estimate of the number of rows it
+    /// produces, by wrapping it in a [LogicalPlan::EstimatedRows] node.
+    /// The estimate is shown when the plan is displayed, e.g. by `EXPLAIN`.
+    pub fn with_estimated_rows(&self, rows: usize) -> Self {
+        Self::from(LogicalPlan::EstimatedRows(EstimatedRows {
+            rows,
+            input: Arc::new(self.plan.clone()),
+        }))
+    }
+
     /// Return the output schema of the plan build so far
     pub fn schema(&self) -> &DFSchemaRef {
         self.plan.schema()
@@ -188,6 +220,38 @@ impl LogicalPlanBuilder {
         Ok(Self::from(LogicalPlan::Values(Values { schema, values })))
     }
 
+    /// Create a values list based relation like [`Self::values`], naming its
+    /// columns `names` instead of column1, column2, etc.
+    pub fn values_with_names(values: Vec<Vec<Expr>>, names: Vec<String>) -> Result<Self> {
+        let (schema, values) = match Self::values(values)?.plan {
+            LogicalPlan::Values(Values { schema, values }) => (schema, values),
+            _ => unreachable!(),
+        };
+        if names.len() != schema.fields().len() {
+            return Err(DataFusionError::Plan(format!(
+                "Expected {} column names for values list, got {}",
+                schema.fields().len(),
+                names.len()
+            )));
+        }
+        if names.iter().any(|name| name.is_empty()) {
+            return Err(DataFusionError::Plan(
+                "Values list column names cannot be empty".into(),
+            ));
+        }
+        let fields = schema
+            .fields()
+            .iter()
+            .zip(names)
+            .map(|(field, name)| {
+                DFField::new(None, &name, field.data_type().clone(), field.is_nullable())
+            })
+            .collect::<Vec<_>>();
+        let schema =
+            DFSchemaRef::new(DFSchema::new_with_metadata(fields, HashMap::new())?);
+        Ok(Self::from(LogicalPlan::Values(Values { schema, values })))
+    }
+
     /// Scan an empty data source, mainly used in tests
     pub fn scan_empty(
         name: Option<&str>,
@@ -214,6 +278,79 @@ impl LogicalPlanBuilder {
         provider: Arc<dyn TableProvider>,
         projection: Option<Vec<usize>>,
         filters: Vec<Expr>,
+    ) -> Result<Self> {
+        Self::scan_with_partition_filters(
+            table_name,
+            provider,
+            projection,
+            filters,
+            vec![],
+        )
+    }
+
+    /// Convert a table provider into a builder with a TableScan that only
+    /// reads the partitions matching `partition_filters`.
+    ///
+    /// The provider must expose its partition columns, and the filters
+    /// must only reference partition columns that are in the projection.
+    /// Filters the provider does not apply exactly are evaluated on top of
+    /// the scan.
+    pub fn scan_partitions(
+        table_name: impl Into<String>,
+        provider: Arc<dyn TableProvider>,
+        projection: Option<Vec<usize>>,
+        partition_filters: Vec<Expr>,
+    ) -> Result<Self> {
+        let table_name = table_name.into();
+        let partition_cols = provider
+            .table_partition_cols()
+            .filter(|cols| !cols.is_empty())
+            .ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "Table {} does not expose any partition columns",
+                    table_name
+                ))
+            })?;
+        for filter in &partition_filters {
+            let mut columns = HashSet::new();
+            expr_to_columns(filter, &mut columns)?;
+            if let Some(column) =
+                columns.iter().find(|c| !partition_cols.contains(&c.name))
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "Partition filter {:?} references non-partition column {}",
+                    filter, column
+                )));
+            }
+        }
+        let builder = Self::scan_with_partition_filters(
+            table_name,
+            provider,
+            projection,
+            vec![],
+            partition_filters.clone(),
+        )?;
+        for filter in &partition_filters {
+            let mut columns = HashSet::new();
+            expr_to_columns(filter, &mut columns)?;
+            for column in &columns {
+                if builder.plan.schema().index_of_column(column).is_err() {
+                    return Err(DataFusionError::Plan(format!(
+                        "Partition filter {:?} references column {} which is not in the projection",
+                        filter, column
+                    )));
+                }
+            }
+        }
+        Ok(builder)
+    }
+
+    fn scan_with_partition_filters(
+        table_name: impl Into<String>,
+        provider: Arc<dyn TableProvider>,
+        projection: Option<Vec<usize>>,
+        filters: Vec<Expr>,
+        partition_filters: Vec<Expr>,
     ) -> Result<Self> {
         let table_name = table_name.into();
 
@@ -247,6 +384,7 @@ impl LogicalPlanBuilder {
             projected_schema: Arc::new(projected_schema),
             projection,
             filters,
+            partition_filters,
             limit: None,
         });
         Ok(Self::from(table_scan))
@@ -295,6 +433,44 @@ impl LogicalPlanBuilder {
         )?))
     }
 
+    /// Apply a projection that keeps the current schema, as done for the
+    /// inputs of a union. Expressions other than columns and aliases are
+    /// aliased by their position (`column0`, `column1`, ...).
+    pub fn project_indexed_alias(
+        &self,
+        exprs: Vec<Expr>,
+        alias: Option<String>,
+    ) -> Result<Self> {
+        Ok(Self::from(project_with_column_index_alias(
+            exprs,
+            Arc::new(self.plan.clone()),
+            self.plan.schema().clone(),
+            alias,
+        )?))
+    }
+
+    /// Apply a projection of all columns except the named `columns`, which
+    /// may be qualified (e.g. `t.a`). Errors if a column does not exist.
+    pub fn drop_columns(&self, columns: &[&str]) -> Result<Self> {
+        let schema = self.plan.schema();
+        let dropped = columns
+            .iter()
+            .map(|name| {
+                schema
+                    .field_from_column(&Column::from_qualified_name(name))
+                    .map(|field| field.qualified_column())
+            })
+            .collect::<Result<HashSet<_>>>()?;
+        let expr = schema
+            .fields()
+            .iter()
+            .map(|field| field.qualified_column())
+            .filter(|column| !dropped.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        self.project(expr)
+    }
+
     /// Apply a filter
     pub fn filter(&self, expr: impl Into<Expr>) -> Result<Self> {
         let expr = normalize_col(expr.into(), &self.plan)?;
@@ -304,14 +480,105 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply a `HAVING` filter to the output of an aggregate. The current
+    /// plan must be a [LogicalPlan::Aggregate]; `expr` may reference its
+    /// group by and aggregate output columns.
+    pub fn having(&self, expr: impl Into<Expr>) -> Result<Self> {
+        match &self.plan {
+            LogicalPlan::Aggregate(_) => self.filter(expr),
+            other => Err(DataFusionError::Plan(format!(
+                "having can only be applied to an aggregate, got:\n{:?}",
+                other
+            ))),
+        }
+    }
+
+    /// Apply a filter, combining it with the predicate of the current plan
+    /// using `AND` if that plan is already a filter rather than stacking a
+    /// second filter on top of it
+    pub fn filter_and(&self, expr: impl Into<Expr>) -> Result<Self> {
+        match &self.plan {
+            LogicalPlan::Filter(Filter { predicate, input }) => {
+                let expr = normalize_col(expr.into(), input)?;
+                Ok(Self::from(LogicalPlan::Filter(Filter {
+                    predicate: and(predicate.clone(), expr),
+                    input: input.clone(),
+                })))
+            }
+            _ => self.filter(expr),
+        }
+    }
+
+    /// Apply a filter keeping the rows whose `column` is (or, if `negated`,
+    /// is not) one of `values`, as a single `IN` list rather than a chain
+    /// of `OR`s. Every value must have the data type of `column`; duplicate
+    /// values are removed.
+    ///
+    /// No separate marker is needed for large sets: the physical `IN` list
+    /// expression already probes a hash set when its list consists of
+    /// enough literals.
+    pub fn filter_in_set(
+        &self,
+        column: Column,
+        values: Vec<ScalarValue>,
+        negated: bool,
+    ) -> Result<Self> {
+        let column = Self::normalize(&self.plan, column)?;
+        let data_type = self
+            .plan
+            .schema()
+            .field_from_column(&column)?
+            .data_type()
+            .clone();
+        if let Some(value) = values.iter().find(|v| v.get_datatype() != data_type) {
+            return Err(DataFusionError::Plan(format!(
+                "filter_in_set values must have the type {:?} of column {}, got {:?}",
+                data_type,
+                column,
+                value.get_datatype()
+            )));
+        }
+        let mut seen = HashSet::new();
+        let list = values
+            .into_iter()
+            .filter(|value| seen.insert(value.clone()))
+            .map(Expr::Literal)
+            .collect();
+        self.filter(Expr::InList {
+            expr: Box::new(Expr::Column(column)),
+            list,
+            negated,
+        })
+    }
+
     /// Apply a limit
     pub fn limit(&self, n: usize) -> Result<Self> {
         Ok(Self::from(LogicalPlan::Limit(Limit {
+            skip: None,
             n,
             input: Arc::new(self.plan.clone()),
         })))
     }
 
+    /// Apply a limit of `fetch` rows, or leave the plan unchanged if `fetch`
+    /// is `None`, as for `LIMIT ALL`
+    pub fn maybe_limit(&self, fetch: Option<usize>) -> Result<Self> {
+        match fetch {
+            Some(n) => self.limit(n),
+            None => Ok(Self::from(self.plan.clone())),
+        }
+    }
+
+    /// Apply a limit that skips the first `skip` rows and then
+    /// fetches at most `fetch` rows
+    pub fn limit_with_offset(&self, skip: usize, fetch: usize) -> Result<Self> {
+        Ok(Self::from(LogicalPlan::Limit(Limit {
+            skip: Some(skip),
+            n: fetch,
+            input: Arc::new(self.plan.clone()),
+        })))
+    }
+
     /// Apply an alias
     pub fn alias(&self, alias: &str) -> Result<Self> {
         let schema: Schema = self.schema().as_ref().clone().into();
@@ -324,6 +591,20 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Wrap the current plan in a [LogicalPlan::Subquery]
+    pub fn subquery(&self) -> Result<Self> {
+        Ok(Self::from(LogicalPlan::Subquery(Subquery {
+            subquery: Arc::new(self.plan.clone()),
+        })))
+    }
+
+    /// Wrap the current plan in a [LogicalPlan::Subquery] named `alias`
+    /// by a [LogicalPlan::SubqueryAlias], so that its columns can be
+    /// referenced as `alias.column`
+    pub fn subquery_as(&self, alias: &str) -> Result<Self> {
+        self.subquery()?.alias(alias)
+    }
+
     /// Add missing sort columns to all downstream projection
     fn add_missing_columns(
         &self,
@@ -376,6 +657,16 @@ impl LogicalPlanBuilder {
         }
     }
 
+    /// Apply a sort on `(expr, ascending)` keys, with nulls first for
+    /// ascending keys and last for descending ones
+    pub fn sort_by(&self, keys: Vec<(Expr, bool)>) -> Result<Self> {
+        self.sort(keys.into_iter().map(|(expr, asc)| Expr::Sort {
+            expr: Box::new(expr),
+            asc,
+            nulls_first: asc,
+        }))
+    }
+
     /// Apply a sort
     pub fn sort(
         &self,
@@ -407,6 +698,7 @@ impl LogicalPlanBuilder {
             return Ok(Self::from(LogicalPlan::Sort(Sort {
                 expr: normalize_cols(exprs, &self.plan)?,
                 input: Arc::new(self.plan.clone()),
+                fetch: None,
             })));
         }
 
@@ -414,6 +706,7 @@ impl LogicalPlanBuilder {
         let sort_plan = LogicalPlan::Sort(Sort {
             expr: normalize_cols(exprs, &plan)?,
             input: Arc::new(plan.clone()),
+            fetch: None,
         });
         // remove pushed down sort columns
         let new_expr = schema
@@ -434,6 +727,43 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply a sort by the `exprs` sort expressions followed by every other
+    /// column of the input in ascending order, so that the order of the
+    /// output rows is fully determined by their values.
+    pub fn sort_total(&self, exprs: Vec<Expr>) -> Result<Self> {
+        let mut sort_exprs = Vec::with_capacity(exprs.len());
+        let mut sorted_columns = HashSet::new();
+        for expr in exprs {
+            let expr = normalize_col(expr, &self.plan)?;
+            match &expr {
+                Expr::Sort { expr: inner, .. } => {
+                    if let Expr::Column(column) = inner.as_ref() {
+                        sorted_columns.insert(column.clone());
+                    }
+                }
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "sort_total expects sort expressions, got {:?}",
+                        other
+                    )))
+                }
+            }
+            sort_exprs.push(expr);
+        }
+
+        let tiebreakers = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.qualified_column())
+            .filter(|column| !sorted_columns.contains(column))
+            .map(|column| Expr::Column(column).sort(true, false))
+            .collect::<Vec<_>>();
+        sort_exprs.extend(tiebreakers);
+        self.sort(sort_exprs)
+    }
+
     /// Apply a union
     pub fn union(&self, plan: LogicalPlan) -> Result<Self> {
         Ok(Self::from(union_with_alias(self.plan.clone(), plan, None)?))
@@ -448,6 +778,48 @@ impl LogicalPlanBuilder {
         Self::from(plan).project(vec![Expr::Wildcard])
     }
 
+    /// Apply a Postgres style `DISTINCT ON (on_expr) select_expr ORDER BY
+    /// sort_expr`: keep the first row, according to `sort_expr`, of every
+    /// group of rows with equal `on_expr` values and project `select_expr`.
+    /// Without `sort_expr` an arbitrary row of every group is kept.
+    ///
+    /// This is planned as a `ROW_NUMBER` window function partitioned by
+    /// `on_expr`, as there is no `FIRST_VALUE` aggregate function.
+    pub fn distinct_on(
+        &self,
+        on_expr: Vec<Expr>,
+        select_expr: Vec<Expr>,
+        sort_expr: Option<Vec<Expr>>,
+    ) -> Result<Self> {
+        if on_expr.is_empty() {
+            return Err(DataFusionError::Plan(
+                "DISTINCT ON requires at least one expression".to_string(),
+            ));
+        }
+        let on_expr = normalize_cols(on_expr, &self.plan)?;
+        let sort_expr = normalize_cols(sort_expr.unwrap_or_default(), &self.plan)?;
+        for expr in on_expr.iter().chain(sort_expr.iter()) {
+            expr.get_type(self.plan.schema())?;
+        }
+
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: on_expr,
+            order_by: sort_expr,
+            window_frame: None,
+            distinct: false,
+        }
+        .alias(DISTINCT_ON_ROW_NUMBER);
+
+        self.window(vec![row_number])?
+            .filter(
+                Expr::Column(Column::from_name(DISTINCT_ON_ROW_NUMBER))
+                    .eq(Expr::Literal(ScalarValue::UInt64(Some(1)))),
+            )?
+            .project(select_expr)
+    }
+
     /// Apply a join with on constraint
     pub fn join(
         &self,
@@ -458,6 +830,69 @@ impl LogicalPlanBuilder {
         self.join_detailed(right, join_type, join_keys, false)
     }
 
+    /// Apply a semi join: keep the rows of this plan that have at least one
+    /// match in `right`. NULL keys are considered equal.
+    pub fn semi_join(
+        &self,
+        right: &LogicalPlan,
+        join_keys: (Vec<impl Into<Column>>, Vec<impl Into<Column>>),
+    ) -> Result<Self> {
+        self.join_detailed(right, JoinType::Semi, join_keys, true)
+    }
+
+    /// Apply an anti join: keep the rows of this plan that have no match in
+    /// `right`. NULL keys are considered equal.
+    pub fn anti_join(
+        &self,
+        right: &LogicalPlan,
+        join_keys: (Vec<impl Into<Column>>, Vec<impl Into<Column>>),
+    ) -> Result<Self> {
+        self.join_detailed(right, JoinType::Anti, join_keys, true)
+    }
+
+    /// Apply a join with on constraint, hinting which input should be
+    /// broadcast. The hint is advisory and may be ignored by the physical
+    /// planner, but the broadcast input must not be one whose unmatched rows
+    /// are preserved by `join_type`.
+    pub fn join_with_hint(
+        &self,
+        right: &LogicalPlan,
+        join_type: JoinType,
+        join_keys: (Vec<impl Into<Column>>, Vec<impl Into<Column>>),
+        hint: JoinHint,
+    ) -> Result<Self> {
+        let preserves_left = matches!(
+            join_type,
+            JoinType::Left | JoinType::Full | JoinType::Semi | JoinType::Anti
+        );
+        let preserves_right = matches!(join_type, JoinType::Right | JoinType::Full);
+        match hint {
+            JoinHint::BroadcastLeft if preserves_left => {
+                return Err(DataFusionError::Plan(format!(
+                    "Cannot broadcast the left input of a {} join",
+                    join_type
+                )))
+            }
+            JoinHint::BroadcastRight if preserves_right => {
+                return Err(DataFusionError::Plan(format!(
+                    "Cannot broadcast the right input of a {} join",
+                    join_type
+                )))
+            }
+            _ => {}
+        }
+
+        match self.join(right, join_type, join_keys)?.plan {
+            LogicalPlan::Join(join) => {
+                Ok(Self::from(LogicalPlan::Join(Join { hint, ..join })))
+            }
+            plan => Err(DataFusionError::Internal(format!(
+                "Expected a join plan, got {:?}",
+                plan
+            ))),
+        }
+    }
+
     fn normalize(
         plan: &LogicalPlan,
         column: impl Into<Column> + Clone,
@@ -564,6 +999,7 @@ impl LogicalPlanBuilder {
         let right_keys = right_keys.into_iter().collect::<Result<Vec<Column>>>()?;
 
         let on: Vec<(_, _)> = left_keys.into_iter().zip(right_keys.into_iter()).collect();
+        validate_join_key_types(self.plan.schema(), right.schema(), &on)?;
         let join_schema =
             build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
 
@@ -575,6 +1011,7 @@ impl LogicalPlanBuilder {
             join_constraint: JoinConstraint::On,
             schema: DFSchemaRef::new(join_schema),
             null_equals_null,
+            hint: JoinHint::None,
         })))
     }
 
@@ -596,6 +1033,7 @@ impl LogicalPlanBuilder {
             .collect::<Result<_>>()?;
 
         let on: Vec<(_, _)> = left_keys.into_iter().zip(right_keys.into_iter()).collect();
+        validate_join_key_types(self.plan.schema(), right.schema(), &on)?;
         let join_schema =
             build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
 
@@ -607,6 +1045,7 @@ impl LogicalPlanBuilder {
             join_constraint: JoinConstraint::Using,
             schema: DFSchemaRef::new(join_schema),
             null_equals_null: false,
+            hint: JoinHint::None,
         })))
     }
 
@@ -620,6 +1059,25 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Cross join all of `inputs` into a left-deep chain of
+    /// [LogicalPlan::CrossJoin]s. A single input is returned unchanged.
+    pub fn cross_join_many(inputs: Vec<LogicalPlan>) -> Result<LogicalPlan> {
+        let mut inputs = inputs.into_iter();
+        let first = inputs.next().ok_or_else(|| {
+            DataFusionError::Plan(
+                "cross_join_many requires at least one input".to_string(),
+            )
+        })?;
+        inputs.try_fold(first, |left, right| {
+            let schema = left.schema().join(right.schema())?;
+            Ok(LogicalPlan::CrossJoin(CrossJoin {
+                left: Arc::new(left),
+                right: Arc::new(right),
+                schema: DFSchemaRef::new(schema),
+            }))
+        })
+    }
+
     /// Repartition
     pub fn repartition(&self, partitioning_scheme: Partitioning) -> Result<Self> {
         Ok(Self::from(LogicalPlan::Repartition(Repartition {
@@ -628,6 +1086,253 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Keep only the rows whose `key_columns` differ from the ones of the
+    /// immediately preceding row, removing adjacent duplicates.
+    ///
+    /// Rows are compared in the order given by the `order_by` sort
+    /// expressions, which must not be empty as the order of the input is
+    /// not preserved by the window functions used to find the preceding row.
+    pub fn dedup_consecutive(
+        &self,
+        key_columns: Vec<Column>,
+        order_by: Vec<Expr>,
+    ) -> Result<Self> {
+        if key_columns.is_empty() {
+            return Err(DataFusionError::Plan(
+                "dedup_consecutive requires at least one key column".to_string(),
+            ));
+        }
+        if order_by.is_empty() {
+            return Err(DataFusionError::Plan(
+                "dedup_consecutive requires at least one sort expression".to_string(),
+            ));
+        }
+        let keys = key_columns
+            .into_iter()
+            .map(|column| Self::normalize(&self.plan, column))
+            .collect::<Result<Vec<_>>>()?;
+        let order_by = normalize_cols(order_by, &self.plan)?;
+        for expr in &order_by {
+            expr.get_type(self.plan.schema())?;
+        }
+        let output_exprs = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: vec![],
+            order_by: order_by.clone(),
+            window_frame: None,
+            distinct: false,
+        }
+        .alias(DEDUP_ROW_NUMBER);
+        let previous_keys = keys.iter().enumerate().map(|(i, key)| {
+            Expr::WindowFunction {
+                fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::Lag),
+                args: vec![Expr::Column(key.clone())],
+                partition_by: vec![],
+                order_by: order_by.clone(),
+                window_frame: None,
+                distinct: false,
+            }
+            .alias(&format!("{}_{}", DEDUP_PREVIOUS_KEY, i))
+        });
+
+        // the first row is always kept, the others if any key changed
+        let predicate = keys.iter().enumerate().fold(
+            Expr::Column(Column::from_name(DEDUP_ROW_NUMBER))
+                .eq(Expr::Literal(ScalarValue::UInt64(Some(1)))),
+            |acc, (i, key)| {
+                acc.or(Expr::BinaryExpr {
+                    left: Box::new(Expr::Column(Column::from_name(format!(
+                        "{}_{}",
+                        DEDUP_PREVIOUS_KEY, i
+                    )))),
+                    op: Operator::IsDistinctFrom,
+                    right: Box::new(Expr::Column(key.clone())),
+                })
+            },
+        );
+
+        self.window(iter::once(row_number).chain(previous_keys))?
+            .filter(predicate)?
+            .project(output_exprs)
+    }
+
+    /// Keep the first `n` rows of every group of rows with equal
+    /// `partition_by` values, ordered by the `order_by` sort expressions.
+    /// The output has the same schema as the input.
+    pub fn top_n_per_group(
+        &self,
+        partition_by: Vec<Expr>,
+        order_by: Vec<Expr>,
+        n: usize,
+    ) -> Result<Self> {
+        let schema = self.plan.schema();
+        let partition_by = normalize_cols(partition_by, &self.plan)?;
+        let order_by = normalize_cols(order_by, &self.plan)?;
+        for expr in partition_by.iter().chain(order_by.iter()) {
+            expr.get_type(schema)?;
+        }
+        let output_exprs = schema
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by,
+            order_by,
+            window_frame: None,
+            distinct: false,
+        }
+        .alias(TOP_N_ROW_NUMBER);
+
+        self.window(vec![row_number])?
+            .filter(
+                Expr::Column(Column::from_name(TOP_N_ROW_NUMBER))
+                    .lt_eq(Expr::Literal(ScalarValue::UInt64(Some(n as u64)))),
+            )?
+            .project(output_exprs)
+    }
+
+    /// Keep all rows of this plan, but fail at execution time if two rows
+    /// have the same values for `key_columns`. Rows with a null key are
+    /// never duplicates, as in a SQL `UNIQUE` constraint.
+    ///
+    /// The rows per key are counted with a `COUNT` window function, and a
+    /// filter calls the [assert_unique_udf] function on the count, which
+    /// returns an execution error for counts above one.
+    pub fn assert_unique(&self, key_columns: Vec<Column>) -> Result<Self> {
+        if key_columns.is_empty() {
+            return Err(DataFusionError::Plan(
+                "assert_unique requires at least one key column".to_string(),
+            ));
+        }
+        let key_columns = key_columns
+            .into_iter()
+            .map(|c| Self::normalize(&self.plan, c))
+            .collect::<Result<Vec<_>>>()?;
+        let output_exprs = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+
+        let message = format!(
+            "assert_unique: duplicate values found for key columns [{}]",
+            key_columns
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        // only count the rows whose key columns are all non-null
+        let non_null_keys = key_columns
+            .iter()
+            .map(|c| Expr::Column(c.clone()).is_not_null())
+            .reduce(and)
+            .expect("at least one key column");
+
+        let count = Expr::WindowFunction {
+            fun: WindowFunction::AggregateFunction(AggregateFunction::Count),
+            args: vec![when(non_null_keys, lit(1u8)).end()?],
+            partition_by: key_columns.into_iter().map(Expr::Column).collect(),
+            order_by: vec![],
+            window_frame: None,
+            distinct: false,
+        }
+        .alias(ASSERT_UNIQUE_COUNT);
+
+        self.window(vec![count])?
+            .filter(Expr::ScalarUDF {
+                fun: Arc::new(assert_unique_udf()),
+                args: vec![
+                    Expr::Column(Column::from_name(ASSERT_UNIQUE_COUNT)),
+                    lit(message),
+                ],
+            })?
+            .project(output_exprs)
+    }
+
+    /// Append a column named `bucket_column` holding the index of the bucket
+    /// `column` falls into, given the ascending lower `bounds` of the buckets
+    /// (`width_bucket` semantics): values below the first bound are in
+    /// bucket 0, values in `[bounds[i], bounds[i + 1])` in bucket `i + 1`,
+    /// and values above the last bound in bucket `bounds.len()`.
+    pub fn bucketize(
+        &self,
+        column: Column,
+        bounds: Vec<ScalarValue>,
+        bucket_column: &str,
+    ) -> Result<Self> {
+        let column = Self::normalize(&self.plan, column)?;
+        let data_type = self.plan.schema().field_from_column(&column)?.data_type();
+        if bounds.is_empty() {
+            return Err(DataFusionError::Plan(
+                "bucketize requires at least one bound".to_string(),
+            ));
+        }
+        if let Some(bound) = bounds.iter().find(|b| &b.get_datatype() != data_type) {
+            return Err(DataFusionError::Plan(format!(
+                "bucketize bound {:?} does not match type {:?} of column {}",
+                bound, data_type, column
+            )));
+        }
+        if bounds.iter().any(|b| b.is_null()) || bounds.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(DataFusionError::Plan(format!(
+                "bucketize bounds must be non-null and sorted ascending, got {:?}",
+                bounds
+            )));
+        }
+
+        let value = Expr::Column(column);
+        let bucket =
+            |i: usize| Box::new(Expr::Literal(ScalarValue::Int32(Some(i as i32))));
+        let when_then_expr = iter::once((
+            Box::new(value.clone().is_null()),
+            Box::new(Expr::Literal(ScalarValue::Int32(None))),
+        ))
+        .chain(bounds.iter().enumerate().map(|(i, bound)| {
+            (
+                Box::new(value.clone().lt(Expr::Literal(bound.clone()))),
+                bucket(i),
+            )
+        }))
+        .collect();
+        let bucket_expr = Expr::Case {
+            expr: None,
+            when_then_expr,
+            else_expr: Some(bucket(bounds.len())),
+        };
+
+        let mut exprs = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+        exprs.push(bucket_expr.alias(bucket_column));
+        self.project(exprs)
+    }
+
+    /// Gather all the data of the plan into a single partition, making the
+    /// single partition requirement of e.g. a global sort explicit
+    pub fn coalesce_to_single_partition(&self) -> Result<Self> {
+        self.repartition(Partitioning::RoundRobinBatch(1))
+    }
+
     /// Apply a window functions to extend the schema
     pub fn window(
         &self,
@@ -648,6 +1353,74 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply window functions to extend the schema like [`Self::window`],
+    /// using `frame` as the window frame of every window function that does
+    /// not specify one
+    pub fn window_with_frame(
+        &self,
+        window_expr: impl IntoIterator<Item = impl Into<Expr>>,
+        frame: WindowFrame,
+    ) -> Result<Self> {
+        fn with_frame(expr: Expr, frame: WindowFrame) -> Expr {
+            match expr {
+                Expr::WindowFunction {
+                    fun,
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame: None,
+                    distinct,
+                } => Expr::WindowFunction {
+                    fun,
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame: Some(frame),
+                    distinct,
+                },
+                Expr::Alias(expr, name) => {
+                    Expr::Alias(Box::new(with_frame(*expr, frame)), name)
+                }
+                expr => expr,
+            }
+        }
+        self.window(
+            window_expr
+                .into_iter()
+                .map(|expr| with_frame(expr.into(), frame)),
+        )
+    }
+
+    /// Apply window functions to extend the schema like [`Self::window`],
+    /// marking every aggregate window function as `DISTINCT`, e.g.
+    /// `COUNT(DISTINCT x) OVER (...)`
+    pub fn window_distinct(&self, window_expr: Vec<Expr>) -> Result<Self> {
+        fn with_distinct(expr: Expr) -> Expr {
+            match expr {
+                Expr::WindowFunction {
+                    fun: fun @ WindowFunction::AggregateFunction(_),
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame,
+                    ..
+                } => Expr::WindowFunction {
+                    fun,
+                    args,
+                    partition_by,
+                    order_by,
+                    window_frame,
+                    distinct: true,
+                },
+                Expr::Alias(expr, name) => {
+                    Expr::Alias(Box::new(with_distinct(*expr)), name)
+                }
+                expr => expr,
+            }
+        }
+        self.window(window_expr.into_iter().map(with_distinct))
+    }
+
     /// Apply an aggregate: grouping on the `group_expr` expressions
     /// and calculating `aggr_expr` aggregates for each distinct
     /// value of the `group_expr`;
@@ -679,6 +1452,17 @@ impl LogicalPlanBuilder {
     ///
     /// if `verbose` is true, prints out additional details.
     pub fn explain(&self, verbose: bool, analyze: bool) -> Result<Self> {
+        self.explain_with_type(verbose, analyze, PlanType::InitialLogicalPlan)
+    }
+
+    /// Like [`Self::explain`], but labels the stringified plan with
+    /// `plan_type` instead of [`PlanType::InitialLogicalPlan`]
+    pub fn explain_with_type(
+        &self,
+        verbose: bool,
+        analyze: bool,
+        plan_type: PlanType,
+    ) -> Result<Self> {
         let schema = LogicalPlan::explain_schema();
         let schema = schema.to_dfschema_ref()?;
 
@@ -689,8 +1473,7 @@ impl LogicalPlanBuilder {
                 schema,
             })))
         } else {
-            let stringified_plans =
-                vec![self.plan.to_stringified(PlanType::InitialLogicalPlan)];
+            let stringified_plans = vec![self.plan.to_stringified(plan_type)];
 
             Ok(Self::from(LogicalPlan::Explain(Explain {
                 verbose,
@@ -701,32 +1484,153 @@ impl LogicalPlanBuilder {
         }
     }
 
-    /// Process intersect set operator
-    pub(crate) fn intersect(
-        left_plan: LogicalPlan,
-        right_plan: LogicalPlan,
-        is_all: bool,
-    ) -> Result<LogicalPlan> {
-        LogicalPlanBuilder::intersect_or_except(
-            left_plan,
-            right_plan,
-            JoinType::Semi,
-            is_all,
-        )
+    /// Apply `INTERSECT` with `right`, keeping the distinct rows of the
+    /// current plan that also appear in `right`
+    pub fn intersect_distinct(&self, right: &LogicalPlan) -> Result<Self> {
+        self.set_operation(right, "INTERSECT", |left, right| {
+            Self::intersect(left, right, false)
+        })
     }
 
-    /// Process except set operator
-    pub(crate) fn except(
+    /// Apply `INTERSECT ALL` with `right`, keeping the rows of the current
+    /// plan, including duplicates, that also appear in `right`
+    pub fn intersect_all(&self, right: &LogicalPlan) -> Result<Self> {
+        self.set_operation(right, "INTERSECT ALL", |left, right| {
+            Self::intersect(left, right, true)
+        })
+    }
+
+    /// Apply `EXCEPT` with `right`, keeping the distinct rows of the current
+    /// plan that do not appear in `right`
+    pub fn except_distinct(&self, right: &LogicalPlan) -> Result<Self> {
+        self.set_operation(right, "EXCEPT", |left, right| {
+            Self::except(left, right, false)
+        })
+    }
+
+    /// Apply `EXCEPT ALL` with `right`, subtracting the multiplicities of
+    /// the rows of `right` from the ones of the current plan
+    pub fn except_all(&self, right: &LogicalPlan) -> Result<Self> {
+        self.set_operation(right, "EXCEPT ALL", |left, right| {
+            Self::except(left, right, true)
+        })
+    }
+
+    /// Check that `right` has as many columns as the current plan and
+    /// combine both plans with `op`
+    fn set_operation(
+        &self,
+        right: &LogicalPlan,
+        name: &str,
+        op: impl FnOnce(LogicalPlan, LogicalPlan) -> Result<LogicalPlan>,
+    ) -> Result<Self> {
+        let left_len = self.plan.schema().fields().len();
+        let right_len = right.schema().fields().len();
+        if left_len != right_len {
+            return Err(DataFusionError::Plan(format!(
+                "{} inputs must have the same number of columns, got {} and {}",
+                name, left_len, right_len
+            )));
+        }
+        Ok(Self::from(op(self.plan.clone(), right.clone())?))
+    }
+
+    /// Process intersect set operator
+    pub(crate) fn intersect(
         left_plan: LogicalPlan,
         right_plan: LogicalPlan,
         is_all: bool,
     ) -> Result<LogicalPlan> {
-        LogicalPlanBuilder::intersect_or_except(
+        let plan = LogicalPlanBuilder::intersect_or_except(
             left_plan,
             right_plan,
-            JoinType::Anti,
+            JoinType::Semi,
             is_all,
-        )
+        )?;
+        // Like `union`, expose the columns of the left branch without
+        // qualifiers so that downstream references do not depend on
+        // which relation the rows came from. Columns with the same name
+        // under different qualifiers (e.g. `t1.a` and `t2.a`) keep their
+        // qualifiers, as their bare names would clash.
+        let names_clash = {
+            let mut names = HashSet::new();
+            !plan
+                .schema()
+                .fields()
+                .iter()
+                .all(|field| names.insert(field.name()))
+        };
+        if names_clash {
+            return Ok(plan);
+        }
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()).alias(field.name()))
+            .collect::<Vec<_>>();
+        LogicalPlanBuilder::from(plan).project(exprs)?.build()
+    }
+
+    /// Process except set operator
+    ///
+    /// `EXCEPT` returns the distinct rows of the left input that do not
+    /// appear in the right input. `EXCEPT ALL` subtracts multiplicities: a
+    /// row occurring `m` times on the left and `n` times on the right is
+    /// returned `max(m - n, 0)` times. This is implemented by numbering the
+    /// duplicates of each row on both sides and anti joining on the rows
+    /// together with their number.
+    pub(crate) fn except(
+        left_plan: LogicalPlan,
+        right_plan: LogicalPlan,
+        is_all: bool,
+    ) -> Result<LogicalPlan> {
+        if !is_all {
+            return LogicalPlanBuilder::intersect_or_except(
+                left_plan,
+                right_plan,
+                JoinType::Anti,
+                false,
+            );
+        }
+
+        let output_exprs = left_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+        let plan = LogicalPlanBuilder::intersect_or_except(
+            Self::number_duplicate_rows(left_plan)?,
+            Self::number_duplicate_rows(right_plan)?,
+            JoinType::Anti,
+            true,
+        )?;
+        LogicalPlanBuilder::from(plan)
+            .project(output_exprs)?
+            .build()
+    }
+
+    /// Tag every row with its occurrence number among identical rows
+    fn number_duplicate_rows(plan: LogicalPlan) -> Result<LogicalPlan> {
+        let partition_by = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect();
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by,
+            order_by: vec![],
+            window_frame: None,
+            distinct: false,
+        }
+        .alias(EXCEPT_ROW_NUMBER);
+        LogicalPlanBuilder::from(plan)
+            .window(vec![row_number])?
+            .build()
     }
 
     /// Process intersect or except
@@ -743,8 +1647,8 @@ impl LogicalPlanBuilder {
             .zip(right_plan.schema().fields().iter())
             .map(|(left_field, right_field)| {
                 (
-                    (Column::from_name(left_field.name())),
-                    (Column::from_name(right_field.name())),
+                    left_field.qualified_column(),
+                    right_field.qualified_column(),
                 )
             })
             .unzip();
@@ -791,6 +1695,28 @@ pub fn build_join_schema(
     DFSchema::new_with_metadata(fields, metadata)
 }
 
+/// Errors if the left and right key of a join pair have data types that
+/// cannot be compared for equality. Nullability may differ.
+fn validate_join_key_types(
+    left: &DFSchema,
+    right: &DFSchema,
+    on: &[(Column, Column)],
+) -> Result<()> {
+    for (left_key, right_key) in on {
+        let left_type = left.field_from_column(left_key)?.data_type();
+        let right_type = right.field_from_column(right_key)?.data_type();
+        if left_type != right_type
+            && coerce_types(left_type, &Operator::Eq, right_type).is_err()
+        {
+            return Err(DataFusionError::Plan(format!(
+                "Join key {} of type {:?} is not compatible with join key {} of type {:?}",
+                left_key, left_type, right_key, right_type
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Errors if one or more expressions have equal names.
 fn validate_unique_names<'a>(
     node_name: &str,
@@ -806,11 +1732,17 @@ fn validate_unique_names<'a>(
                 Ok(())
             },
             Some((existing_position, existing_expr)) => {
+                // the Debug output of window functions lists every partition
+                // and order expression, which makes the message unreadable
+                let describe = |expr: &Expr| match node_name {
+                    "Windows" => window_expr_summary(expr),
+                    _ => format!("{:?}", expr),
+                };
                 Err(DataFusionError::Plan(
                     format!("{} require unique expression names \
-                             but the expression \"{:?}\" at position {} and \"{:?}\" \
+                             but the expression \"{}\" at position {} and \"{}\" \
                              at position {} have the same name. Consider aliasing (\"AS\") one of them.",
-                             node_name, existing_expr, existing_position, expr, position,
+                             node_name, describe(existing_expr), existing_position, describe(expr), position,
                             )
                 ))
             }
@@ -818,6 +1750,26 @@ fn validate_unique_names<'a>(
     })
 }
 
+/// Short description of a window expression naming the window function and
+/// the number of its partition and order expressions
+fn window_expr_summary(expr: &Expr) -> String {
+    match expr {
+        Expr::WindowFunction {
+            fun,
+            partition_by,
+            order_by,
+            ..
+        } => format!(
+            "{} with {} partition and {} order expressions",
+            fun,
+            partition_by.len(),
+            order_by.len()
+        ),
+        Expr::Alias(expr, name) => format!("{} AS {}", window_expr_summary(expr), name),
+        expr => format!("{:?}", expr),
+    }
+}
+
 pub fn project_with_column_index_alias(
     expr: Vec<Expr>,
     input: Arc<LogicalPlan>,
@@ -892,6 +1844,78 @@ pub fn union_with_alias(
     }))
 }
 
+/// Union two logical plans, casting the columns of each input to the
+/// common type of the corresponding columns of both inputs
+/// # Errors
+/// This function errors if the inputs have a different number of columns,
+/// or if some pair of columns has no common type
+pub fn union_coerced(left: LogicalPlan, right: LogicalPlan) -> Result<LogicalPlan> {
+    let left_fields = left.schema().fields();
+    let right_fields = right.schema().fields();
+    if left_fields.len() != right_fields.len() {
+        return Err(DataFusionError::Plan(format!(
+            "Union inputs must have the same number of columns, got {} and {}",
+            left_fields.len(),
+            right_fields.len()
+        )));
+    }
+    let union_types = left_fields
+        .iter()
+        .zip(right_fields.iter())
+        .map(|(left_field, right_field)| {
+            let (left_type, right_type) =
+                (left_field.data_type(), right_field.data_type());
+            if left_type == right_type {
+                return Ok(left_type.clone());
+            }
+            coerce_types(left_type, &Operator::Eq, right_type).map_err(|_| {
+                DataFusionError::Plan(format!(
+                    "Union column {} has no common type for {:?} and {:?}",
+                    left_field.name(),
+                    left_type,
+                    right_type
+                ))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let left = coerce_union_input(left, &union_types)?;
+    let right = coerce_union_input(right, &union_types)?;
+    union_with_alias(left, right, None)
+}
+
+/// Project `plan` so that its columns have the types in `union_types`
+fn coerce_union_input(
+    plan: LogicalPlan,
+    union_types: &[DataType],
+) -> Result<LogicalPlan> {
+    let fields = plan.schema().fields();
+    if fields
+        .iter()
+        .zip(union_types)
+        .all(|(field, data_type)| field.data_type() == data_type)
+    {
+        return Ok(plan);
+    }
+    let expr = fields
+        .iter()
+        .zip(union_types)
+        .map(|(field, data_type)| {
+            let column = Expr::Column(field.qualified_column());
+            if field.data_type() == data_type {
+                column
+            } else {
+                Expr::Cast {
+                    expr: Box::new(column),
+                    data_type: data_type.clone(),
+                }
+                .alias(field.name())
+            }
+        })
+        .collect::<Vec<_>>();
+    project_with_alias(plan, expr, None)
+}
+
 /// Project with optional alias
 /// # Errors
 /// This function errors under any of the following conditions:
@@ -934,6 +1958,47 @@ pub fn project_with_alias(
     }))
 }
 
+/// The `assert_unique(count, message)` function used by
+/// [LogicalPlanBuilder::assert_unique]. It returns `true` for every row, or
+/// an execution error with `message` if any `count` is above one.
+///
+/// It is registered in every [SessionContext](crate::execution::context::SessionContext)
+/// so that plans calling it can be serialized and looked up by name.
+pub fn assert_unique_udf() -> ScalarUDF {
+    let check: ScalarFunctionImplementation = Arc::new(|args: &[ColumnarValue]| {
+        let counts = args[0].clone().into_array(1);
+        let counts = counts
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .ok_or_else(|| {
+                DataFusionError::Internal(
+                    "assert_unique expects Int64 counts".to_string(),
+                )
+            })?;
+        if counts.iter().any(|count| count.unwrap_or(0) > 1) {
+            return match &args[1] {
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some(message))) => {
+                    Err(DataFusionError::Execution(message.clone()))
+                }
+                _ => Err(DataFusionError::Internal(
+                    "assert_unique expects a literal message".to_string(),
+                )),
+            };
+        }
+        Ok(ColumnarValue::Array(Arc::new(BooleanArray::from(vec![
+            true;
+            counts.len()
+        ]))))
+    });
+    create_udf(
+        "assert_unique",
+        vec![DataType::Int64, DataType::Utf8],
+        Arc::new(DataType::Boolean),
+        Volatility::Volatile,
+        check,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use arrow::datatypes::{DataType, Field};
@@ -1031,6 +2096,161 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_sort_by() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![3, 4]),
+        )?
+        .sort_by(vec![(col("state"), true), (col("salary"), false)])?
+        .build()?;
+
+        let expected = "Sort: #employee_csv.state ASC NULLS FIRST, #employee_csv.salary DESC NULLS LAST\
+        \n  TableScan: employee_csv projection=Some([3, 4])";
+
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_limit_with_offset() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![3, 4]),
+        )?
+        .limit_with_offset(2, 10)?
+        .build()?;
+
+        let expected = "Limit: skip=2, fetch=10\
+        \n  TableScan: employee_csv projection=Some([3, 4])";
+
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_values_with_names() -> Result<()> {
+        let values = || {
+            vec![
+                vec![lit(1), lit("a")],
+                vec![lit(ScalarValue::Null), lit("b")],
+            ]
+        };
+        let plan = LogicalPlanBuilder::values_with_names(
+            values(),
+            vec!["id".to_string(), "name".to_string()],
+        )?
+        .project(vec![col("name"), col("id")])?
+        .build()?;
+
+        let expected = "Projection: #name, #id\
+        \n  Values: (Int32(1), Utf8(\"a\")), (Int32(NULL), Utf8(\"b\"))";
+        assert_eq!(expected, format!("{:?}", plan));
+        let field = plan.schema().field_with_unqualified_name("id")?;
+        assert_eq!(field.data_type(), &DataType::Int32);
+
+        let err = LogicalPlanBuilder::values_with_names(values(), vec!["id".to_string()])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Expected 2 column names for values list, got 1"
+        );
+        let err = LogicalPlanBuilder::values_with_names(
+            values(),
+            vec!["id".to_string(), "".to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Values list column names cannot be empty"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_filter_and() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .filter_and(col("state").eq(lit("CO")))?
+        .filter_and(col("salary").gt(lit(1000)))?
+        .filter_and(col("id").lt(lit(10)))?
+        .build()?;
+
+        let expected = "Filter: #employee_csv.state = Utf8(\"CO\") \
+        AND #employee_csv.salary > Int32(1000) AND #employee_csv.id < Int32(10)\
+        \n  TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_filter_in_set() -> Result<()> {
+        let scan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?;
+
+        let plan = scan
+            .filter_in_set(
+                Column::from_name("state"),
+                vec![
+                    ScalarValue::Utf8(Some("CO".to_string())),
+                    ScalarValue::Utf8(Some("CA".to_string())),
+                    ScalarValue::Utf8(Some("CO".to_string())),
+                ],
+                true,
+            )?
+            .build()?;
+        let expected =
+            "Filter: #employee_csv.state NOT IN ([Utf8(\"CO\"), Utf8(\"CA\")])\
+        \n  TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        // large sets stay a single IN list of literals
+        let values = (0..3000).map(|i| ScalarValue::Int32(Some(i % 1500)));
+        let plan = scan
+            .filter_in_set(Column::from_name("id"), values.collect(), false)?
+            .build()?;
+        match &plan {
+            LogicalPlan::Filter(Filter {
+                predicate: Expr::InList { list, negated, .. },
+                ..
+            }) => {
+                assert!(!negated);
+                assert_eq!(list.len(), 1500);
+                assert!(list.iter().all(|e| matches!(e, Expr::Literal(_))));
+            }
+            _ => panic!("expected an IN list filter, got {:?}", plan),
+        }
+
+        let err = scan
+            .filter_in_set(
+                Column::from_name("id"),
+                vec![ScalarValue::Utf8(Some("1".to_string()))],
+                false,
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("filter_in_set values must have the type Int32"));
+
+        assert!(scan
+            .filter_in_set(Column::from_name("missing"), vec![], false)
+            .is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn plan_using_join_wildcard_projection() -> Result<()> {
         let t2 = LogicalPlanBuilder::scan_empty(Some("t2"), &employee_schema(), None)?
@@ -1052,6 +2272,81 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_semi_anti_join() -> Result<()> {
+        let t2 = LogicalPlanBuilder::scan_empty(Some("t2"), &employee_schema(), None)?
+            .build()?;
+
+        let plan = LogicalPlanBuilder::scan_empty(Some("t1"), &employee_schema(), None)?
+            .semi_join(&t2, (vec!["id"], vec!["id"]))?
+            .build()?;
+        let expected = "Semi Join: #t1.id = #t2.id\
+        \n  TableScan: t1 projection=None\
+        \n  TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        // only the left columns are returned
+        assert_eq!(
+            plan.schema().fields().len(),
+            employee_schema().fields().len()
+        );
+
+        let plan = LogicalPlanBuilder::scan_empty(Some("t1"), &employee_schema(), None)?
+            .anti_join(&t2, (vec!["id", "state"], vec!["id", "state"]))?
+            .build()?;
+        let expected = "Anti Join: #t1.id = #t2.id, #t1.state = #t2.state\
+        \n  TableScan: t1 projection=None\
+        \n  TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let err = LogicalPlanBuilder::scan_empty(Some("t1"), &employee_schema(), None)?
+            .semi_join(&t2, (vec!["id", "state"], vec!["id"]))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: left_keys and right_keys were not the same length"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_join_key_type_mismatch() -> Result<()> {
+        let t1 = LogicalPlanBuilder::scan_empty(
+            Some("t1"),
+            &Schema::new(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Int64, true),
+            ]),
+            None,
+        )?;
+        let t2 = LogicalPlanBuilder::scan_empty(
+            Some("t2"),
+            &Schema::new(vec![
+                Field::new("a", DataType::Utf8, true),
+                Field::new("b", DataType::Int32, true),
+            ]),
+            None,
+        )?
+        .build()?;
+
+        let err = t1.join_using(&t2, JoinType::Inner, vec!["a"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Join key #t1.a of type Int32 is not compatible \
+            with join key #t2.a of type Utf8"
+        );
+        let err = t1
+            .join(&t2, JoinType::Inner, (vec!["a"], vec!["a"]))
+            .unwrap_err();
+        assert!(err.to_string().contains("is not compatible"), "{}", err);
+
+        // numeric keys that can be coerced are allowed, as is differing nullability
+        t1.join(&t2, JoinType::Inner, (vec!["b"], vec!["b"]))?;
+        t1.join(&t2, JoinType::Inner, (vec!["a"], vec!["b"]))?;
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_union_combined_single_union() -> Result<()> {
         let plan = LogicalPlanBuilder::scan_empty(
@@ -1079,65 +2374,773 @@ mod tests {
     }
 
     #[test]
-    fn exists_subquery() -> Result<()> {
-        let foo = test_table_scan_with_name("foo")?;
-        let bar = test_table_scan_with_name("bar")?;
-
-        let subquery = LogicalPlanBuilder::from(foo)
-            .project(vec![col("a")])?
-            .filter(col("a").eq(col("bar.a")))?
-            .build()?;
-
-        let outer_query = LogicalPlanBuilder::from(bar)
-            .project(vec![col("a")])?
-            .filter(exists(Arc::new(subquery)))?
+    fn plan_builder_project_indexed_alias() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .project_indexed_alias(
+                vec![col("t1.a"), col("t1.b") + lit(1u32), lit(2u32)],
+                Some("t".to_string()),
+            )?
             .build()?;
 
-        let expected = "Filter: EXISTS (\
-            Subquery: Filter: #foo.a = #bar.a\
-            \n  Projection: #foo.a\
-            \n    TableScan: foo projection=None)\
-        \n  Projection: #bar.a\n    TableScan: bar projection=None";
-        assert_eq!(expected, format!("{:?}", outer_query));
+        let expected = "Projection: #t1.a, #t1.b + UInt32(1) AS column1, UInt32(2) AS column2, alias=t\
+        \n  TableScan: t1 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        assert_eq!(plan.schema().field_names(), vec!["t1.a", "t1.b", "t1.c"]);
 
         Ok(())
     }
 
     #[test]
-    fn filter_in_subquery() -> Result<()> {
-        let foo = test_table_scan_with_name("foo")?;
-        let bar = test_table_scan_with_name("bar")?;
+    fn plan_builder_drop_columns() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            None,
+        )?
+        .drop_columns(&["employee_csv.last_name"])?
+        .build()?;
 
-        let subquery = LogicalPlanBuilder::from(foo)
-            .project(vec![col("a")])?
-            .filter(col("a").eq(col("bar.a")))?
-            .build()?;
+        let expected = "Projection: #employee_csv.id, #employee_csv.first_name, #employee_csv.state, #employee_csv.salary\
+        \n  TableScan: employee_csv projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        for field in plan.schema().fields() {
+            assert_eq!(field.qualifier().map(|q| q.as_str()), Some("employee_csv"));
+        }
 
-        // SELECT a FROM bar WHERE a IN (SELECT a FROM foo WHERE a = bar.a)
-        let outer_query = LogicalPlanBuilder::from(bar)
-            .project(vec![col("a")])?
-            .filter(in_subquery(col("a"), Arc::new(subquery)))?
-            .build()?;
+        Ok(())
+    }
 
-        let expected = "Filter: #bar.a IN (Subquery: Filter: #foo.a = #bar.a\
-        \n  Projection: #foo.a\
-        \n    TableScan: foo projection=None)\
-        \n  Projection: #bar.a\
-        \n    TableScan: bar projection=None";
-        assert_eq!(expected, format!("{:?}", outer_query));
+    #[test]
+    fn plan_builder_drop_missing_column() -> Result<()> {
+        let err = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            None,
+        )?
+        .drop_columns(&["age"])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: No field named 'age'. Valid fields are 'employee_csv.id', \
+            'employee_csv.first_name', 'employee_csv.last_name', 'employee_csv.state', \
+            'employee_csv.salary'."
+        );
 
         Ok(())
     }
 
     #[test]
-    fn select_scalar_subquery() -> Result<()> {
-        let foo = test_table_scan_with_name("foo")?;
-        let bar = test_table_scan_with_name("bar")?;
+    fn plan_builder_union_coerced() -> Result<()> {
+        let t1 = LogicalPlanBuilder::scan_empty(
+            Some("t1"),
+            &Schema::new(vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Utf8, false),
+            ]),
+            None,
+        )?
+        .build()?;
+        let t2 = LogicalPlanBuilder::scan_empty(
+            Some("t2"),
+            &Schema::new(vec![
+                Field::new("a", DataType::Int64, false),
+                Field::new("b", DataType::Utf8, false),
+            ]),
+            None,
+        )?
+        .build()?;
 
-        let subquery = LogicalPlanBuilder::from(foo)
-            .project(vec![col("b")])?
-            .filter(col("a").eq(col("bar.a")))?
-            .build()?;
+        let plan = union_coerced(t1, t2)?;
+
+        let expected = "Union\
+        \n  Projection: CAST(#t1.a AS Int64) AS a, #t1.b\
+        \n    TableScan: t1 projection=None\
+        \n  TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        let types = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec![DataType::Int64, DataType::Utf8]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_union_coerced_incompatible() -> Result<()> {
+        let t1 = LogicalPlanBuilder::scan_empty(
+            Some("t1"),
+            &Schema::new(vec![Field::new("a", DataType::Utf8, false)]),
+            None,
+        )?
+        .build()?;
+        let t2 = LogicalPlanBuilder::scan_empty(
+            Some("t2"),
+            &Schema::new(vec![Field::new(
+                "a",
+                DataType::Struct(vec![Field::new("x", DataType::Int32, true)]),
+                false,
+            )]),
+            None,
+        )?
+        .build()?;
+
+        let err = union_coerced(t1, t2).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Union column a has no common type"),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_intersect_unqualified_schema() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        let plan = LogicalPlanBuilder::intersect(t1, t2, false)?;
+
+        for field in plan.schema().fields() {
+            assert_eq!(None, field.qualifier());
+        }
+        assert_eq!(plan.schema().field_names(), vec!["a", "b", "c"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_scan_partitions() -> Result<()> {
+        let provider = Arc::new(
+            EmptyTable::new(Arc::new(employee_schema()))
+                .with_partition_cols(vec!["state".to_string()]),
+        );
+        let plan = LogicalPlanBuilder::scan_partitions(
+            "employee_csv",
+            provider,
+            Some(vec![0, 3]),
+            vec![col("state").eq(lit("CO"))],
+        )?
+        .build()?;
+
+        match &plan {
+            LogicalPlan::TableScan(TableScan {
+                filters,
+                partition_filters,
+                ..
+            }) => {
+                assert!(filters.is_empty());
+                assert_eq!(partition_filters, &vec![col("state").eq(lit("CO"))]);
+            }
+            _ => panic!("expected TableScan, got {:?}", plan),
+        }
+
+        let expected = "TableScan: employee_csv projection=Some([0, 3]), \
+        partition_filters=[#state = Utf8(\"CO\")]";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        // partition filters are rewritable plan expressions
+        let filter = col("state").eq(lit("CA"));
+        let plan = utils::from_plan(&plan, &[filter.clone()], &[])?;
+        assert_eq!(plan.expressions(), vec![filter]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_scan_partitions_invalid() {
+        let unpartitioned = Arc::new(EmptyTable::new(Arc::new(employee_schema())));
+        let err = LogicalPlanBuilder::scan_partitions(
+            "employee_csv",
+            unpartitioned,
+            None,
+            vec![col("state").eq(lit("CO"))],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Table employee_csv does not expose any partition columns"
+        );
+
+        let provider = Arc::new(
+            EmptyTable::new(Arc::new(employee_schema()))
+                .with_partition_cols(vec!["state".to_string()]),
+        );
+        let err = LogicalPlanBuilder::scan_partitions(
+            "employee_csv",
+            provider.clone(),
+            None,
+            vec![col("id").eq(lit(1))],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Partition filter #id = Int32(1) references non-partition column id"
+        );
+
+        let err = LogicalPlanBuilder::scan_partitions(
+            "employee_csv",
+            provider,
+            Some(vec![0]),
+            vec![col("state").eq(lit("CO"))],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Partition filter #state = Utf8(\"CO\") references column state which is not in the projection"
+        );
+    }
+
+    #[test]
+    fn plan_builder_coalesce_to_single_partition() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .coalesce_to_single_partition()?
+        .build()?;
+
+        match &plan {
+            LogicalPlan::Repartition(Repartition {
+                partitioning_scheme,
+                input,
+            }) => {
+                assert!(matches!(
+                    partitioning_scheme,
+                    Partitioning::RoundRobinBatch(1)
+                ));
+                assert_eq!(plan.schema(), input.schema());
+            }
+            _ => panic!("expected Repartition, got {:?}", plan),
+        }
+
+        let expected = "Repartition: RoundRobinBatch partition_count=1\
+        \n  TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_explain_estimated_rows() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .filter(col("state").eq(lit("CO")))?
+        .with_estimated_rows(42)
+        .explain(true, false)?
+        .build()?;
+
+        let stringified_plans = match plan {
+            LogicalPlan::Explain(Explain {
+                stringified_plans, ..
+            }) => stringified_plans,
+            _ => panic!("expected Explain, got {:?}", plan),
+        };
+        let expected = "EstimatedRows: 42\
+        \n  Filter: #employee_csv.state = Utf8(\"CO\")\
+        \n    TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, stringified_plans[0].plan.as_str());
+        assert!(stringified_plans[0].should_display(true));
+
+        // the estimate is part of the plan, so it survives rebuilding it
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .with_estimated_rows(42)
+        .maybe_limit(None)?
+        .build()?;
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let plan = utils::from_plan(&plan, &plan.expressions(), &inputs)?;
+        let expected = "EstimatedRows: 42\
+        \n  TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_explain_with_type() -> Result<()> {
+        let plan_type = PlanType::OptimizedLogicalPlan {
+            optimizer_name: "my_rule".to_string(),
+        };
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .explain_with_type(false, false, plan_type.clone())?
+        .build()?;
+
+        match plan {
+            LogicalPlan::Explain(Explain {
+                stringified_plans, ..
+            }) => {
+                assert_eq!(stringified_plans.len(), 1);
+                assert_eq!(stringified_plans[0].plan_type, plan_type);
+                assert_eq!(
+                    "TableScan: employee_csv projection=Some([0, 3])",
+                    stringified_plans[0].plan.as_str()
+                );
+            }
+            _ => panic!("expected Explain, got {:?}", plan),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_dedup_consecutive() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .dedup_consecutive(
+            vec![Column::from_name("state")],
+            vec![col("id").sort(true, false)],
+        )?
+        .build()?;
+
+        let expected = "Projection: #employee_csv.id, #employee_csv.state\
+        \n  Filter: #__datafusion_dedup_row_number = UInt64(1) \
+        OR #__datafusion_dedup_previous_key_0 IS DISTINCT FROM #employee_csv.state\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() ORDER BY [#employee_csv.id ASC NULLS LAST] \
+        AS __datafusion_dedup_row_number, \
+        LAG(#employee_csv.state) ORDER BY [#employee_csv.id ASC NULLS LAST] \
+        AS __datafusion_dedup_previous_key_0]]\
+        \n      TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let err = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .dedup_consecutive(vec![Column::from_name("state")], vec![])
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires at least one sort expression"),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_sort_total() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .sort_total(vec![col("salary").sort(false, true)])?
+        .build()?;
+
+        let expected = "Sort: #employee_csv.salary DESC NULLS FIRST, \
+        #employee_csv.id ASC NULLS LAST, #employee_csv.state ASC NULLS LAST\
+        \n  TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let err = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .sort_total(vec![col("salary")])
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("sort_total expects sort expressions"),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_assert_unique() -> Result<()> {
+        let scan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?;
+        let plan = scan
+            .assert_unique(vec![Column::from_name("id"), Column::from_name("state")])?
+            .build()?;
+
+        let expected = "Projection: #employee_csv.id, #employee_csv.state, #employee_csv.salary\
+        \n  Filter: assert_unique(#__datafusion_assert_unique_count, Utf8(\"assert_unique: \
+        duplicate values found for key columns [employee_csv.id, employee_csv.state]\"))\
+        \n    WindowAggr: windowExpr=[[COUNT(CASE WHEN #employee_csv.id IS NOT NULL \
+        AND #employee_csv.state IS NOT NULL THEN UInt8(1) END) \
+        PARTITION BY [#employee_csv.id, #employee_csv.state] \
+        AS __datafusion_assert_unique_count]]\
+        \n      TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+        assert_eq!(plan.schema(), scan.build()?.schema());
+
+        let err = scan
+            .assert_unique(vec![Column::from_name("first_name")])
+            .unwrap_err();
+        assert!(err.to_string().contains("first_name"), "{}", err);
+        assert!(scan.assert_unique(vec![]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_window_duplicate_names() -> Result<()> {
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: vec![col("state"), col("last_name")],
+            order_by: vec![col("salary").sort(false, false)],
+            window_frame: None,
+            distinct: false,
+        };
+
+        let err = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            None,
+        )?
+        .window(vec![row_number.clone(), row_number])
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(
+            message.contains("ROW_NUMBER with 2 partition and 1 order expressions"),
+            "{}",
+            message
+        );
+        assert!(!message.contains("PARTITION BY"), "{}", message);
+        assert!(!message.contains("#employee_csv.state"), "{}", message);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_window_with_frame() -> Result<()> {
+        use datafusion_expr::{WindowFrameBound, WindowFrameUnits};
+
+        let frame = WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(Some(1)),
+            end_bound: WindowFrameBound::CurrentRow,
+        };
+        let framed = WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(None),
+            end_bound: WindowFrameBound::CurrentRow,
+        };
+        let window = |name: &str, window_frame| {
+            Expr::WindowFunction {
+                fun: WindowFunction::AggregateFunction(AggregateFunction::Sum),
+                args: vec![col("salary")],
+                partition_by: vec![col("state")],
+                order_by: vec![col("id").sort(true, false)],
+                window_frame,
+                distinct: false,
+            }
+            .alias(name)
+        };
+
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .window_with_frame(
+            vec![window("moving", None), window("running", Some(framed))],
+            frame,
+        )?
+        .build()?;
+
+        match &plan {
+            LogicalPlan::Window(Window { window_expr, .. }) => {
+                let frames = window_expr
+                    .iter()
+                    .map(|expr| match expr {
+                        Expr::Alias(expr, _) => match expr.as_ref() {
+                            Expr::WindowFunction { window_frame, .. } => *window_frame,
+                            _ => panic!("expected a window function, got {:?}", expr),
+                        },
+                        _ => panic!("expected an alias, got {:?}", expr),
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(frames, vec![Some(frame), Some(framed)]);
+            }
+            _ => panic!("expected Window, got {:?}", plan),
+        }
+        let names = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["moving", "running", "id", "state", "salary"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_window_distinct() -> Result<()> {
+        let window = Expr::WindowFunction {
+            fun: WindowFunction::AggregateFunction(AggregateFunction::Count),
+            args: vec![col("state")],
+            partition_by: vec![],
+            order_by: vec![col("id").sort(true, false)],
+            window_frame: None,
+            distinct: false,
+        };
+
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .window_distinct(vec![window])?
+        .build()?;
+
+        let is_distinct = |plan: &LogicalPlan| match plan {
+            LogicalPlan::Window(Window { window_expr, .. }) => {
+                matches!(window_expr[0], Expr::WindowFunction { distinct: true, .. })
+            }
+            _ => panic!("expected Window, got {:?}", plan),
+        };
+        assert!(is_distinct(&plan));
+
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let rebuilt = utils::from_plan(&plan, &plan.expressions(), &inputs)?;
+        assert!(is_distinct(&rebuilt));
+
+        let expected = "WindowAggr: windowExpr=[[COUNT(DISTINCT #employee_csv.state) ORDER BY [#employee_csv.id ASC NULLS LAST]]]\
+        \n  TableScan: employee_csv projection=Some([0, 3])";
+        assert_eq!(expected, format!("{:?}", rebuilt));
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_distinct_on() -> Result<()> {
+        let scan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?;
+        let plan = scan
+            .distinct_on(
+                vec![col("state")],
+                vec![col("state"), col("id")],
+                Some(vec![col("salary").sort(false, true)]),
+            )?
+            .build()?;
+
+        let expected = "Projection: #employee_csv.state, #employee_csv.id\
+        \n  Filter: #__datafusion_distinct_on_row_number = UInt64(1)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [#employee_csv.state] \
+        ORDER BY [#employee_csv.salary DESC NULLS FIRST] AS __datafusion_distinct_on_row_number]]\
+        \n      TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let plan = scan
+            .distinct_on(vec![col("state"), col("id")], vec![col("salary")], None)?
+            .build()?;
+        let expected = "Projection: #employee_csv.salary\
+        \n  Filter: #__datafusion_distinct_on_row_number = UInt64(1)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [#employee_csv.state, #employee_csv.id] \
+        AS __datafusion_distinct_on_row_number]]\
+        \n      TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let err = scan.distinct_on(vec![], vec![col("id")], None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: DISTINCT ON requires at least one expression"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_top_n_per_group() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .top_n_per_group(vec![col("state")], vec![col("salary").sort(false, true)], 3)?
+        .build()?;
+
+        let expected = "Projection: #employee_csv.id, #employee_csv.state, #employee_csv.salary\
+        \n  Filter: #__datafusion_top_n_row_number <= UInt64(3)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [#employee_csv.state] \
+        ORDER BY [#employee_csv.salary DESC NULLS FIRST] AS __datafusion_top_n_row_number]]\
+        \n      TableScan: employee_csv projection=Some([0, 3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let input_schema = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .build()?
+        .schema()
+        .clone();
+        assert_eq!(plan.schema(), &input_schema);
+
+        let err = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .top_n_per_group(vec![col("employee_csv.nope")], vec![], 3)
+        .unwrap_err();
+        assert!(err.to_string().contains("nope"), "{}", err);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_bucketize() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 4]),
+        )?
+        .bucketize(
+            Column::from_name("salary"),
+            vec![
+                ScalarValue::Int32(Some(1000)),
+                ScalarValue::Int32(Some(2000)),
+                ScalarValue::Int32(Some(5000)),
+            ],
+            "bucket",
+        )?
+        .build()?;
+
+        let bucket_expr = match &plan {
+            LogicalPlan::Projection(Projection { expr, .. }) => match &expr[2] {
+                Expr::Alias(expr, name) => {
+                    assert_eq!(name, "bucket");
+                    expr.as_ref().clone()
+                }
+                e => panic!("expected alias, got {:?}", e),
+            },
+            _ => panic!("expected Projection, got {:?}", plan),
+        };
+        match &bucket_expr {
+            Expr::Case {
+                expr: None,
+                when_then_expr,
+                else_expr: Some(else_expr),
+            } => {
+                assert_eq!(when_then_expr.len(), 4);
+                assert_eq!(**else_expr, lit(3));
+            }
+            e => panic!("expected CASE, got {:?}", e),
+        }
+        let expected = "CASE WHEN #employee_csv.salary IS NULL THEN Int32(NULL) \
+        WHEN #employee_csv.salary < Int32(1000) THEN Int32(0) \
+        WHEN #employee_csv.salary < Int32(2000) THEN Int32(1) \
+        WHEN #employee_csv.salary < Int32(5000) THEN Int32(2) \
+        ELSE Int32(3) END";
+        assert_eq!(expected, format!("{:?}", bucket_expr));
+
+        let field = plan.schema().field_with_unqualified_name("bucket")?;
+        assert_eq!(field.data_type(), &DataType::Int32);
+        assert_eq!(plan.schema().fields().len(), 3);
+
+        // bounds must be sorted and of the column type
+        let scan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            None,
+        )?;
+        let unsorted = vec![ScalarValue::Int32(Some(2)), ScalarValue::Int32(Some(1))];
+        assert!(scan
+            .bucketize(Column::from_name("salary"), unsorted, "b")
+            .is_err());
+        let mistyped = vec![ScalarValue::Int64(Some(1))];
+        assert!(scan
+            .bucketize(Column::from_name("salary"), mistyped, "b")
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn exists_subquery() -> Result<()> {
+        let foo = test_table_scan_with_name("foo")?;
+        let bar = test_table_scan_with_name("bar")?;
+
+        let subquery = LogicalPlanBuilder::from(foo)
+            .project(vec![col("a")])?
+            .filter(col("a").eq(col("bar.a")))?
+            .build()?;
+
+        let outer_query = LogicalPlanBuilder::from(bar)
+            .project(vec![col("a")])?
+            .filter(exists(Arc::new(subquery)))?
+            .build()?;
+
+        let expected = "Filter: EXISTS (\
+            Subquery: Filter: #foo.a = #bar.a\
+            \n  Projection: #foo.a\
+            \n    TableScan: foo projection=None)\
+        \n  Projection: #bar.a\n    TableScan: bar projection=None";
+        assert_eq!(expected, format!("{:?}", outer_query));
+
+        Ok(())
+    }
+
+    #[test]
+    fn filter_in_subquery() -> Result<()> {
+        let foo = test_table_scan_with_name("foo")?;
+        let bar = test_table_scan_with_name("bar")?;
+
+        let subquery = LogicalPlanBuilder::from(foo)
+            .project(vec![col("a")])?
+            .filter(col("a").eq(col("bar.a")))?
+            .build()?;
+
+        // SELECT a FROM bar WHERE a IN (SELECT a FROM foo WHERE a = bar.a)
+        let outer_query = LogicalPlanBuilder::from(bar)
+            .project(vec![col("a")])?
+            .filter(in_subquery(col("a"), Arc::new(subquery)))?
+            .build()?;
+
+        let expected = "Filter: #bar.a IN (Subquery: Filter: #foo.a = #bar.a\
+        \n  Projection: #foo.a\
+        \n    TableScan: foo projection=None)\
+        \n  Projection: #bar.a\
+        \n    TableScan: bar projection=None";
+        assert_eq!(expected, format!("{:?}", outer_query));
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_scalar_subquery() -> Result<()> {
+        let foo = test_table_scan_with_name("foo")?;
+        let bar = test_table_scan_with_name("bar")?;
+
+        let subquery = LogicalPlanBuilder::from(foo)
+            .project(vec![col("b")])?
+            .filter(col("a").eq(col("bar.a")))?
+            .build()?;
 
         // SELECT (SELECT a FROM foo WHERE a = bar.a) FROM bar
         let outer_query = LogicalPlanBuilder::from(bar)
@@ -1246,4 +3249,270 @@ mod tests {
         assert!(stringified_plan.should_display(true));
         assert!(!stringified_plan.should_display(false));
     }
+
+    #[test]
+    fn plan_builder_cross_join_many() -> Result<()> {
+        let inputs = vec![
+            test_table_scan_with_name("t1")?,
+            test_table_scan_with_name("t2")?,
+            test_table_scan_with_name("t3")?,
+        ];
+        let field_count: usize = inputs.iter().map(|p| p.schema().fields().len()).sum();
+
+        let plan = LogicalPlanBuilder::cross_join_many(inputs)?;
+        assert_eq!(plan.schema().fields().len(), field_count);
+
+        let expected = "CrossJoin:\
+        \n  CrossJoin:\
+        \n    TableScan: t1 projection=None\
+        \n    TableScan: t2 projection=None\
+        \n  TableScan: t3 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let single =
+            LogicalPlanBuilder::cross_join_many(vec![test_table_scan_with_name("t1")?])?;
+        assert_eq!(
+            format!("{:?}", single),
+            format!("{:?}", test_table_scan_with_name("t1")?)
+        );
+
+        let err = LogicalPlanBuilder::cross_join_many(vec![]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: cross_join_many requires at least one input"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_having() -> Result<()> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![3, 4]),
+        )?
+        .aggregate(
+            vec![col("state")],
+            vec![sum(col("salary")).alias("total_salary")],
+        )?
+        .having(
+            col("total_salary")
+                .gt(lit(100))
+                .and(col("state").not_eq(lit("CO"))),
+        )?
+        .build()?;
+
+        let expected = "Filter: #total_salary > Int32(100) AND #employee_csv.state != Utf8(\"CO\")\
+        \n  Aggregate: groupBy=[[#employee_csv.state]], aggr=[[SUM(#employee_csv.salary) AS total_salary]]\
+        \n    TableScan: employee_csv projection=Some([3, 4])";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_having_without_aggregate() -> Result<()> {
+        let err = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .having(col("a").gt(lit(1)))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("having can only be applied to an aggregate"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn exists_subquery_with_builder() -> Result<()> {
+        let foo = test_table_scan_with_name("foo")?;
+        let bar = test_table_scan_with_name("bar")?;
+
+        let subquery = LogicalPlanBuilder::from(foo)
+            .project(vec![col("a")])?
+            .filter(col("a").eq(col("bar.a")))?
+            .subquery()?
+            .build()?;
+        match &subquery {
+            LogicalPlan::Subquery(Subquery { subquery }) => {
+                assert!(matches!(subquery.as_ref(), LogicalPlan::Filter(_)));
+            }
+            other => panic!("expected a Subquery, got {:?}", other),
+        }
+
+        let outer_query = LogicalPlanBuilder::from(bar)
+            .project(vec![col("a")])?
+            .filter(exists(Arc::new(subquery.clone())))?
+            .build()?;
+
+        match &outer_query {
+            LogicalPlan::Filter(Filter {
+                predicate:
+                    Expr::Exists {
+                        subquery: Subquery { subquery: inner },
+                        negated: false,
+                    },
+                ..
+            }) => assert_eq!(format!("{:?}", inner), format!("{:?}", subquery)),
+            other => panic!("expected an EXISTS filter, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn filter_in_subquery_as() -> Result<()> {
+        let foo = test_table_scan_with_name("foo")?;
+        let bar = test_table_scan_with_name("bar")?;
+
+        let subquery = LogicalPlanBuilder::from(foo)
+            .project(vec![col("a")])?
+            .filter(col("a").eq(col("bar.a")))?
+            .subquery_as("sq")?;
+
+        // the columns of the subquery are qualified by its alias
+        let plan = subquery.project(vec![col("sq.a")])?.build()?;
+        assert_eq!(plan.schema().field(0).qualified_name(), "sq.a");
+
+        let subquery = subquery.build()?;
+        match &subquery {
+            LogicalPlan::SubqueryAlias(SubqueryAlias { alias, input, .. }) => {
+                assert_eq!(alias, "sq");
+                assert!(matches!(input.as_ref(), LogicalPlan::Subquery(_)));
+            }
+            other => panic!("expected a SubqueryAlias, got {:?}", other),
+        }
+
+        // SELECT a FROM bar WHERE a IN (SELECT a FROM foo WHERE a = bar.a) AS sq
+        let outer_query = LogicalPlanBuilder::from(bar)
+            .project(vec![col("a")])?
+            .filter(in_subquery(col("a"), Arc::new(subquery.clone())))?
+            .build()?;
+
+        match &outer_query {
+            LogicalPlan::Filter(Filter {
+                predicate:
+                    Expr::InSubquery {
+                        subquery: Subquery { subquery: inner },
+                        negated: false,
+                        ..
+                    },
+                ..
+            }) => assert_eq!(format!("{:?}", inner), format!("{:?}", subquery)),
+            other => panic!("expected an IN filter, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_intersect_distinct() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        let plan = LogicalPlanBuilder::from(t1)
+            .intersect_distinct(&t2)?
+            .build()?;
+
+        let expected = "Projection: #t1.a AS a, #t1.b AS b, #t1.c AS c\
+        \n  Semi Join: #t1.a = #t2.a, #t1.b = #t2.b, #t1.c = #t2.c\
+        \n    Projection: #t1.a, #t1.b, #t1.c\
+        \n      Aggregate: groupBy=[[#t1.a, #t1.b, #t1.c]], aggr=[[]]\
+        \n        TableScan: t1 projection=None\
+        \n    TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_intersect_same_names() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+        let t3 = test_table_scan_with_name("t3")?;
+
+        // SELECT t1.a, t2.a FROM t1, t2 INTERSECT SELECT a, b FROM t3
+        let plan = LogicalPlanBuilder::from(t1)
+            .cross_join(&t2)?
+            .project(vec![col("t1.a"), col("t2.a")])?
+            .intersect_distinct(
+                &LogicalPlanBuilder::from(t3)
+                    .project(vec![col("a"), col("b")])?
+                    .build()?,
+            )?
+            .build()?;
+
+        // the bare names would clash, so the qualifiers are kept
+        assert!(matches!(plan, LogicalPlan::Join(_)));
+        assert_eq!(plan.schema().field_names(), vec!["t1.a", "t2.a"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_except_all() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        let plan = LogicalPlanBuilder::from(t1).except_all(&t2)?.build()?;
+        assert_eq!(plan.schema().field_names(), vec!["t1.a", "t1.b", "t1.c"]);
+
+        // the rows are anti joined together with their occurrence number
+        let input = match &plan {
+            LogicalPlan::Projection(Projection { input, .. }) => input.as_ref(),
+            other => panic!("expected a Projection, got {:?}", other),
+        };
+        match input {
+            LogicalPlan::Join(Join {
+                join_type,
+                on,
+                left,
+                right,
+                ..
+            }) => {
+                assert_eq!(*join_type, JoinType::Anti);
+                assert_eq!(on.len(), 4);
+                assert!(matches!(left.as_ref(), LogicalPlan::Window(_)));
+                assert!(matches!(right.as_ref(), LogicalPlan::Window(_)));
+            }
+            other => panic!("expected an Anti Join, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_set_operation_column_count() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = LogicalPlanBuilder::from(test_table_scan_with_name("t2")?)
+            .project(vec![col("a")])?
+            .build()?;
+
+        let err = LogicalPlanBuilder::from(t1)
+            .except_distinct(&t2)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: EXCEPT inputs must have the same number of \
+            columns, got 3 and 1"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_maybe_limit() -> Result<()> {
+        let builder = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?);
+
+        let plan = builder.maybe_limit(None)?.build()?;
+        assert!(matches!(plan, LogicalPlan::TableScan(_)));
+
+        let plan = builder.maybe_limit(Some(5))?.build()?;
+        let expected = "Limit: 5\
+        \n  TableScan: t1 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
 }
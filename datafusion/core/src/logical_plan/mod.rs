@@ -28,7 +28,8 @@ pub mod plan;
 mod registry;
 pub mod window_frames;
 pub use builder::{
-    build_join_schema, union_with_alias, LogicalPlanBuilder, UNNAMED_TABLE,
+    assert_unique_udf, build_join_schema, union_coerced, union_with_alias,
+    LogicalPlanBuilder, UNNAMED_TABLE,
 };
 pub use datafusion_common::{DFField, DFSchema, DFSchemaRef, ToDFSchema};
 pub use datafusion_expr::{
@@ -57,12 +58,12 @@ pub use expr_rewriter::{
     ExprRewriter, RewriteRecursion,
 };
 pub use expr_simplier::{ExprSimplifiable, SimplifyInfo};
-pub use plan::{provider_as_source, source_as_provider};
 pub use plan::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
-    CreateView, CrossJoin, DropTable, EmptyRelation, FileType, JoinConstraint, JoinType,
-    Limit, LogicalPlan, Partitioning, PlanType, PlanVisitor, Repartition,
-    StringifiedPlan, Subquery, TableScan, ToStringifiedPlan, Union,
-    UserDefinedLogicalNode, Values,
+    collect_columns, first_table_scan, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable, CreateMemoryTable, CreateView, CrossJoin, DropTable,
+    EmptyRelation, EstimatedRows, FileType, JoinConstraint, JoinHint, JoinType, Limit,
+    LogicalPlan, Partitioning, PlanType, PlanVisitor, Repartition, StringifiedPlan,
+    Subquery, TableScan, ToStringifiedPlan, Union, UserDefinedLogicalNode, Values,
 };
+pub use plan::{provider_as_source, source_as_provider};
 pub use registry::FunctionRegistry;
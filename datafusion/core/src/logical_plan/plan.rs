@@ -23,13 +23,15 @@ use crate::datasource::TableProvider;
 use crate::error::DataFusionError;
 pub use crate::logical_expr::{
     logical_plan::{
+        collect_columns,
         display::{GraphvizVisitor, IndentVisitor},
-        Aggregate, Analyze, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
-        CreateMemoryTable, CreateView, CrossJoin, DropTable, EmptyRelation, Explain,
-        Extension, FileType, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
-        Partitioning, PlanType, PlanVisitor, Projection, Repartition, Sort,
-        StringifiedPlan, Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union,
-        UserDefinedLogicalNode, Values, Window,
+        first_table_scan, Aggregate, Analyze, CreateCatalog, CreateCatalogSchema,
+        CreateExternalTable, CreateMemoryTable, CreateView, CrossJoin, DropTable,
+        EmptyRelation, EstimatedRows, Explain, Extension, FileType, Filter, Join,
+        JoinConstraint, JoinHint, JoinType, Limit, LogicalPlan, Partitioning, PlanType,
+        PlanVisitor, Projection, Repartition, Sort, StringifiedPlan, Subquery,
+        SubqueryAlias, TableScan, ToStringifiedPlan, Union, UserDefinedLogicalNode,
+        Values, Window,
     },
     TableProviderFilterPushDown, TableSource,
 };
@@ -100,7 +102,7 @@ pub fn source_as_provider(
 
 #[cfg(test)]
 mod tests {
-    use super::super::{col, lit, LogicalPlanBuilder};
+    use super::super::{col, exists, lit, LogicalPlanBuilder};
     use super::*;
     use arrow::datatypes::{DataType, Field, Schema};
 
@@ -433,4 +435,64 @@ mod tests {
             .build()
             .unwrap()
     }
+
+    #[test]
+    fn test_collect_columns() -> Result<(), DataFusionError> {
+        let scan = |name: &str| {
+            LogicalPlanBuilder::scan_empty(Some(name), &employee_schema(), None)
+        };
+        let subquery = scan("t3")?
+            .filter(col("t3.last_name").eq(col("t1.last_name")))?
+            .project(vec![col("t3.id")])?
+            .build()?;
+        let plan = scan("t1")?
+            .join(
+                &scan("t2")?.build()?,
+                JoinType::Inner,
+                (vec!["id"], vec!["id"]),
+            )?
+            .filter(col("t1.state").eq(lit("CO")))?
+            .filter(exists(Arc::new(subquery)))?
+            .project(vec![col("t1.first_name")])?
+            .build()?;
+
+        let mut columns = collect_columns(&plan)?
+            .into_iter()
+            .map(|c| c.flat_name())
+            .collect::<Vec<_>>();
+        columns.sort();
+        assert_eq!(
+            columns,
+            vec![
+                "t1.first_name",
+                "t1.id",
+                "t1.last_name",
+                "t1.state",
+                "t2.id",
+                "t3.id",
+                "t3.last_name"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_table_scan() -> Result<(), DataFusionError> {
+        let plan = LogicalPlanBuilder::scan_empty(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3]),
+        )?
+        .project(vec![col("id"), col("state")])?
+        .filter(col("state").eq(lit("CO")))?
+        .build()?;
+
+        let scan = first_table_scan(&plan).expect("plan has a table scan");
+        assert_eq!(scan.table_name, "employee_csv");
+        assert_eq!(scan.projection, Some(vec![0, 3]));
+
+        let plan = LogicalPlanBuilder::empty(false).build()?;
+        assert!(first_table_scan(&plan).is_none());
+        Ok(())
+    }
 }
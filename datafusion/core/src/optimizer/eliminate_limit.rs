@@ -15,16 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Optimizer rule to replace `LIMIT 0` on a plan with an empty relation.
+//! Optimizer rule to replace `LIMIT 0`, or a limit skipping all the rows of
+//! its input, on a plan with an empty relation.
 //! This saves time in planning and executing the query.
 use crate::error::Result;
+use crate::logical_plan::plan::Projection;
 use crate::logical_plan::{EmptyRelation, Limit, LogicalPlan};
 use crate::optimizer::optimizer::OptimizerRule;
+use std::sync::Arc;
 
 use super::utils;
 use crate::execution::context::ExecutionProps;
 
-/// Optimization rule that replaces LIMIT 0 with an [LogicalPlan::EmptyRelation]
+/// Optimization rule that replaces LIMIT 0, or a limit whose skip is past the
+/// last row of its input, with an [LogicalPlan::EmptyRelation]
 #[derive(Default)]
 pub struct EliminateLimit;
 
@@ -42,12 +46,29 @@ impl OptimizerRule for EliminateLimit {
         execution_props: &ExecutionProps,
     ) -> Result<LogicalPlan> {
         match plan {
-            LogicalPlan::Limit(Limit { n, input }) if *n == 0 => {
+            LogicalPlan::Limit(Limit { n, input, .. }) if *n == 0 => {
                 Ok(LogicalPlan::EmptyRelation(EmptyRelation {
                     produce_one_row: false,
                     schema: input.schema().clone(),
                 }))
             }
+            LogicalPlan::Limit(Limit { skip, n, input }) => {
+                let input = self.optimize(input, execution_props)?;
+                match (skip, max_rows(&input)) {
+                    // skipping past the last row
+                    (Some(skip), Some(max_rows)) if *skip >= max_rows => {
+                        Ok(LogicalPlan::EmptyRelation(EmptyRelation {
+                            produce_one_row: false,
+                            schema: input.schema().clone(),
+                        }))
+                    }
+                    _ => Ok(LogicalPlan::Limit(Limit {
+                        skip: *skip,
+                        n: *n,
+                        input: Arc::new(input),
+                    })),
+                }
+            }
             // Rest: recurse and find possible LIMIT 0 nodes
             _ => {
                 let expr = plan.expressions();
@@ -69,11 +90,23 @@ impl OptimizerRule for EliminateLimit {
     }
 }
 
+/// The maximum number of rows `plan` produces, if known without executing it
+fn max_rows(plan: &LogicalPlan) -> Option<usize> {
+    match plan {
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row, ..
+        }) => Some(*produce_one_row as usize),
+        LogicalPlan::Limit(Limit { n, .. }) => Some(*n),
+        LogicalPlan::Projection(Projection { input, .. }) => max_rows(input),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::logical_plan::LogicalPlanBuilder;
-    use crate::logical_plan::{col, sum};
+    use crate::logical_plan::{col, lit, sum};
     use crate::test::*;
 
     fn assert_optimized_plan_eq(plan: &LogicalPlan, expected: &str) {
@@ -127,4 +160,59 @@ mod tests {
             \n    TableScan: test projection=None";
         assert_optimized_plan_eq(&plan, expected);
     }
+
+    #[test]
+    fn limit_0_drops_input() {
+        let table_scan = test_table_scan().unwrap();
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(col("a").gt(lit(1u32)))
+            .unwrap()
+            .limit(0)
+            .unwrap()
+            .project(vec![col("a")])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // Filter and scan are removed
+        let expected = "Projection: #test.a\
+            \n  EmptyRelation";
+        assert_optimized_plan_eq(&plan, expected);
+    }
+
+    #[test]
+    fn limit_skip_past_input() {
+        let table_scan = test_table_scan().unwrap();
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .limit(3)
+            .unwrap()
+            .project(vec![col("a")])
+            .unwrap()
+            .limit_with_offset(3, 10)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The outer limit skips all the rows of the inner one
+        let expected = "EmptyRelation";
+        assert_optimized_plan_eq(&plan, expected);
+    }
+
+    #[test]
+    fn limit_skip_within_input() {
+        let table_scan = test_table_scan().unwrap();
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .limit(3)
+            .unwrap()
+            .limit_with_offset(2, 10)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The last row of the inner limit is kept
+        let expected = "Limit: skip=2, fetch=10\
+            \n  Limit: 3\
+            \n    TableScan: test projection=None";
+        assert_optimized_plan_eq(&plan, expected);
+    }
 }
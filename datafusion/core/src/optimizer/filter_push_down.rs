@@ -17,7 +17,7 @@
 use crate::error::Result;
 use crate::execution::context::ExecutionProps;
 use crate::logical_expr::TableProviderFilterPushDown;
-use crate::logical_plan::plan::{Aggregate, Filter, Join, Projection, Union};
+use crate::logical_plan::plan::{Aggregate, Filter, Join, Projection, Sort, Union};
 use crate::logical_plan::{
     col, replace_col, Column, CrossJoin, JoinType, Limit, LogicalPlan, TableScan,
 };
@@ -355,10 +355,24 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
 
             issue_filters(state, used_columns, plan)
         }
-        LogicalPlan::Sort { .. } => {
+        LogicalPlan::EstimatedRows(_) => {
+            // the estimate is only an annotation and is filter-commutable
+            push_down(&state, plan)
+        }
+        LogicalPlan::Sort(Sort { fetch: None, .. }) => {
             // sort is filter-commutable
             push_down(&state, plan)
         }
+        LogicalPlan::Sort(Sort { input, .. }) => {
+            // a sort with a fetch limits its output and is _not_ filter-commutable
+            let used_columns = input
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| f.qualified_column())
+                .collect::<HashSet<_>>();
+            issue_filters(state, used_columns, plan)
+        }
         LogicalPlan::Union(Union {
             inputs: _,
             schema,
@@ -468,6 +482,7 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
             filters,
             projection,
             table_name,
+            partition_filters,
             limit,
         }) => {
             let mut used_columns = HashSet::new();
@@ -504,6 +519,7 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
                     projected_schema: projected_schema.clone(),
                     table_name: table_name.clone(),
                     filters: new_filters,
+                    partition_filters: partition_filters.clone(),
                     limit: *limit,
                 }),
             )
@@ -602,6 +618,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn filter_through_estimated_rows() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .project(vec![col("a"), col("b")])?
+            .with_estimated_rows(42)
+            .filter(col("a").eq(lit(1i64)))?
+            .build()?;
+        // filter is pushed below the estimate
+        let expected = "\
+            EstimatedRows: 42\
+            \n  Projection: #test.a, #test.b\
+            \n    Filter: #test.a = Int64(1)\
+            \n      TableScan: test projection=None";
+        assert_optimized_plan_eq(&plan, expected);
+        Ok(())
+    }
+
     #[test]
     fn filter_after_limit() -> Result<()> {
         let table_scan = test_table_scan()?;
@@ -1393,6 +1427,7 @@ mod tests {
             )?),
             projection: None,
             source: provider_as_source(Arc::new(test_provider)),
+            partition_filters: vec![],
             limit: None,
         });
 
@@ -1466,6 +1501,7 @@ mod tests {
             )?),
             projection: Some(vec![0]),
             source: provider_as_source(Arc::new(test_provider)),
+            partition_filters: vec![],
             limit: None,
         });
 
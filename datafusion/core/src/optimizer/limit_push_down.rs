@@ -21,7 +21,7 @@ use super::utils;
 use crate::error::Result;
 use crate::execution::context::ExecutionProps;
 use crate::logical_plan::plan::Projection;
-use crate::logical_plan::{Limit, TableScan};
+use crate::logical_plan::{EstimatedRows, Limit, TableScan};
 use crate::logical_plan::{LogicalPlan, Union};
 use crate::optimizer::optimizer::OptimizerRule;
 use std::sync::Arc;
@@ -45,14 +45,16 @@ fn limit_push_down(
     _execution_props: &ExecutionProps,
 ) -> Result<LogicalPlan> {
     match (plan, upper_limit) {
-        (LogicalPlan::Limit(Limit { n, input }), upper_limit) => {
+        (LogicalPlan::Limit(Limit { skip, n, input }), upper_limit) => {
             let smallest = upper_limit.map(|x| std::cmp::min(x, *n)).unwrap_or(*n);
             Ok(LogicalPlan::Limit(Limit {
+                skip: *skip,
                 n: smallest,
-                // push down limit to plan (minimum of upper limit and current limit)
+                // push down limit to plan (minimum of upper limit and current limit),
+                // the input also has to produce the skipped rows
                 input: Arc::new(limit_push_down(
                     _optimizer,
-                    Some(smallest),
+                    Some(smallest.saturating_add(skip.unwrap_or(0))),
                     input.as_ref(),
                     _execution_props,
                 )?),
@@ -64,6 +66,7 @@ fn limit_push_down(
                 source,
                 projection,
                 filters,
+                partition_filters,
                 limit,
                 projected_schema,
             }),
@@ -73,6 +76,7 @@ fn limit_push_down(
             source: source.clone(),
             projection: projection.clone(),
             filters: filters.clone(),
+            partition_filters: partition_filters.clone(),
             limit: limit
                 .map(|x| std::cmp::min(x, upper_limit))
                 .or(Some(upper_limit)),
@@ -100,6 +104,18 @@ fn limit_push_down(
                 alias: alias.clone(),
             }))
         }
+        (LogicalPlan::EstimatedRows(EstimatedRows { input, rows }), upper_limit) => {
+            // Push down limit directly (the estimate is only an annotation)
+            Ok(LogicalPlan::EstimatedRows(EstimatedRows {
+                input: Arc::new(limit_push_down(
+                    _optimizer,
+                    upper_limit,
+                    input.as_ref(),
+                    _execution_props,
+                )?),
+                rows: *rows,
+            }))
+        }
         (
             LogicalPlan::Union(Union {
                 inputs,
@@ -113,6 +129,7 @@ fn limit_push_down(
                 .iter()
                 .map(|x| {
                     Ok(LogicalPlan::Limit(Limit {
+                        skip: None,
                         n: upper_limit,
                         input: Arc::new(limit_push_down(
                             _optimizer,
@@ -197,6 +214,46 @@ mod test {
         Ok(())
     }
     #[test]
+    fn limit_pushdown_with_large_skip() -> Result<()> {
+        let table_scan = test_table_scan()?;
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .limit_with_offset(usize::MAX, 10)?
+            .build()?;
+
+        // The limit pushed to the table provider saturates instead of overflowing
+        let expected = format!(
+            "Limit: skip={}, fetch=10\
+            \n  TableScan: test projection=None, limit={}",
+            usize::MAX,
+            usize::MAX
+        );
+
+        assert_optimized_plan_eq(&plan, &expected);
+
+        Ok(())
+    }
+    #[test]
+    fn limit_pushdown_through_estimated_rows() -> Result<()> {
+        let table_scan = test_table_scan()?;
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .project(vec![col("a")])?
+            .with_estimated_rows(42)
+            .limit(1000)?
+            .build()?;
+
+        // The estimate doesn't stop the limit from reaching the table provider
+        let expected = "Limit: 1000\
+        \n  EstimatedRows: 42\
+        \n    Projection: #test.a\
+        \n      TableScan: test projection=None, limit=1000";
+
+        assert_optimized_plan_eq(&plan, expected);
+
+        Ok(())
+    }
+    #[test]
     fn limit_push_down_take_smaller_limit() -> Result<()> {
         let table_scan = test_table_scan()?;
 
@@ -209,6 +209,7 @@ fn optimize_plan(
             join_type,
             join_constraint,
             null_equals_null,
+            hint,
             ..
         }) => {
             for (l, r) in on {
@@ -246,6 +247,7 @@ fn optimize_plan(
                 on: on.clone(),
                 schema: DFSchemaRef::new(schema),
                 null_equals_null: *null_equals_null,
+                hint: *hint,
             }))
         }
         LogicalPlan::Window(Window {
@@ -345,13 +347,18 @@ fn optimize_plan(
             table_name,
             source,
             filters,
+            partition_filters,
             limit,
             ..
         }) => {
+            // partition filters the provider does not apply exactly are
+            // evaluated on the scan output, so keep their columns
+            let mut required_columns = required_columns.clone();
+            exprlist_to_columns(partition_filters, &mut required_columns)?;
             let (projection, projected_schema) = get_projected_schema(
                 Some(table_name),
                 &source.schema(),
-                required_columns,
+                &required_columns,
                 has_projection,
             )?;
             // return the table scan with projection
@@ -361,6 +368,7 @@ fn optimize_plan(
                 projection: Some(projection),
                 projected_schema,
                 filters: filters.clone(),
+                partition_filters: partition_filters.clone(),
                 limit: *limit,
             }))
         }
@@ -469,6 +477,7 @@ fn optimize_plan(
         // all other nodes: Add any additional columns used by
         // expressions in this node to the list of required columns
         LogicalPlan::Limit(_)
+        | LogicalPlan::EstimatedRows(_)
         | LogicalPlan::Filter { .. }
         | LogicalPlan::Repartition(_)
         | LogicalPlan::EmptyRelation(_)
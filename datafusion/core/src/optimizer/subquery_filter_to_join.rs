@@ -32,7 +32,7 @@ use crate::error::{DataFusionError, Result};
 use crate::execution::context::ExecutionProps;
 use crate::logical_plan::plan::{Filter, Join};
 use crate::logical_plan::{
-    build_join_schema, Expr, JoinConstraint, JoinType, LogicalPlan,
+    build_join_schema, Expr, JoinConstraint, JoinHint, JoinType, LogicalPlan,
 };
 use crate::optimizer::optimizer::OptimizerRule;
 use crate::optimizer::utils;
@@ -136,6 +136,7 @@ impl OptimizerRule for SubqueryFilterToJoin {
                                 join_constraint: JoinConstraint::On,
                                 schema: Arc::new(schema),
                                 null_equals_null: false,
+                                hint: JoinHint::None,
                             }))
                         }
                         _ => Err(DataFusionError::Plan(
@@ -20,25 +20,32 @@
 use super::optimizer::OptimizerRule;
 use crate::execution::context::ExecutionProps;
 use datafusion_expr::logical_plan::{
-    Aggregate, Analyze, Extension, Filter, Join, Projection, Sort, Subquery,
-    SubqueryAlias, Window,
+    Aggregate, Analyze, EstimatedRows, Extension, Filter, Join, Projection, Sort,
+    Subquery, SubqueryAlias, TableScan, Window,
 };
 
 use crate::error::{DataFusionError, Result};
 use crate::logical_plan::{
-    and, build_join_schema, CreateMemoryTable, CreateView, DFSchemaRef, Expr, Limit,
-    LogicalPlan, LogicalPlanBuilder, Operator, Partitioning, Repartition, Union, Values,
+    and, build_join_schema, Column, CreateMemoryTable, CreateView, CrossJoin,
+    DFSchemaRef, Expr, JoinConstraint, JoinHint, JoinType, Limit, LogicalPlan,
+    LogicalPlanBuilder, Operator, Partitioning, Repartition, Union, Values,
 };
 use crate::prelude::lit;
 use crate::scalar::ScalarValue;
 use datafusion_common::DFSchema;
 use datafusion_expr::expr::GroupingSet;
+use datafusion_expr::expr_rewriter::{ExprRewritable, ExprRewriter};
+use datafusion_expr::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
+use datafusion_expr::utils::expr_to_columns;
+use datafusion_expr::Volatility;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 const CASE_EXPR_MARKER: &str = "__DATAFUSION_CASE_EXPR__";
 const CASE_ELSE_MARKER: &str = "__DATAFUSION_CASE_ELSE__";
 const WINDOW_PARTITION_MARKER: &str = "__DATAFUSION_WINDOW_PARTITION__";
 const WINDOW_SORT_MARKER: &str = "__DATAFUSION_WINDOW_SORT__";
+const GROUPING_SET_MARKER: &str = "__DATAFUSION_GROUPING_SET__";
 
 /// Convenience rule for writing optimizers: recursively invoke
 /// optimize on plan's children and then return a node of the same
@@ -136,15 +143,17 @@ pub fn from_plan(
             input: Arc::new(inputs[0].clone()),
             schema: schema.clone(),
         })),
-        LogicalPlan::Sort(Sort { .. }) => Ok(LogicalPlan::Sort(Sort {
+        LogicalPlan::Sort(Sort { fetch, .. }) => Ok(LogicalPlan::Sort(Sort {
             expr: expr.to_vec(),
             input: Arc::new(inputs[0].clone()),
+            fetch: *fetch,
         })),
         LogicalPlan::Join(Join {
             join_type,
             join_constraint,
             on,
             null_equals_null,
+            hint,
             ..
         }) => {
             let schema =
@@ -157,6 +166,7 @@ pub fn from_plan(
                 on: on.clone(),
                 schema: DFSchemaRef::new(schema),
                 null_equals_null: *null_equals_null,
+                hint: *hint,
             }))
         }
         LogicalPlan::CrossJoin(_) => {
@@ -165,6 +175,16 @@ pub fn from_plan(
             LogicalPlanBuilder::from(left).cross_join(right)?.build()
         }
         LogicalPlan::Subquery(_) => {
+            // A subquery has no expressions of its own: correlated references
+            // to the outer query live in the nodes of the subquery plan,
+            // which is rebuilt from `inputs`. Refuse expressions rather than
+            // silently dropping them.
+            if !expr.is_empty() {
+                return Err(DataFusionError::Internal(format!(
+                    "Subquery expects no expressions, got {:?}",
+                    expr
+                )));
+            }
             let subquery = LogicalPlanBuilder::from(inputs[0].clone()).build()?;
             Ok(LogicalPlan::Subquery(Subquery {
                 subquery: Arc::new(subquery),
@@ -180,10 +200,17 @@ pub fn from_plan(
                 schema,
             }))
         }
-        LogicalPlan::Limit(Limit { n, .. }) => Ok(LogicalPlan::Limit(Limit {
+        LogicalPlan::Limit(Limit { skip, n, .. }) => Ok(LogicalPlan::Limit(Limit {
+            skip: *skip,
             n: *n,
             input: Arc::new(inputs[0].clone()),
         })),
+        LogicalPlan::EstimatedRows(EstimatedRows { rows, .. }) => {
+            Ok(LogicalPlan::EstimatedRows(EstimatedRows {
+                rows: *rows,
+                input: Arc::new(inputs[0].clone()),
+            }))
+        }
         LogicalPlan::CreateMemoryTable(CreateMemoryTable {
             name,
             if_not_exists,
@@ -233,8 +260,14 @@ pub fn from_plan(
             );
             Ok(plan.clone())
         }
+        LogicalPlan::TableScan(scan) => {
+            assert!(inputs.is_empty(), "{:?}  should have no inputs", plan);
+            Ok(LogicalPlan::TableScan(TableScan {
+                partition_filters: expr.to_vec(),
+                ..scan.clone()
+            }))
+        }
         LogicalPlan::EmptyRelation(_)
-        | LogicalPlan::TableScan { .. }
         | LogicalPlan::CreateExternalTable(_)
         | LogicalPlan::DropTable(_)
         | LogicalPlan::CreateCatalogSchema(_)
@@ -270,9 +303,16 @@ pub fn expr_sub_expressions(expr: &Expr) -> Result<Vec<Expr>> {
         Expr::GroupingSet(grouping_set) => match grouping_set {
             GroupingSet::Rollup(exprs) => Ok(exprs.clone()),
             GroupingSet::Cube(exprs) => Ok(exprs.clone()),
-            GroupingSet::GroupingSets(_) => Err(DataFusionError::Plan(
-                "GroupingSets are not supported yet".to_string(),
-            )),
+            GroupingSet::GroupingSets(groups) => {
+                // every group is preceded by a marker, so that empty groups
+                // survive the round trip through `rewrite_expression`
+                let mut expr_list: Vec<Expr> = vec![];
+                for group in groups {
+                    expr_list.push(lit(GROUPING_SET_MARKER));
+                    expr_list.extend(group.clone());
+                }
+                Ok(expr_list)
+            }
         },
         Expr::WindowFunction {
             args,
@@ -358,7 +398,10 @@ pub fn rewrite_expression(expr: &Expr, expressions: &[Expr]) -> Result<Expr> {
             args: expressions.to_vec(),
         }),
         Expr::WindowFunction {
-            fun, window_frame, ..
+            fun,
+            window_frame,
+            distinct,
+            ..
         } => {
             let partition_index = expressions
                 .iter()
@@ -397,6 +440,7 @@ pub fn rewrite_expression(expr: &Expr, expressions: &[Expr]) -> Result<Expr> {
                     partition_by: expressions[partition_index + 1..sort_index].to_vec(),
                     order_by: expressions[sort_index + 1..].to_vec(),
                     window_frame: *window_frame,
+                    distinct: *distinct,
                 })
             }
         }
@@ -416,9 +460,28 @@ pub fn rewrite_expression(expr: &Expr, expressions: &[Expr]) -> Result<Expr> {
             GroupingSet::Cube(_exprs) => {
                 Ok(Expr::GroupingSet(GroupingSet::Rollup(expressions.to_vec())))
             }
-            GroupingSet::GroupingSets(_) => Err(DataFusionError::Plan(
-                "GroupingSets are not supported yet".to_string(),
-            )),
+            GroupingSet::GroupingSets(_) => {
+                let mut groups: Vec<Vec<Expr>> = vec![];
+                for expr in expressions {
+                    match expr {
+                        Expr::Literal(ScalarValue::Utf8(Some(str)))
+                            if str == GROUPING_SET_MARKER =>
+                        {
+                            groups.push(vec![]);
+                        }
+                        _ => groups
+                            .last_mut()
+                            .ok_or_else(|| {
+                                DataFusionError::Internal(
+                                    "Ill-formed grouping sets expressions: missing marker"
+                                        .to_owned(),
+                                )
+                            })?
+                            .push(expr.clone()),
+                    }
+                }
+                Ok(Expr::GroupingSet(GroupingSet::GroupingSets(groups)))
+            }
         },
         Expr::Case { .. } => {
             let mut base_expr: Option<Box<Expr>> = None;
@@ -537,15 +600,17 @@ pub fn split_conjunction<'a>(predicate: &'a Expr, predicates: &mut Vec<&'a Expr>
 }
 
 /// returns a new [LogicalPlan] that wraps `plan` in a [LogicalPlan::Filter] with
-/// its predicate be all `predicates` ANDed.
+/// its predicate be all `predicates` ANDed. `plan` is returned unchanged if
+/// there are no `predicates`.
 pub fn add_filter(plan: LogicalPlan, predicates: &[&Expr]) -> LogicalPlan {
+    let (first, rest) = match predicates.split_first() {
+        Some(split) => split,
+        None => return plan,
+    };
     // reduce filters to a single filter with an AND
-    let predicate = predicates
-        .iter()
-        .skip(1)
-        .fold(predicates[0].clone(), |acc, predicate| {
-            and(acc, (*predicate).to_owned())
-        });
+    let predicate = rest.iter().fold((*first).clone(), |acc, predicate| {
+        and(acc, (*predicate).to_owned())
+    });
 
     LogicalPlan::Filter(Filter {
         predicate,
@@ -553,14 +618,402 @@ pub fn add_filter(plan: LogicalPlan, predicates: &[&Expr]) -> LogicalPlan {
     })
 }
 
+/// Converts a [LogicalPlan::Filter] directly above a [LogicalPlan::CrossJoin]
+/// into an inner [LogicalPlan::Join] keyed on the equalities between columns
+/// of the two sides. Remaining conjuncts are kept in a filter above the join.
+///
+/// Returns `None` if the filter input is not a cross join or if the
+/// predicate contains no equi-join condition.
+pub fn cross_join_to_inner_join(filter: &Filter) -> Result<Option<LogicalPlan>> {
+    let CrossJoin {
+        left,
+        right,
+        schema,
+    } = match filter.input.as_ref() {
+        LogicalPlan::CrossJoin(cross_join) => cross_join,
+        _ => return Ok(None),
+    };
+
+    let is_from = |plan: &LogicalPlan, column: &Column| {
+        plan.schema().field_from_column(column).is_ok()
+    };
+
+    let mut predicates = vec![];
+    split_conjunction(&filter.predicate, &mut predicates);
+
+    let mut on = vec![];
+    let mut residual = vec![];
+    for predicate in predicates {
+        match predicate {
+            Expr::BinaryExpr {
+                left: l,
+                op: Operator::Eq,
+                right: r,
+            } => match (l.as_ref(), r.as_ref()) {
+                (Expr::Column(l), Expr::Column(r))
+                    if is_from(left, l) && is_from(right, r) =>
+                {
+                    on.push((l.clone(), r.clone()))
+                }
+                (Expr::Column(l), Expr::Column(r))
+                    if is_from(right, l) && is_from(left, r) =>
+                {
+                    on.push((r.clone(), l.clone()))
+                }
+                _ => residual.push(predicate),
+            },
+            _ => residual.push(predicate),
+        }
+    }
+
+    if on.is_empty() {
+        return Ok(None);
+    }
+
+    let join = LogicalPlan::Join(Join {
+        left: left.clone(),
+        right: right.clone(),
+        on,
+        join_type: JoinType::Inner,
+        join_constraint: JoinConstraint::On,
+        schema: schema.clone(),
+        null_equals_null: false,
+        hint: JoinHint::None,
+    });
+
+    if residual.is_empty() {
+        Ok(Some(join))
+    } else {
+        Ok(Some(add_filter(join, &residual)))
+    }
+}
+
+/// Moves a [LogicalPlan::Filter] directly above a [LogicalPlan::Projection]
+/// below it, rewriting the predicate in terms of the projection's input.
+///
+/// Returns `None` if a column referenced by the predicate is computed by a
+/// volatile, aggregate or window expression, as evaluating it below the
+/// projection could change the result. Returns an error if the input of
+/// `filter` is not `projection`.
+pub fn push_filter_through_projection(
+    filter: &Filter,
+    projection: &Projection,
+) -> Result<Option<LogicalPlan>> {
+    match filter.input.as_ref() {
+        LogicalPlan::Projection(input)
+            if input.expr == projection.expr && input.schema == projection.schema => {}
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "push_filter_through_projection expects the projection below the filter, got {:?}",
+                other
+            )))
+        }
+    }
+
+    let mut columns = HashSet::new();
+    expr_to_columns(&filter.predicate, &mut columns)?;
+
+    let mut replace_map = HashMap::new();
+    for column in columns {
+        let index = projection.schema.index_of_column(&column)?;
+        // strip alias, as they should not be part of filters
+        let expr = match &projection.expr[index] {
+            Expr::Alias(expr, _) => expr.as_ref(),
+            expr => expr,
+        };
+        if !expr
+            .accept(DeterministicVisitor {
+                deterministic: true,
+            })?
+            .deterministic
+        {
+            return Ok(None);
+        }
+        replace_map.insert(column, expr.clone());
+    }
+
+    struct ColumnReplacer {
+        replace_map: HashMap<Column, Expr>,
+    }
+
+    impl ExprRewriter for ColumnReplacer {
+        fn mutate(&mut self, expr: Expr) -> Result<Expr> {
+            match &expr {
+                Expr::Column(c) => Ok(self.replace_map.get(c).cloned().unwrap_or(expr)),
+                _ => Ok(expr),
+            }
+        }
+    }
+
+    let predicate = filter
+        .predicate
+        .clone()
+        .rewrite(&mut ColumnReplacer { replace_map })?;
+    let new_filter = LogicalPlan::Filter(Filter {
+        predicate,
+        input: projection.input.clone(),
+    });
+    Ok(Some(LogicalPlan::Projection(Projection {
+        expr: projection.expr.clone(),
+        input: Arc::new(new_filter),
+        schema: projection.schema.clone(),
+        alias: projection.alias.clone(),
+    })))
+}
+
+/// Fuses a [Limit] directly above a [LogicalPlan::Sort] into the sort by
+/// setting its `fetch`, so that it can be executed as a top-k. The limit is
+/// removed unless it skips rows, in which case the sort fetches the skipped
+/// rows as well. Returns `None` if the input of `limit` is not a sort.
+pub fn fuse_sort_limit(limit: &Limit) -> Option<LogicalPlan> {
+    match limit.input.as_ref() {
+        LogicalPlan::Sort(Sort { expr, input, fetch }) => {
+            let limit_fetch = limit.n.saturating_add(limit.skip.unwrap_or(0));
+            let sort = LogicalPlan::Sort(Sort {
+                expr: expr.clone(),
+                input: input.clone(),
+                fetch: Some(fetch.map_or(limit_fetch, |f| f.min(limit_fetch))),
+            });
+            match limit.skip {
+                None => Some(sort),
+                Some(skip) => Some(LogicalPlan::Limit(Limit {
+                    skip: Some(skip),
+                    n: limit.n,
+                    input: Arc::new(sort),
+                })),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Combines the `outer` [Limit] with the [Limit] that is its input into a
+/// single limit over the input of the inner one. See [combine_limits]
+/// for how the fetch and skip are computed. Returns an error if the input of
+/// `outer` is not a limit.
+pub fn combine_limit_nodes(outer: &Limit) -> Result<Limit> {
+    match outer.input.as_ref() {
+        LogicalPlan::Limit(inner) => {
+            let (n, skip) = combine_limits(outer.n, outer.skip, inner.n, inner.skip);
+            Ok(Limit {
+                skip,
+                n,
+                input: inner.input.clone(),
+            })
+        }
+        other => Err(DataFusionError::Internal(format!(
+            "combine_limit_nodes expects a Limit below the outer limit, got {:?}",
+            other
+        ))),
+    }
+}
+
+/// Computes the `(n, skip)` of a single [Limit] equivalent to a limit of
+/// `outer_n` rows skipping `outer_skip` rows, applied on top of a limit of
+/// `inner_n` rows skipping `inner_skip` rows. The fetch is zero if the outer
+/// limit skips all the rows of the inner one.
+pub fn combine_limits(
+    outer_n: usize,
+    outer_skip: Option<usize>,
+    inner_n: usize,
+    inner_skip: Option<usize>,
+) -> (usize, Option<usize>) {
+    let outer_skip = outer_skip.unwrap_or(0);
+    let skip = inner_skip.unwrap_or(0).saturating_add(outer_skip);
+    let n = outer_n.min(inner_n.saturating_sub(outer_skip));
+    (n, if skip == 0 { None } else { Some(skip) })
+}
+
+/// Rewrites every expression of `plan` and of its inputs with `f`, which is
+/// applied to each expression returned by [LogicalPlan::expressions]. The
+/// inputs are rewritten before the node itself is rebuilt using [from_plan].
+pub fn rewrite_plan_exprs(
+    plan: &LogicalPlan,
+    f: &mut impl FnMut(Expr) -> Result<Expr>,
+) -> Result<LogicalPlan> {
+    let new_inputs = plan
+        .inputs()
+        .into_iter()
+        .map(|input| rewrite_plan_exprs(input, f))
+        .collect::<Result<Vec<_>>>()?;
+    let new_exprs = plan
+        .expressions()
+        .into_iter()
+        .map(&mut *f)
+        .collect::<Result<Vec<_>>>()?;
+
+    from_plan(plan, &new_exprs, &new_inputs)
+}
+
+/// Returns the output columns of `plan` that are known to hold a single
+/// non-null value: columns compared for equality with a literal in the
+/// predicate of a [LogicalPlan::Filter], and literal expressions of a
+/// [LogicalPlan::Projection]. Other plans have no known constant columns.
+pub fn constant_columns(plan: &LogicalPlan) -> HashMap<Column, ScalarValue> {
+    let mut constants = HashMap::new();
+    match plan {
+        LogicalPlan::Filter(Filter { predicate, .. }) => {
+            let mut predicates = vec![];
+            split_conjunction(predicate, &mut predicates);
+            for predicate in predicates {
+                if let Expr::BinaryExpr {
+                    left,
+                    op: Operator::Eq,
+                    right,
+                } = predicate
+                {
+                    match (left.as_ref(), right.as_ref()) {
+                        (Expr::Column(column), Expr::Literal(value))
+                        | (Expr::Literal(value), Expr::Column(column))
+                            if !value.is_null() =>
+                        {
+                            constants.insert(column.clone(), value.clone());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        LogicalPlan::Projection(Projection { expr, schema, .. }) => {
+            for (expr, field) in expr.iter().zip(schema.fields()) {
+                let value = match expr {
+                    Expr::Alias(expr, _) => expr.as_ref(),
+                    expr => expr,
+                };
+                if let Expr::Literal(value) = value {
+                    if !value.is_null() {
+                        constants.insert(field.qualified_column(), value.clone());
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    constants
+}
+
+/// Rewrites `NOT (left op right)` into `left inverse_op right` when `op` is
+/// a comparison, e.g. `NOT (a > b)` becomes `a <= b`.
+///
+/// This is safe under three-valued logic: both forms evaluate to NULL
+/// exactly when one of the operands is NULL (`NOT NULL` is `NULL`), and
+/// `IS [NOT] DISTINCT FROM` never evaluates to NULL. Returns `None` for any
+/// other expression.
+pub fn negate_comparison(expr: &Expr) -> Option<Expr> {
+    if let Expr::Not(inner) = expr {
+        if let Expr::BinaryExpr { left, op, right } = inner.as_ref() {
+            let op = match op {
+                Operator::Eq => Operator::NotEq,
+                Operator::NotEq => Operator::Eq,
+                Operator::Lt => Operator::GtEq,
+                Operator::LtEq => Operator::Gt,
+                Operator::Gt => Operator::LtEq,
+                Operator::GtEq => Operator::Lt,
+                Operator::IsDistinctFrom => Operator::IsNotDistinctFrom,
+                Operator::IsNotDistinctFrom => Operator::IsDistinctFrom,
+                _ => return None,
+            };
+            return Some(Expr::BinaryExpr {
+                left: left.clone(),
+                op,
+                right: right.clone(),
+            });
+        }
+    }
+    None
+}
+
+/// Removes the `WHEN` branches of a searched [Expr::Case] whose condition is
+/// a boolean literal: branches that are always false (or NULL) are dropped,
+/// and the first always-true branch ends the `CASE`, becoming its `ELSE`.
+/// A `CASE` left without branches collapses to its `ELSE`, or to a NULL
+/// literal if it has none.
+///
+/// Returns `None` if `expr` is not a searched `CASE` with a literal `WHEN`
+/// condition, and an error if such a literal is not a boolean.
+pub fn simplify_case(expr: &Expr) -> Result<Option<Expr>> {
+    let (when_then_expr, else_expr) = match expr {
+        Expr::Case {
+            expr: None,
+            when_then_expr,
+            else_expr,
+        } => (when_then_expr, else_expr),
+        _ => return Ok(None),
+    };
+    if !when_then_expr
+        .iter()
+        .any(|(when, _)| matches!(when.as_ref(), Expr::Literal(_)))
+    {
+        return Ok(None);
+    }
+
+    let mut remaining = vec![];
+    let mut else_expr = else_expr.clone();
+    for (when, then) in when_then_expr {
+        match when.as_ref() {
+            Expr::Literal(ScalarValue::Boolean(Some(true))) => {
+                else_expr = Some(then.clone());
+                break;
+            }
+            Expr::Literal(ScalarValue::Boolean(_) | ScalarValue::Null) => {}
+            Expr::Literal(value) => {
+                return Err(DataFusionError::Plan(format!(
+                    "CASE WHEN condition must be a boolean, got {:?}",
+                    value
+                )))
+            }
+            _ => remaining.push((when.clone(), then.clone())),
+        }
+    }
+
+    if remaining.is_empty() {
+        return Ok(Some(match else_expr {
+            Some(else_expr) => *else_expr,
+            None => Expr::Literal(ScalarValue::Null),
+        }));
+    }
+    Ok(Some(Expr::Case {
+        expr: None,
+        when_then_expr: remaining,
+        else_expr,
+    }))
+}
+
+/// Checks that an expression produces the same value for a row wherever
+/// it is evaluated in the plan
+struct DeterministicVisitor {
+    deterministic: bool,
+}
+
+impl ExpressionVisitor for DeterministicVisitor {
+    fn pre_visit(mut self, expr: &Expr) -> Result<Recursion<Self>> {
+        self.deterministic = match expr {
+            Expr::ScalarFunction { fun, .. } => fun.volatility() != Volatility::Volatile,
+            Expr::ScalarUDF { fun, .. } => {
+                fun.signature.volatility != Volatility::Volatile
+            }
+            Expr::AggregateFunction { .. }
+            | Expr::AggregateUDF { .. }
+            | Expr::WindowFunction { .. } => false,
+            _ => true,
+        };
+        if self.deterministic {
+            Ok(Recursion::Continue(self))
+        } else {
+            Ok(Recursion::Stop(self))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::logical_plan::col;
+    use crate::logical_plan::{col, lit, replace_col};
+    use crate::test::test_table_scan;
+    use crate::test::test_table_scan_with_name;
     use arrow::datatypes::DataType;
     use datafusion_common::Column;
-    use datafusion_expr::utils::expr_to_columns;
-    use std::collections::HashSet;
+    use datafusion_expr::random;
 
     #[test]
     fn test_collect_expr() -> Result<()> {
@@ -583,4 +1036,574 @@ mod tests {
         assert!(accum.contains(&Column::from_name("a")));
         Ok(())
     }
+
+    fn cross_join_filter(predicate: Expr) -> Result<Filter> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+        let plan = LogicalPlanBuilder::from(t1)
+            .cross_join(&t2)?
+            .filter(predicate)?
+            .build()?;
+        match plan {
+            LogicalPlan::Filter(filter) => Ok(filter),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn cross_join_to_inner_join_with_residual() -> Result<()> {
+        let filter = cross_join_filter(
+            col("t2.a")
+                .eq(col("t1.a"))
+                .and(col("t1.b").gt(col("t2.b")))
+                .and(col("t1.c").eq(col("t2.c"))),
+        )?;
+
+        let plan = cross_join_to_inner_join(&filter)?.unwrap();
+        let expected = "Filter: #t1.b > #t2.b\
+        \n  Inner Join: #t1.a = #t2.a, #t1.c = #t2.c\
+        \n    TableScan: t1 projection=None\
+        \n    TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        assert_eq!(plan.schema(), filter.input.schema());
+        Ok(())
+    }
+
+    #[test]
+    fn cross_join_to_inner_join_not_convertible() -> Result<()> {
+        // no equality between the two sides
+        let filter = cross_join_filter(col("t1.a").gt(col("t2.a")))?;
+        assert!(cross_join_to_inner_join(&filter)?.is_none());
+
+        // equality within a single side
+        let filter = cross_join_filter(col("t1.a").eq(col("t1.b")))?;
+        assert!(cross_join_to_inner_join(&filter)?.is_none());
+
+        // input is not a cross join
+        let filter = Filter {
+            predicate: col("a").eq(col("b")),
+            input: Arc::new(test_table_scan_with_name("t1")?),
+        };
+        assert!(cross_join_to_inner_join(&filter)?.is_none());
+        Ok(())
+    }
+
+    fn filter_over_projection(
+        projection_exprs: Vec<Expr>,
+        predicate: Expr,
+    ) -> Result<(Filter, Projection)> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(projection_exprs)?
+            .filter(predicate)?
+            .build()?;
+        match plan {
+            LogicalPlan::Filter(filter) => match filter.input.as_ref() {
+                LogicalPlan::Projection(projection) => {
+                    let projection = projection.clone();
+                    Ok((filter, projection))
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn push_filter_through_projection_rewrites_predicate() -> Result<()> {
+        let (filter, projection) = filter_over_projection(
+            vec![col("a").alias("x"), (col("b") + lit(1u32)).alias("y")],
+            col("y").gt(lit(5u32)).and(col("x").eq(lit(1u32))),
+        )?;
+
+        let plan = push_filter_through_projection(&filter, &projection)?.unwrap();
+        let expected = "Projection: #test.a AS x, #test.b + UInt32(1) AS y\
+        \n  Filter: #test.b + UInt32(1) > UInt32(5) AND #test.a = UInt32(1)\
+        \n    TableScan: test projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        Ok(())
+    }
+
+    #[test]
+    fn push_filter_through_projection_volatile() -> Result<()> {
+        let (filter, projection) = filter_over_projection(
+            vec![col("a"), random().alias("r")],
+            col("r").gt(lit(0.5)),
+        )?;
+        assert!(push_filter_through_projection(&filter, &projection)?.is_none());
+
+        // filters only referencing deterministic columns can still be pushed
+        let (filter, projection) = filter_over_projection(
+            vec![col("a"), random().alias("r")],
+            col("a").gt(lit(1u32)),
+        )?;
+        assert!(push_filter_through_projection(&filter, &projection)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn push_filter_through_other_projection() -> Result<()> {
+        let (filter, _) =
+            filter_over_projection(vec![col("a"), col("b")], col("a").gt(lit(1u32)))?;
+        let (_, projection) =
+            filter_over_projection(vec![col("b"), col("a")], col("a").gt(lit(1u32)))?;
+
+        let err = push_filter_through_projection(&filter, &projection).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expects the projection below the filter"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn constant_columns_from_filter_and_projection() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(
+                col("a")
+                    .eq(lit(5u32))
+                    .and(lit(7u32).eq(col("b")))
+                    .and(col("c").gt(lit(1u32))),
+            )?
+            .build()?;
+        let expected = HashMap::from([
+            (
+                Column::from_qualified_name("test.a"),
+                ScalarValue::UInt32(Some(5)),
+            ),
+            (
+                Column::from_qualified_name("test.b"),
+                ScalarValue::UInt32(Some(7)),
+            ),
+        ]);
+        assert_eq!(constant_columns(&plan), expected);
+
+        // a disjunction does not make either column constant
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").eq(lit(5u32)).or(col("b").eq(lit(7u32))))?
+            .build()?;
+        assert!(constant_columns(&plan).is_empty());
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .project(vec![col("a"), lit(1i64).alias("one"), lit("x")])?
+            .build()?;
+        let expected = HashMap::from([
+            (Column::from_name("one"), ScalarValue::Int64(Some(1))),
+            (
+                Column::from_name("Utf8(\"x\")"),
+                ScalarValue::Utf8(Some("x".to_string())),
+            ),
+        ]);
+        assert_eq!(constant_columns(&plan), expected);
+
+        // other plans are not inspected
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .limit(1)?
+            .build()?;
+        assert!(constant_columns(&plan).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn from_plan_keeps_join_hint() -> Result<()> {
+        let left = test_table_scan_with_name("t1")?;
+        let right = test_table_scan_with_name("t2")?;
+        let plan = LogicalPlanBuilder::from(left)
+            .join_with_hint(
+                &right,
+                JoinType::Inner,
+                (vec!["a"], vec!["a"]),
+                JoinHint::BroadcastRight,
+            )?
+            .build()?;
+
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let new_plan = from_plan(&plan, &plan.expressions(), &inputs)?;
+        match &new_plan {
+            LogicalPlan::Join(Join { hint, .. }) => {
+                assert_eq!(*hint, JoinHint::BroadcastRight)
+            }
+            _ => panic!("expected Join, got {:?}", new_plan),
+        }
+        let expected = "Inner Join: #t1.a = #t2.a, hint=BroadcastRight\
+        \n  TableScan: t1 projection=None\
+        \n  TableScan: t2 projection=None";
+        assert_eq!(expected, format!("{:?}", new_plan));
+
+        // the preserved side of an outer join cannot be broadcast
+        let err = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .join_with_hint(
+                &test_table_scan_with_name("t2")?,
+                JoinType::Left,
+                (vec!["a"], vec!["a"]),
+                JoinHint::BroadcastLeft,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Cannot broadcast"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn from_plan_keeps_limit_skip() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .limit_with_offset(3, 5)?
+            .build()?;
+
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let new_plan = from_plan(&plan, &plan.expressions(), &inputs)?;
+        match &new_plan {
+            LogicalPlan::Limit(Limit { skip, n, .. }) => {
+                assert_eq!(*skip, Some(3));
+                assert_eq!(*n, 5);
+            }
+            _ => panic!("expected Limit, got {:?}", new_plan),
+        }
+        assert_eq!(format!("{:?}", plan), format!("{:?}", new_plan));
+        Ok(())
+    }
+
+    #[test]
+    fn negate_comparison_inverts_operator() {
+        let cases = vec![
+            (Operator::Eq, Operator::NotEq),
+            (Operator::NotEq, Operator::Eq),
+            (Operator::Lt, Operator::GtEq),
+            (Operator::LtEq, Operator::Gt),
+            (Operator::Gt, Operator::LtEq),
+            (Operator::GtEq, Operator::Lt),
+            (Operator::IsDistinctFrom, Operator::IsNotDistinctFrom),
+            (Operator::IsNotDistinctFrom, Operator::IsDistinctFrom),
+        ];
+        for (op, negated) in cases {
+            let comparison = Expr::BinaryExpr {
+                left: Box::new(col("a")),
+                op,
+                right: Box::new(col("b")),
+            };
+            let expected = Expr::BinaryExpr {
+                left: Box::new(col("a")),
+                op: negated,
+                right: Box::new(col("b")),
+            };
+            assert_eq!(negate_comparison(&comparison.not()), Some(expected));
+        }
+
+        // only negated comparisons are rewritten
+        assert_eq!(negate_comparison(&col("a").gt(col("b"))), None);
+        assert_eq!(negate_comparison(&(col("a") + lit(1)).not()), None);
+        assert_eq!(negate_comparison(&col("a").not()), None);
+    }
+
+    #[test]
+    fn simplify_case_with_literal_conditions() -> Result<()> {
+        let case = |when_then: Vec<(Expr, Expr)>, else_expr: Option<Expr>| Expr::Case {
+            expr: None,
+            when_then_expr: when_then
+                .into_iter()
+                .map(|(when, then)| (Box::new(when), Box::new(then)))
+                .collect(),
+            else_expr: else_expr.map(Box::new),
+        };
+
+        // the first branch is always true
+        let expr = case(vec![(lit(true), col("a")), (col("c"), col("b"))], None);
+        assert_eq!(simplify_case(&expr)?, Some(col("a")));
+
+        // an always true branch after a dynamic one becomes the ELSE
+        let expr = case(
+            vec![
+                (col("c"), col("a")),
+                (lit(false), col("b")),
+                (lit(true), col("d")),
+            ],
+            Some(col("e")),
+        );
+        assert_eq!(
+            simplify_case(&expr)?,
+            Some(case(vec![(col("c"), col("a"))], Some(col("d"))))
+        );
+
+        // always false and NULL branches are dropped
+        let expr = case(
+            vec![
+                (lit(false), col("a")),
+                (col("c"), col("b")),
+                (lit(ScalarValue::Boolean(None)), col("d")),
+            ],
+            None,
+        );
+        assert_eq!(
+            simplify_case(&expr)?,
+            Some(case(vec![(col("c"), col("b"))], None))
+        );
+
+        // all branches are false
+        let expr = case(vec![(lit(false), col("a"))], Some(col("b")));
+        assert_eq!(simplify_case(&expr)?, Some(col("b")));
+        let expr = case(vec![(lit(false), col("a"))], None);
+        assert_eq!(simplify_case(&expr)?, Some(lit(ScalarValue::Null)));
+
+        // nothing to simplify
+        let expr = case(vec![(col("c"), col("a"))], Some(col("b")));
+        assert_eq!(simplify_case(&expr)?, None);
+        assert_eq!(simplify_case(&col("a"))?, None);
+
+        let expr = case(vec![(lit(1), col("a"))], None);
+        assert!(simplify_case(&expr).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn grouping_sets_sub_expressions_round_trip() -> Result<()> {
+        let expr = Expr::GroupingSet(GroupingSet::GroupingSets(vec![
+            vec![col("a"), col("b")],
+            vec![col("c")],
+            vec![],
+        ]));
+
+        let sub_expressions = expr_sub_expressions(&expr)?;
+        assert_eq!(
+            sub_expressions,
+            vec![
+                lit(GROUPING_SET_MARKER),
+                col("a"),
+                col("b"),
+                lit(GROUPING_SET_MARKER),
+                col("c"),
+                lit(GROUPING_SET_MARKER),
+            ]
+        );
+        assert_eq!(rewrite_expression(&expr, &sub_expressions)?, expr);
+
+        let expr = Expr::GroupingSet(GroupingSet::GroupingSets(vec![
+            vec![col("a")],
+            vec![col("a"), col("b")],
+        ]));
+        let sub_expressions = expr_sub_expressions(&expr)?;
+        assert_eq!(rewrite_expression(&expr, &sub_expressions)?, expr);
+        Ok(())
+    }
+
+    #[test]
+    fn fuse_sort_limit_sets_fetch() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .sort(vec![col("a").sort(true, false)])?
+            .limit(10)?
+            .build()?;
+        let limit = match &plan {
+            LogicalPlan::Limit(limit) => limit,
+            _ => panic!("expected Limit, got {:?}", plan),
+        };
+
+        let fused = fuse_sort_limit(limit).unwrap();
+        match &fused {
+            LogicalPlan::Sort(Sort { fetch, .. }) => assert_eq!(*fetch, Some(10)),
+            _ => panic!("expected Sort, got {:?}", fused),
+        }
+        let expected = "Sort: #test.a ASC NULLS LAST, fetch=10\
+        \n  TableScan: test projection=None";
+        assert_eq!(expected, format!("{:?}", fused));
+
+        // the fetch survives rebuilding the plan
+        let inputs = fused.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let rebuilt = from_plan(&fused, &fused.expressions(), &inputs)?;
+        assert_eq!(expected, format!("{:?}", rebuilt));
+
+        // a limit skipping rows stays above the sort
+        let plan = LogicalPlanBuilder::from(fused)
+            .limit_with_offset(2, 3)?
+            .build()?;
+        let limit = match &plan {
+            LogicalPlan::Limit(limit) => limit,
+            _ => panic!("expected Limit, got {:?}", plan),
+        };
+        let expected = "Limit: skip=2, fetch=3\
+        \n  Sort: #test.a ASC NULLS LAST, fetch=5\
+        \n    TableScan: test projection=None";
+        assert_eq!(expected, format!("{:?}", fuse_sort_limit(limit).unwrap()));
+
+        // the sort fetch saturates instead of overflowing
+        let limit = Limit {
+            skip: Some(usize::MAX),
+            n: 3,
+            input: Arc::new(
+                LogicalPlanBuilder::from(test_table_scan()?)
+                    .sort(vec![col("a").sort(true, false)])?
+                    .build()?,
+            ),
+        };
+        match fuse_sort_limit(&limit).unwrap() {
+            LogicalPlan::Limit(Limit { input, .. }) => match input.as_ref() {
+                LogicalPlan::Sort(Sort { fetch, .. }) => {
+                    assert_eq!(*fetch, Some(usize::MAX))
+                }
+                other => panic!("expected Sort, got {:?}", other),
+            },
+            other => panic!("expected Limit, got {:?}", other),
+        }
+
+        let limit = Limit {
+            skip: None,
+            n: 1,
+            input: Arc::new(test_table_scan()?),
+        };
+        assert!(fuse_sort_limit(&limit).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn add_filter_without_predicates() -> Result<()> {
+        let plan = test_table_scan()?;
+        let expected = format!("{:?}", plan);
+        let plan = add_filter(plan, &[]);
+        assert!(matches!(plan, LogicalPlan::TableScan(_)));
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let predicate = col("a").gt(lit(1u32));
+        let plan = add_filter(plan, &[&predicate]);
+        let expected = "Filter: #a > UInt32(1)\
+        \n  TableScan: test projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        Ok(())
+    }
+
+    #[test]
+    fn from_plan_keeps_rewritten_subquery_expressions() -> Result<()> {
+        // the subquery filter references the outer column #outer.a
+        let inner = LogicalPlanBuilder::from(test_table_scan_with_name("inner")?)
+            .filter(col("inner.a").eq(col("outer.a")))?
+            .project(vec![col("inner.b")])?
+            .build()?;
+        let plan = LogicalPlan::Subquery(Subquery {
+            subquery: Arc::new(inner),
+        });
+        assert!(plan.expressions().is_empty());
+
+        // rewrite the correlated column in all nodes of the plan
+        fn rewrite(plan: &LogicalPlan) -> Result<LogicalPlan> {
+            let from = Column::from_qualified_name("outer.a");
+            let to = Column::from_qualified_name("outer.c");
+            let replace_map = vec![(&from, &to)].into_iter().collect();
+            let expr = plan
+                .expressions()
+                .into_iter()
+                .map(|e| replace_col(e, &replace_map))
+                .collect::<Result<Vec<_>>>()?;
+            let inputs = plan
+                .inputs()
+                .into_iter()
+                .map(rewrite)
+                .collect::<Result<Vec<_>>>()?;
+            from_plan(plan, &expr, &inputs)
+        }
+        let new_plan = rewrite(&plan)?;
+
+        let subquery = match &new_plan {
+            LogicalPlan::Subquery(Subquery { subquery }) => subquery.as_ref(),
+            _ => panic!("expected Subquery, got {:?}", new_plan),
+        };
+        let expected = "Projection: #inner.b\
+        \n  Filter: #inner.a = #outer.c\
+        \n    TableScan: inner projection=None";
+        assert_eq!(expected, format!("{:?}", subquery));
+
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let err = from_plan(&plan, &[col("a")], &inputs).unwrap_err();
+        assert!(
+            err.to_string().contains("Subquery expects no expressions"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn combine_limit_nodes_of_plan() -> Result<()> {
+        let inner = Limit {
+            skip: Some(3),
+            n: 5,
+            input: Arc::new(test_table_scan()?),
+        };
+        let outer = Limit {
+            skip: Some(2),
+            n: 10,
+            input: Arc::new(LogicalPlan::Limit(inner.clone())),
+        };
+        let combined = combine_limit_nodes(&outer)?;
+        assert_eq!(combined.skip, Some(5));
+        assert_eq!(combined.n, 3);
+        assert!(Arc::ptr_eq(&combined.input, &inner.input));
+
+        // skipping past the inner limit fetches nothing
+        let outer = Limit {
+            skip: Some(6),
+            ..outer
+        };
+        let combined = combine_limit_nodes(&outer)?;
+        assert_eq!((combined.n, combined.skip), (0, Some(9)));
+
+        // the outer limit must be directly above another limit
+        let err = combine_limit_nodes(&inner).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expects a Limit below the outer limit"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn combine_limits_overlapping() {
+        // rows [0, 10) of rows [0, 5)
+        assert_eq!(combine_limits(10, None, 5, None), (5, None));
+        // rows [2, 5) of rows [0, 5)
+        assert_eq!(combine_limits(3, Some(2), 5, None), (3, Some(2)));
+        // rows [2, 12) of rows [3, 8)
+        assert_eq!(combine_limits(10, Some(2), 5, Some(3)), (3, Some(5)));
+        // rows [0, 2) of rows [3, 13)
+        assert_eq!(combine_limits(2, None, 10, Some(3)), (2, Some(3)));
+    }
+
+    #[test]
+    fn combine_limits_non_overlapping() {
+        // the outer limit skips past the end of the inner one
+        assert_eq!(combine_limits(10, Some(5), 5, None), (0, Some(5)));
+        assert_eq!(combine_limits(10, Some(8), 5, Some(1)), (0, Some(9)));
+        // skips saturate instead of overflowing
+        assert_eq!(
+            combine_limits(10, Some(usize::MAX), 5, Some(1)),
+            (0, Some(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn rewrite_plan_exprs_uppercases_columns() -> Result<()> {
+        struct UppercaseColumns;
+        impl ExprRewriter for UppercaseColumns {
+            fn mutate(&mut self, expr: Expr) -> Result<Expr> {
+                match expr {
+                    Expr::Column(c) => Ok(Expr::Column(Column {
+                        relation: c.relation,
+                        name: c.name.to_uppercase(),
+                    })),
+                    expr => Ok(expr),
+                }
+            }
+        }
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .project(vec![col("a"), col("b")])?
+            .build()?;
+
+        let plan =
+            rewrite_plan_exprs(&plan, &mut |expr| expr.rewrite(&mut UppercaseColumns))?;
+        let expected = "Projection: #test.A, #test.B\
+        \n  Filter: #test.A > UInt32(1)\
+        \n    TableScan: test projection=None";
+        assert_eq!(expected, format!("{:?}", plan));
+        Ok(())
+    }
 }
@@ -23,14 +23,15 @@ use super::{
     hash_join::PartitionMode, udaf, union::UnionExec, values::ValuesExec, windows,
 };
 use crate::execution::context::{ExecutionProps, SessionState};
+use crate::logical_expr::TableProviderFilterPushDown;
 use crate::logical_plan::plan::{
-    source_as_provider, Aggregate, EmptyRelation, Filter, Join, Projection, Sort,
-    SubqueryAlias, TableScan, Window,
+    source_as_provider, Aggregate, EmptyRelation, EstimatedRows, Filter, Join,
+    Projection, Sort, SubqueryAlias, TableScan, Window,
 };
 use crate::logical_plan::{
-    unalias, unnormalize_cols, CrossJoin, DFSchema, Expr, LogicalPlan, Operator,
-    Partitioning as LogicalPartitioning, PlanType, Repartition, ToStringifiedPlan, Union,
-    UserDefinedLogicalNode,
+    combine_filters, unalias, unnormalize_cols, CrossJoin, DFSchema, Expr, LogicalPlan,
+    Operator, Partitioning as LogicalPartitioning, PlanType, Repartition,
+    ToStringifiedPlan, Union, UserDefinedLogicalNode,
 };
 use crate::logical_plan::{Limit, Values};
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
@@ -378,16 +379,47 @@ impl DefaultPhysicalPlanner {
                     source,
                     projection,
                     filters,
+                    partition_filters,
                     limit,
+                    projected_schema,
                     ..
                 }) => {
                     let source = source_as_provider(source)?;
                     // Remove all qualifiers from the scan as the provider
                     // doesn't know (nor should care) how the relation was
-                    // referred to in the query
-                    let filters = unnormalize_cols(filters.iter().cloned());
+                    // referred to in the query. Partition filters are handed
+                    // over with the row filters so the provider can prune.
+                    let filters = unnormalize_cols(
+                        filters.iter().chain(partition_filters.iter()).cloned(),
+                    );
                     let unaliased: Vec<Expr> = filters.into_iter().map(unalias).collect();
-                    source.scan(projection, &unaliased, *limit).await
+                    // Partition filters the provider does not apply exactly
+                    // are evaluated on top of the scan
+                    let mut residual = vec![];
+                    for (filter, unaliased) in partition_filters
+                        .iter()
+                        .zip(&unaliased[unaliased.len() - partition_filters.len()..])
+                    {
+                        if !matches!(
+                            source.supports_filter_pushdown(unaliased)?,
+                            TableProviderFilterPushDown::Exact
+                        ) {
+                            residual.push(filter.clone());
+                        }
+                    }
+                    let scan = source.scan(projection, &unaliased, *limit).await?;
+                    match combine_filters(&residual) {
+                        Some(predicate) => {
+                            let runtime_expr = self.create_physical_expr(
+                                &predicate,
+                                projected_schema,
+                                &scan.schema(),
+                                session_state,
+                            )?;
+                            Ok(Arc::new(FilterExec::try_new(runtime_expr, scan)?))
+                        }
+                        None => Ok(scan),
+                    }
                 }
                 LogicalPlan::Values(Values {
                     values,
@@ -722,7 +754,7 @@ impl DefaultPhysicalPlanner {
                         physical_partitioning,
                     )?) )
                 }
-                LogicalPlan::Sort(Sort { expr, input, .. }) => {
+                LogicalPlan::Sort(Sort { expr, input, fetch }) => {
                     let physical_input = self.create_initial_plan(input, session_state).await?;
                     let input_schema = physical_input.as_ref().schema();
                     let input_dfschema = input.as_ref().schema();
@@ -748,7 +780,12 @@ impl DefaultPhysicalPlanner {
                             )),
                         })
                         .collect::<Result<Vec<_>>>()?;
-                    Ok(Arc::new(SortExec::try_new(sort_expr, physical_input)?) )
+                    let sort = Arc::new(SortExec::try_new(sort_expr, physical_input)?);
+                    match fetch {
+                        // the sort produces a single partition
+                        Some(fetch) => Ok(Arc::new(GlobalLimitExec::new(sort, *fetch))),
+                        None => Ok(sort),
+                    }
                 }
                 LogicalPlan::Join(Join {
                     left,
@@ -838,7 +875,13 @@ impl DefaultPhysicalPlanner {
                         _ => Err(DataFusionError::Plan("SubqueryAlias should only wrap TableScan".to_string()))
                     }
                 }
-                LogicalPlan::Limit(Limit { input, n, .. }) => {
+                LogicalPlan::Limit(Limit { input, n, skip }) => {
+                    if skip.is_some() {
+                        return Err(DataFusionError::NotImplemented(
+                            "Physical plan does not support skipping rows in a Limit"
+                                .to_string(),
+                        ));
+                    }
                     let limit = *n;
                     let input = self.create_initial_plan(input, session_state).await?;
 
@@ -853,6 +896,10 @@ impl DefaultPhysicalPlanner {
 
                     Ok(Arc::new(GlobalLimitExec::new(input, limit)))
                 }
+                LogicalPlan::EstimatedRows(EstimatedRows { input, .. }) => {
+                    // the estimate is only a planning hint
+                    self.create_initial_plan(input, session_state).await
+                }
                 LogicalPlan::CreateExternalTable(_) => {
                     // There is no default plan for "CREATE EXTERNAL
                     // TABLE" -- it must be handled at a higher level (so
@@ -1259,7 +1306,14 @@ pub fn create_window_expr_with_name(
             partition_by,
             order_by,
             window_frame,
+            distinct,
         } => {
+            if *distinct {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "DISTINCT window functions are not supported: {:?}",
+                    e
+                )));
+            }
             let args = args
                 .iter()
                 .map(|e| {
@@ -1528,6 +1582,7 @@ fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::datasource::empty::EmptyTable;
     use crate::execution::context::TaskContext;
     use crate::execution::options::CsvReadOptions;
     use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
@@ -1882,6 +1937,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_scan_partitions_filters_unsupported_provider() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("state", DataType::Utf8, false),
+        ]);
+        let provider = Arc::new(
+            EmptyTable::new(Arc::new(schema))
+                .with_partition_cols(vec!["state".to_string()]),
+        );
+        let logical_plan = LogicalPlanBuilder::scan_partitions(
+            "employee",
+            provider,
+            None,
+            vec![col("state").eq(lit("CO"))],
+        )?
+        .build()?;
+
+        // EmptyTable does not apply the partition filter itself
+        let plan = plan(&logical_plan).await?;
+        let plan = displayable(plan.as_ref()).indent().to_string();
+        assert!(plan.starts_with("FilterExec: state@1 = CO"), "{}", plan);
+        Ok(())
+    }
+
     /// An example extension node that doesn't do anything
     struct NoOpExtensionNode {
         schema: DFSchemaRef,
@@ -17,14 +17,21 @@
 
 //! ScalarValue reimported from datafusion-common
 
-pub use datafusion_common::{ScalarType, ScalarValue};
+#[cfg(feature = "crypto_expressions")]
+pub use datafusion_common::DigestAlgorithm;
+pub use datafusion_common::{
+    ScalarArrayCache, ScalarColumnBuilder, ScalarType, ScalarValue,
+    MAX_NUMERIC_SERIES_LEN,
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::DataFusionError;
     use crate::from_slice::FromSlice;
     use arrow::{array::*, datatypes::*};
     use std::cmp::Ordering;
+    use std::collections::HashSet;
     use std::sync::Arc;
 
     #[test]
@@ -159,7 +166,7 @@ mod tests {
     #[test]
     fn scalar_list_null_to_array() {
         let list_array_ref =
-            ScalarValue::List(None, Box::new(DataType::UInt64)).to_array();
+            ScalarValue::List(None, Box::new(DataType::UInt64), true).to_array();
         let list_array = list_array_ref.as_any().downcast_ref::<ListArray>().unwrap();
 
         assert!(list_array.is_null(0));
@@ -176,6 +183,7 @@ mod tests {
                 ScalarValue::UInt64(Some(101)),
             ])),
             Box::new(DataType::UInt64),
+            true,
         )
         .to_array();
 
@@ -877,6 +885,7 @@ mod tests {
                 ScalarValue::from(3i32),
             ])),
             Box::new(DataType::Int32),
+            true,
         );
 
         let l1 = ScalarValue::List(
@@ -885,11 +894,13 @@ mod tests {
                 ScalarValue::from(5i32),
             ])),
             Box::new(DataType::Int32),
+            true,
         );
 
         let l2 = ScalarValue::List(
             Some(Box::new(vec![ScalarValue::from(6i32)])),
             Box::new(DataType::Int32),
+            true,
         );
 
         // Define struct scalars
@@ -935,13 +946,20 @@ mod tests {
         let nl0 = ScalarValue::List(
             Some(Box::new(vec![s0.clone(), s1.clone()])),
             Box::new(s0.get_datatype()),
+            true,
         );
 
-        let nl1 =
-            ScalarValue::List(Some(Box::new(vec![s2])), Box::new(s0.get_datatype()));
+        let nl1 = ScalarValue::List(
+            Some(Box::new(vec![s2])),
+            Box::new(s0.get_datatype()),
+            true,
+        );
 
-        let nl2 =
-            ScalarValue::List(Some(Box::new(vec![s1])), Box::new(s0.get_datatype()));
+        let nl2 = ScalarValue::List(
+            Some(Box::new(vec![s1])),
+            Box::new(s0.get_datatype()),
+            true,
+        );
 
         // iter_to_array for list-of-struct
         let array = ScalarValue::iter_to_array(vec![nl0, nl1, nl2]).unwrap();
@@ -1093,6 +1111,7 @@ mod tests {
                         ScalarValue::from(3i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -1100,6 +1119,7 @@ mod tests {
                         ScalarValue::from(5i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -1107,6 +1127,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let l2 = ScalarValue::List(
@@ -1114,6 +1135,7 @@ mod tests {
                 ScalarValue::List(
                     Some(Box::new(vec![ScalarValue::from(6i32)])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -1121,6 +1143,7 @@ mod tests {
                         ScalarValue::from(8i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -1128,18 +1151,21 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let l3 = ScalarValue::List(
             Some(Box::new(vec![ScalarValue::List(
                 Some(Box::new(vec![ScalarValue::from(9i32)])),
                 Box::new(DataType::Int32),
+                true,
             )])),
             Box::new(DataType::List(Box::new(Field::new(
                 "item",
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let array = ScalarValue::iter_to_array(vec![l1, l2, l3]).unwrap();
@@ -1202,4 +1228,2413 @@ mod tests {
             DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".to_owned()))
         );
     }
+
+    #[test]
+    fn scalar_arithmetic_overflow() {
+        let err = ScalarValue::Int8(Some(100))
+            .mul(&ScalarValue::Int8(Some(2)))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Int8(100)"), "{}", err);
+        assert!(err.contains("Int8(2)"), "{}", err);
+        assert!(err.contains('*'), "{}", err);
+
+        let err = ScalarValue::UInt64(Some(u64::MAX))
+            .add(&ScalarValue::UInt64(Some(1)))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Overflow evaluating UInt64(18446744073709551615) + UInt64(1)"),
+            "{}",
+            err
+        );
+
+        assert_eq!(
+            ScalarValue::Int32(Some(40))
+                .add(&ScalarValue::Int32(Some(2)))
+                .unwrap(),
+            ScalarValue::Int32(Some(42))
+        );
+        assert_eq!(
+            ScalarValue::Int32(None)
+                .mul(&ScalarValue::Int32(Some(2)))
+                .unwrap(),
+            ScalarValue::Int32(None)
+        );
+    }
+
+    #[test]
+    fn scalar_is_zero_is_one() {
+        assert!(ScalarValue::Int32(Some(0)).is_zero());
+        assert!(ScalarValue::UInt8(Some(0)).is_zero());
+        assert!(ScalarValue::Float64(Some(0.0)).is_zero());
+        assert!(ScalarValue::Float64(Some(-0.0)).is_zero());
+        assert!(ScalarValue::Float32(Some(-0.0)).is_zero());
+        assert!(ScalarValue::Decimal128(Some(0), 10, 2).is_zero());
+        assert!(!ScalarValue::Int32(Some(1)).is_zero());
+        assert!(!ScalarValue::Int32(None).is_zero());
+        assert!(!ScalarValue::Utf8(Some("0".to_string())).is_zero());
+
+        assert!(ScalarValue::Int64(Some(1)).is_one());
+        assert!(ScalarValue::Float32(Some(1.0)).is_one());
+        assert!(ScalarValue::Decimal128(Some(100), 10, 2).is_one());
+        assert!(!ScalarValue::Decimal128(Some(1), 10, 2).is_one());
+        assert!(!ScalarValue::Int64(Some(0)).is_one());
+        assert!(!ScalarValue::Int64(None).is_one());
+        assert!(!ScalarValue::Null.is_one());
+        assert!(!ScalarValue::Null.is_zero());
+    }
+
+    #[test]
+    fn scalar_string_replace() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        assert_eq!(
+            utf8("abcabc")
+                .string_replace(&utf8("b"), &utf8("xy"))
+                .unwrap(),
+            utf8("axycaxyc")
+        );
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("aaa".to_string()))
+                .string_replace(&utf8("aa"), &utf8("b"))
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("ba".to_string()))
+        );
+
+        // empty `from` leaves the input untouched
+        assert_eq!(
+            utf8("abc").string_replace(&utf8(""), &utf8("-")).unwrap(),
+            utf8("abc")
+        );
+
+        // nulls propagate from any operand
+        assert_eq!(
+            ScalarValue::Utf8(None)
+                .string_replace(&utf8("a"), &utf8("b"))
+                .unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert_eq!(
+            utf8("abc")
+                .string_replace(&ScalarValue::Utf8(None), &utf8("b"))
+                .unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert_eq!(
+            utf8("abc")
+                .string_replace(&utf8("a"), &ScalarValue::Utf8(None))
+                .unwrap(),
+            ScalarValue::Utf8(None)
+        );
+
+        assert!(ScalarValue::Int32(Some(1))
+            .string_replace(&utf8("a"), &utf8("b"))
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_column_builder() {
+        let scalars = vec![
+            ScalarValue::Boolean(Some(true)),
+            ScalarValue::Int8(Some(-3)),
+            ScalarValue::UInt64(Some(42)),
+            ScalarValue::Float64(Some(1.5)),
+            ScalarValue::Int32(None),
+            ScalarValue::Date32(Some(19000)),
+            ScalarValue::Utf8(Some("foo".to_string())),
+            ScalarValue::LargeUtf8(None),
+            ScalarValue::TimestampNanosecond(Some(1), Some("UTC".to_string())),
+            ScalarValue::Decimal128(Some(123), 10, 2),
+            ScalarValue::Null,
+        ];
+
+        for scalar in scalars {
+            let builder = ScalarColumnBuilder::new(scalar.clone());
+            for size in [0, 1, 7, 1024] {
+                let expected = scalar.to_array_of_size(size);
+                let actual = builder.build(size);
+                assert_eq!(expected.data(), actual.data(), "{:?}", scalar);
+            }
+        }
+    }
+
+    #[test]
+    fn scalar_string_position() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        assert_eq!(
+            utf8("abcb").string_position(&utf8("b")).unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("abc".to_string()))
+                .string_position(&utf8("cd"))
+                .unwrap(),
+            ScalarValue::Int32(Some(0))
+        );
+        // positions count characters, not bytes
+        assert_eq!(
+            utf8("jösé").string_position(&utf8("é")).unwrap(),
+            ScalarValue::Int32(Some(4))
+        );
+        assert_eq!(
+            utf8("abc").string_position(&utf8("")).unwrap(),
+            ScalarValue::Int32(Some(1))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).string_position(&utf8("a")).unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            utf8("abc")
+                .string_position(&ScalarValue::Utf8(None))
+                .unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert!(utf8("abc")
+            .string_position(&ScalarValue::Int32(Some(1)))
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_string_left_right() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+        let s = utf8("héllo wörld");
+
+        assert_eq!(s.string_left(4).unwrap(), utf8("héll"));
+        assert_eq!(s.string_right(4).unwrap(), utf8("örld"));
+        assert_eq!(s.string_left(-4).unwrap(), utf8("héllo w"));
+        assert_eq!(s.string_right(-4).unwrap(), utf8("o wörld"));
+        assert_eq!(s.string_left(100).unwrap(), s);
+        assert_eq!(s.string_right(100).unwrap(), s);
+        assert_eq!(s.string_left(-100).unwrap(), utf8(""));
+        assert_eq!(s.string_right(-100).unwrap(), utf8(""));
+        assert_eq!(s.string_left(i64::MIN).unwrap(), utf8(""));
+        assert_eq!(s.string_right(i64::MIN).unwrap(), utf8(""));
+        assert_eq!(s.string_right(i64::MAX).unwrap(), s);
+
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("abc".to_string()))
+                .string_left(2)
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("ab".to_string()))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).string_right(2).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(ScalarValue::Int64(Some(1)).string_left(1).is_err());
+    }
+
+    #[test]
+    fn scalar_cast_to_checked() {
+        assert_eq!(
+            ScalarValue::Int8(Some(100))
+                .cast_to_checked(&DataType::Int64)
+                .unwrap(),
+            ScalarValue::Int64(Some(100))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(Some("42".to_string()))
+                .cast_to_checked(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(42))
+        );
+
+        // lossy conversions produce a typed null
+        assert_eq!(
+            ScalarValue::Int64(Some(1000))
+                .cast_to_checked(&DataType::Int8)
+                .unwrap(),
+            ScalarValue::Int8(None)
+        );
+        assert_eq!(
+            ScalarValue::Utf8(Some("foo".to_string()))
+                .cast_to_checked(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(1.5))
+                .cast_to_checked(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(i64::MAX))
+                .cast_to_checked(&DataType::Float32)
+                .unwrap(),
+            ScalarValue::Float32(None)
+        );
+
+        // exact narrowing is not lossy
+        assert_eq!(
+            ScalarValue::Float64(Some(2.0))
+                .cast_to_checked(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(2))
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(1 << 24))
+                .cast_to_checked(&DataType::Float32)
+                .unwrap(),
+            ScalarValue::Float32(Some(16777216.0))
+        );
+
+        // structurally impossible casts are errors
+        let scalar = ScalarValue::Struct(
+            Some(Box::new(vec![ScalarValue::Int32(Some(1))])),
+            Box::new(vec![Field::new("a", DataType::Int32, true)]),
+        );
+        assert!(scalar.cast_to_checked(&DataType::Int32).is_err());
+    }
+
+    #[test]
+    fn scalar_interval_nanos() {
+        // 2 days and 500 milliseconds
+        let day_time = ScalarValue::IntervalDayTime(Some((2 << 32) | 500));
+        let nanos = 2 * 86_400 * 1_000_000_000 + 500 * 1_000_000;
+        assert_eq!(day_time.interval_as_nanos().unwrap(), Some(nanos));
+
+        let month_day_nano = ScalarValue::interval_from_nanos(nanos).unwrap();
+        assert_eq!(
+            month_day_nano,
+            ScalarValue::IntervalMonthDayNano(Some((2 << 64) | 500_000_000))
+        );
+        assert_eq!(month_day_nano.interval_as_nanos().unwrap(), Some(nanos));
+
+        // negative intervals round trip
+        let negative = ScalarValue::interval_from_nanos(-nanos).unwrap();
+        assert_eq!(negative.interval_as_nanos().unwrap(), Some(-nanos));
+
+        // the number of days must fit in an i32
+        let err = ScalarValue::interval_from_nanos(i128::MAX).unwrap_err();
+        assert!(err.to_string().contains("Overflow"), "{}", err);
+
+        assert_eq!(
+            ScalarValue::IntervalDayTime(None)
+                .interval_as_nanos()
+                .unwrap(),
+            None
+        );
+
+        // months are not a fixed duration
+        let err = ScalarValue::IntervalMonthDayNano(Some((1 << 96) | 5))
+            .interval_as_nanos()
+            .unwrap_err();
+        assert!(err.to_string().contains("months"), "{}", err);
+        assert!(ScalarValue::IntervalYearMonth(Some(1))
+            .interval_as_nanos()
+            .is_err());
+        assert!(ScalarValue::Int64(Some(1)).interval_as_nanos().is_err());
+    }
+
+    #[test]
+    fn scalar_ascii_chr() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        assert_eq!(utf8("abc").ascii().unwrap(), ScalarValue::Int32(Some(97)));
+        assert_eq!(utf8("éa").ascii().unwrap(), ScalarValue::Int32(Some(233)));
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("😀".to_string()))
+                .ascii()
+                .unwrap(),
+            ScalarValue::Int32(Some(0x1F600))
+        );
+        assert_eq!(utf8("").ascii().unwrap(), ScalarValue::Int32(Some(0)));
+        assert_eq!(
+            ScalarValue::Utf8(None).ascii().unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert!(ScalarValue::Int32(Some(1)).ascii().is_err());
+
+        assert_eq!(ScalarValue::Int32(Some(97)).chr().unwrap(), utf8("a"));
+        assert_eq!(ScalarValue::Int64(Some(233)).chr().unwrap(), utf8("é"));
+        assert_eq!(
+            ScalarValue::Int64(None).chr().unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        // surrogates and values above 0x10FFFF are not characters
+        assert!(ScalarValue::Int32(Some(0xD800)).chr().is_err());
+        assert!(ScalarValue::Int64(Some(0x110000)).chr().is_err());
+        assert!(ScalarValue::Int32(Some(-1)).chr().is_err());
+        assert!(utf8("a").chr().is_err());
+    }
+
+    #[test]
+    fn scalar_add_sub() {
+        assert_eq!(
+            ScalarValue::Int32(Some(40))
+                .add(&ScalarValue::Int32(Some(2)))
+                .unwrap(),
+            ScalarValue::Int32(Some(42))
+        );
+        assert_eq!(
+            ScalarValue::Int32(Some(40))
+                .sub(&ScalarValue::Int32(Some(2)))
+                .unwrap(),
+            ScalarValue::Int32(Some(38))
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(1.5))
+                .add(&ScalarValue::Float64(Some(2.25)))
+                .unwrap(),
+            ScalarValue::Float64(Some(3.75))
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(1.5))
+                .sub(&ScalarValue::Float64(Some(2.25)))
+                .unwrap(),
+            ScalarValue::Float64(Some(-0.75))
+        );
+
+        // decimals with matching precision and scale
+        assert_eq!(
+            ScalarValue::Decimal128(Some(123), 10, 2)
+                .add(&ScalarValue::Decimal128(Some(77), 10, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(200), 10, 2)
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(123), 10, 2)
+                .sub(&ScalarValue::Decimal128(Some(200), 10, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(-77), 10, 2)
+        );
+        // the result must fit in the precision
+        assert!(ScalarValue::Decimal128(Some(999), 3, 0)
+            .add(&ScalarValue::Decimal128(Some(1), 3, 0))
+            .is_err());
+
+        // mismatching scale
+        let err = ScalarValue::Decimal128(Some(123), 10, 2)
+            .add(&ScalarValue::Decimal128(Some(123), 10, 3))
+            .unwrap_err();
+        assert!(matches!(err, DataFusionError::Internal(_)), "{}", err);
+
+        // nulls
+        assert_eq!(
+            ScalarValue::Int64(None)
+                .sub(&ScalarValue::Int64(Some(1)))
+                .unwrap(),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(None, 10, 2)
+                .add(&ScalarValue::Decimal128(Some(1), 10, 2))
+                .unwrap(),
+            ScalarValue::Decimal128(None, 10, 2)
+        );
+        assert_eq!(
+            ScalarValue::Null.add(&ScalarValue::Int32(Some(1))).unwrap(),
+            ScalarValue::Int32(None)
+        );
+        assert_eq!(
+            ScalarValue::Null.sub(&ScalarValue::Null).unwrap(),
+            ScalarValue::Null
+        );
+        // a null does not make non-numeric values addable
+        let err = ScalarValue::Null
+            .add(&ScalarValue::Utf8(Some("a".to_string())))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported arithmetic operation"),
+            "{}",
+            err
+        );
+        assert!(ScalarValue::Boolean(Some(true))
+            .sub(&ScalarValue::Null)
+            .is_err());
+
+        // incompatible types and overflow
+        let err = ScalarValue::Int32(Some(1))
+            .add(&ScalarValue::Int64(Some(1)))
+            .unwrap_err();
+        assert!(matches!(err, DataFusionError::Internal(_)), "{}", err);
+        assert!(ScalarValue::UInt8(Some(0))
+            .sub(&ScalarValue::UInt8(Some(1)))
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_lpad_rpad() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+        let s = utf8("héllo");
+
+        assert_eq!(s.lpad(8, None).unwrap(), utf8("   héllo"));
+        assert_eq!(s.rpad(8, Some(&utf8("*"))).unwrap(), utf8("héllo***"));
+        assert_eq!(s.lpad(10, Some(&utf8("xy"))).unwrap(), utf8("xyxyxhéllo"));
+        assert_eq!(s.rpad(10, Some(&utf8("xy"))).unwrap(), utf8("hélloxyxyx"));
+
+        // a shorter length truncates from the right
+        assert_eq!(s.lpad(3, None).unwrap(), utf8("hél"));
+        assert_eq!(s.rpad(3, None).unwrap(), utf8("hél"));
+        assert_eq!(s.lpad(-1, None).unwrap(), utf8(""));
+        assert_eq!(s.rpad(8, Some(&utf8(""))).unwrap(), s);
+
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("ab".to_string()))
+                .lpad(4, Some(&utf8("0")))
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("00ab".to_string()))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).lpad(4, None).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert_eq!(
+            s.rpad(4, Some(&ScalarValue::Utf8(None))).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(ScalarValue::Int64(Some(1)).lpad(4, None).is_err());
+        assert!(s.rpad(4, Some(&ScalarValue::Int64(Some(1)))).is_err());
+    }
+
+    #[test]
+    fn scalar_try_from_time_duration_fixed_size_binary_datatype() {
+        let data_types = vec![
+            DataType::FixedSizeBinary(3),
+            DataType::Time32(TimeUnit::Second),
+            DataType::Time32(TimeUnit::Millisecond),
+            DataType::Time64(TimeUnit::Microsecond),
+            DataType::Time64(TimeUnit::Nanosecond),
+            DataType::Duration(TimeUnit::Second),
+            DataType::Duration(TimeUnit::Millisecond),
+            DataType::Duration(TimeUnit::Microsecond),
+            DataType::Duration(TimeUnit::Nanosecond),
+        ];
+
+        for data_type in data_types {
+            let scalar = ScalarValue::try_from(&data_type).unwrap();
+            assert!(scalar.is_null(), "{:?}", scalar);
+            assert_eq!(scalar.get_datatype(), data_type);
+
+            let array = scalar.to_array_of_size(3);
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.null_count(), 3);
+            assert_eq!(array.data_type(), &data_type);
+            for i in 0..3 {
+                assert!(scalar.eq_array(&array, i));
+                assert_eq!(ScalarValue::try_from_array(&array, i).unwrap(), scalar);
+            }
+        }
+
+        // non-null values round trip as well
+        let scalars = vec![
+            ScalarValue::FixedSizeBinary(3, Some(vec![1, 2, 3])),
+            ScalarValue::Time32Second(Some(3_600)),
+            ScalarValue::Time32Millisecond(Some(3_600_000)),
+            ScalarValue::Time64Microsecond(Some(3_600_000_000)),
+            ScalarValue::Time64Nanosecond(Some(3_600_000_000_000)),
+            ScalarValue::DurationSecond(Some(-5)),
+            ScalarValue::DurationMillisecond(Some(5)),
+            ScalarValue::DurationMicrosecond(Some(5)),
+            ScalarValue::DurationNanosecond(Some(5)),
+        ];
+        for scalar in scalars {
+            let array = scalar.to_array_of_size(3);
+            assert_eq!(array.len(), 3);
+            assert_eq!(array.null_count(), 0);
+            assert_eq!(array.data_type(), &scalar.get_datatype());
+            for i in 0..3 {
+                assert!(scalar.eq_array(&array, i));
+                assert_eq!(ScalarValue::try_from_array(&array, i).unwrap(), scalar);
+            }
+
+            let array = ScalarValue::iter_to_array(vec![
+                scalar.clone(),
+                ScalarValue::try_from(&scalar.get_datatype()).unwrap(),
+            ])
+            .unwrap();
+            assert_eq!(array.len(), 2);
+            assert_eq!(array.null_count(), 1);
+            assert_eq!(ScalarValue::try_from_array(&array, 0).unwrap(), scalar);
+        }
+
+        assert_eq!(
+            format!("{:?}", ScalarValue::FixedSizeBinary(2, Some(vec![1, 2]))),
+            "FixedSizeBinary(2, \"1,2\")"
+        );
+        assert_ne!(
+            ScalarValue::FixedSizeBinary(2, None),
+            ScalarValue::FixedSizeBinary(3, None)
+        );
+
+        // the value must have the declared width
+        let err = ScalarValue::FixedSizeBinary(3, Some(vec![1, 2]))
+            .try_to_array_of_size(1)
+            .unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::Internal(_)),
+            "unexpected error: {}",
+            err
+        );
+
+        // times and durations are formatted
+        let cases = vec![
+            (ScalarValue::Time32Second(Some(3_661)), "01:01:01"),
+            (
+                ScalarValue::Time32Millisecond(Some(3_661_500)),
+                "01:01:01.500",
+            ),
+            (
+                ScalarValue::Time64Microsecond(Some(3_661_000_001)),
+                "01:01:01.000001",
+            ),
+            (
+                ScalarValue::Time64Nanosecond(Some(3_661_000_000_001)),
+                "01:01:01.000000001",
+            ),
+            (ScalarValue::Time32Second(None), "NULL"),
+            // times outside of a day fall back to the raw value
+            (ScalarValue::Time32Second(Some(-1)), "-1"),
+            (ScalarValue::Time32Second(Some(86_400)), "86400"),
+            (ScalarValue::DurationSecond(Some(-5)), "-PT5S"),
+            (ScalarValue::DurationMillisecond(Some(1_500)), "PT1.5S"),
+            (ScalarValue::DurationMicrosecond(Some(5)), "PT0.000005S"),
+            (ScalarValue::DurationNanosecond(Some(5)), "PT0.000000005S"),
+            (ScalarValue::DurationSecond(None), "NULL"),
+            (
+                ScalarValue::DurationSecond(Some(i64::MAX)),
+                "9223372036854775807",
+            ),
+        ];
+        for (scalar, expected) in cases {
+            assert_eq!(scalar.to_string(), expected, "{:?}", scalar);
+        }
+    }
+
+    #[test]
+    fn scalar_cast_to() {
+        // numeric widening
+        assert_eq!(
+            ScalarValue::Int32(Some(-7))
+                .cast_to(&DataType::Int64)
+                .unwrap(),
+            ScalarValue::Int64(Some(-7))
+        );
+        assert_eq!(
+            ScalarValue::Int32(Some(3))
+                .cast_to(&DataType::Float64)
+                .unwrap(),
+            ScalarValue::Float64(Some(3.0))
+        );
+        assert_eq!(
+            ScalarValue::UInt8(Some(255))
+                .cast_to(&DataType::Int16)
+                .unwrap(),
+            ScalarValue::Int16(Some(255))
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(1))
+                .cast_to(&DataType::Int64)
+                .unwrap(),
+            ScalarValue::Int64(Some(1))
+        );
+
+        // other casts go through the cast kernel
+        assert_eq!(
+            ScalarValue::Utf8(Some("42".to_string()))
+                .cast_to(&DataType::Int32)
+                .unwrap(),
+            ScalarValue::Int32(Some(42))
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(100))
+                .cast_to(&DataType::Int8)
+                .unwrap(),
+            ScalarValue::Int8(Some(100))
+        );
+
+        // nulls stay null with the target type
+        assert_eq!(
+            ScalarValue::Int32(None).cast_to(&DataType::Int64).unwrap(),
+            ScalarValue::Int64(None)
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).cast_to(&DataType::Int32).unwrap(),
+            ScalarValue::Int32(None)
+        );
+
+        // unparsable values and impossible casts are errors
+        assert!(ScalarValue::Utf8(Some("foo".to_string()))
+            .cast_to(&DataType::Int32)
+            .is_err());
+        let struct_type = DataType::Struct(vec![Field::new("a", DataType::Int32, true)]);
+        assert!(ScalarValue::Utf8(Some("a".to_string()))
+            .cast_to(&struct_type)
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_string_reverse() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        assert_eq!(utf8("abc").string_reverse().unwrap(), utf8("cba"));
+        assert_eq!(
+            utf8("héllo wörld").string_reverse().unwrap(),
+            utf8("dlröw olléh")
+        );
+        assert_eq!(utf8("日本語").string_reverse().unwrap(), utf8("語本日"));
+        // combining characters are reversed as separate code points
+        assert_eq!(
+            utf8("e\u{301}x").string_reverse().unwrap(),
+            utf8("x\u{301}e")
+        );
+        assert_eq!(utf8("").string_reverse().unwrap(), utf8(""));
+
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("ab".to_string()))
+                .string_reverse()
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("ba".to_string()))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).string_reverse().unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(ScalarValue::Int32(Some(1)).string_reverse().is_err());
+    }
+
+    #[test]
+    fn scalar_numeric_kind() {
+        let signed = vec![
+            ScalarValue::Int8(Some(1)),
+            ScalarValue::Int16(Some(1)),
+            ScalarValue::Int32(None),
+            ScalarValue::Int64(Some(-1)),
+        ];
+        for v in &signed {
+            assert!(v.is_signed_integer(), "{:?}", v);
+            assert!(!v.is_unsigned_integer(), "{:?}", v);
+            assert!(!v.is_floating(), "{:?}", v);
+        }
+
+        let unsigned = vec![
+            ScalarValue::UInt8(Some(1)),
+            ScalarValue::UInt16(None),
+            ScalarValue::UInt32(Some(1)),
+            ScalarValue::UInt64(Some(1)),
+        ];
+        for v in &unsigned {
+            assert!(!v.is_signed_integer(), "{:?}", v);
+            assert!(v.is_unsigned_integer(), "{:?}", v);
+            assert!(!v.is_floating(), "{:?}", v);
+        }
+
+        for v in &[ScalarValue::Float32(Some(1.0)), ScalarValue::Float64(None)] {
+            assert!(!v.is_signed_integer(), "{:?}", v);
+            assert!(!v.is_unsigned_integer(), "{:?}", v);
+            assert!(v.is_floating(), "{:?}", v);
+        }
+
+        for v in &[
+            ScalarValue::Decimal128(Some(1), 10, 2),
+            ScalarValue::Utf8(Some("1".to_string())),
+            ScalarValue::Null,
+        ] {
+            assert!(!v.is_signed_integer(), "{:?}", v);
+            assert!(!v.is_unsigned_integer(), "{:?}", v);
+            assert!(!v.is_floating(), "{:?}", v);
+        }
+    }
+
+    #[test]
+    fn scalar_timestamp_display() {
+        let utc = || Some("UTC".to_string());
+        let cases = vec![
+            (
+                ScalarValue::TimestampSecond(Some(0), None),
+                "1970-01-01T00:00:00",
+            ),
+            (
+                ScalarValue::TimestampSecond(Some(0), utc()),
+                "1970-01-01T00:00:00Z",
+            ),
+            (
+                ScalarValue::TimestampMillisecond(Some(1_599_566_400_123), utc()),
+                "2020-09-08T12:00:00.123Z",
+            ),
+            (
+                ScalarValue::TimestampMillisecond(Some(1_599_566_400_123), None),
+                "2020-09-08T12:00:00.123",
+            ),
+            (
+                ScalarValue::TimestampMicrosecond(Some(1_599_566_400_000_001), None),
+                "2020-09-08T12:00:00.000001",
+            ),
+            (
+                ScalarValue::TimestampMicrosecond(Some(1_599_566_400_000_001), utc()),
+                "2020-09-08T12:00:00.000001Z",
+            ),
+            (
+                ScalarValue::TimestampNanosecond(Some(0), utc()),
+                "1970-01-01T00:00:00Z",
+            ),
+            (
+                ScalarValue::TimestampNanosecond(Some(-1), None),
+                "1969-12-31T23:59:59.999999999",
+            ),
+            (
+                ScalarValue::TimestampNanosecond(Some(1), Some("+08:00".to_string())),
+                "1970-01-01T08:00:00.000000001+08:00",
+            ),
+            (
+                ScalarValue::TimestampSecond(Some(0), Some("-0530".to_string())),
+                "1969-12-31T18:30:00-05:30",
+            ),
+            // named timezones are shown as the equivalent UTC time
+            (
+                ScalarValue::TimestampSecond(
+                    Some(0),
+                    Some("America/New_York".to_string()),
+                ),
+                "1970-01-01T00:00:00 UTC (America/New_York)",
+            ),
+            (ScalarValue::TimestampSecond(None, utc()), "NULL"),
+            // out of range values fall back to the raw value
+            (
+                ScalarValue::TimestampSecond(Some(i64::MAX), None),
+                "9223372036854775807",
+            ),
+        ];
+        for (scalar, expected) in cases {
+            assert_eq!(scalar.to_string(), expected, "{:?}", scalar);
+        }
+
+        // Debug still shows the raw value and timezone
+        assert_eq!(
+            format!("{:?}", ScalarValue::TimestampNanosecond(Some(0), utc())),
+            "TimestampNanosecond(0, Some(\"UTC\"))"
+        );
+        assert_eq!(
+            format!("{:?}", ScalarValue::TimestampSecond(None, None)),
+            "TimestampSecond(NULL, None)"
+        );
+    }
+
+    #[test]
+    fn scalar_split_part() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+        let s = utf8("a,b,,c");
+
+        assert_eq!(s.split_part(&utf8(","), 1).unwrap(), utf8("a"));
+        assert_eq!(s.split_part(&utf8(","), 2).unwrap(), utf8("b"));
+        assert_eq!(s.split_part(&utf8(","), 3).unwrap(), utf8(""));
+        assert_eq!(s.split_part(&utf8(","), 4).unwrap(), utf8("c"));
+        assert_eq!(
+            utf8("a~@~b~@~c").split_part(&utf8("~@~"), 2).unwrap(),
+            utf8("b")
+        );
+
+        // out of range
+        assert_eq!(s.split_part(&utf8(","), 5).unwrap(), utf8(""));
+        assert_eq!(s.split_part(&utf8(";"), 2).unwrap(), utf8(""));
+
+        // empty delimiter
+        assert_eq!(s.split_part(&utf8(""), 1).unwrap(), s);
+        assert_eq!(s.split_part(&utf8(""), 2).unwrap(), utf8(""));
+
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("x.y".to_string()))
+                .split_part(&utf8("."), 2)
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("y".to_string()))
+        );
+
+        // nulls
+        assert_eq!(
+            ScalarValue::Utf8(None).split_part(&utf8(","), 1).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert_eq!(
+            s.split_part(&ScalarValue::Utf8(None), 1).unwrap(),
+            ScalarValue::Utf8(None)
+        );
+
+        assert!(s.split_part(&utf8(","), 0).is_err());
+        assert!(s.split_part(&utf8(","), -1).is_err());
+        assert!(ScalarValue::Int32(Some(1))
+            .split_part(&utf8(","), 1)
+            .is_err());
+        assert!(s.split_part(&ScalarValue::Int32(Some(1)), 1).is_err());
+    }
+
+    #[test]
+    fn scalar_abs() {
+        assert_eq!(
+            ScalarValue::Int32(Some(5)).abs().unwrap(),
+            ScalarValue::Int32(Some(5))
+        );
+        assert_eq!(
+            ScalarValue::Int8(Some(-5)).abs().unwrap(),
+            ScalarValue::Int8(Some(5))
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(-1.5)).abs().unwrap(),
+            ScalarValue::Float64(Some(1.5))
+        );
+        assert_eq!(
+            ScalarValue::Float32(Some(-0.0)).abs().unwrap(),
+            ScalarValue::Float32(Some(0.0))
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(-12345), 10, 2).abs().unwrap(),
+            ScalarValue::Decimal128(Some(12345), 10, 2)
+        );
+        assert_eq!(
+            ScalarValue::Int64(None).abs().unwrap(),
+            ScalarValue::Int64(None)
+        );
+
+        let err = ScalarValue::Int32(Some(i32::MIN)).abs().unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::Internal(ref msg) if msg.contains("Overflow")),
+            "{}",
+            err
+        );
+        assert_eq!(
+            ScalarValue::UInt32(Some(1)).abs().unwrap(),
+            ScalarValue::UInt32(Some(1))
+        );
+        assert!(ScalarValue::Utf8(Some("-1".to_string())).abs().is_err());
+    }
+
+    #[test]
+    fn scalar_mul_div() {
+        assert_eq!(
+            ScalarValue::Int32(Some(7))
+                .div(&ScalarValue::Int32(Some(2)))
+                .unwrap(),
+            ScalarValue::Int32(Some(3))
+        );
+        assert_eq!(
+            ScalarValue::Int64(None)
+                .div(&ScalarValue::Int64(Some(0)))
+                .unwrap(),
+            ScalarValue::Int64(None)
+        );
+
+        // integer division by zero is an error
+        let err = ScalarValue::Int32(Some(1))
+            .div(&ScalarValue::Int32(Some(0)))
+            .unwrap_err();
+        assert!(err.to_string().contains("Divide by zero"), "{}", err);
+        assert!(ScalarValue::UInt8(Some(1))
+            .div(&ScalarValue::UInt8(Some(0)))
+            .is_err());
+        assert!(ScalarValue::Int8(Some(i8::MIN))
+            .div(&ScalarValue::Int8(Some(-1)))
+            .is_err());
+
+        // float division by zero follows IEEE semantics
+        assert_eq!(
+            ScalarValue::Float64(Some(1.0))
+                .div(&ScalarValue::Float64(Some(0.0)))
+                .unwrap(),
+            ScalarValue::Float64(Some(f64::INFINITY))
+        );
+        match ScalarValue::Float32(Some(0.0))
+            .div(&ScalarValue::Float32(Some(0.0)))
+            .unwrap()
+        {
+            ScalarValue::Float32(Some(v)) => assert!(v.is_nan()),
+            other => panic!("expected Float32, got {:?}", other),
+        }
+
+        // decimal multiplication adds the scales
+        assert_eq!(
+            ScalarValue::Decimal128(Some(150), 5, 2)
+                .mul(&ScalarValue::Decimal128(Some(25), 4, 1))
+                .unwrap(),
+            ScalarValue::Decimal128(Some(3750), 10, 3)
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(None, 5, 2)
+                .mul(&ScalarValue::Decimal128(Some(25), 4, 1))
+                .unwrap(),
+            ScalarValue::Decimal128(None, 10, 3)
+        );
+        assert!(ScalarValue::Decimal128(Some(1), 38, 20)
+            .mul(&ScalarValue::Decimal128(Some(1), 38, 20))
+            .is_err());
+        assert!(ScalarValue::Decimal128(Some(10_i128.pow(37)), 38, 0)
+            .mul(&ScalarValue::Decimal128(Some(100), 38, 0))
+            .is_err());
+    }
+
+    #[cfg(feature = "regex_expressions")]
+    #[test]
+    fn scalar_regexp_is_match() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+        let s = utf8("Hello World");
+
+        assert_eq!(
+            s.regexp_is_match(&utf8("^Hello"), None).unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            s.regexp_is_match(&utf8("^world"), None).unwrap(),
+            ScalarValue::Boolean(Some(false))
+        );
+        assert_eq!(
+            s.regexp_is_match(&utf8("WORLD$"), Some("i")).unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            utf8("a\nb")
+                .regexp_is_match(&utf8("^b$"), Some("m"))
+                .unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            utf8("a\nb")
+                .regexp_is_match(&utf8("a.b"), Some("s"))
+                .unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+
+        assert_eq!(
+            ScalarValue::Utf8(None)
+                .regexp_is_match(&utf8("a"), None)
+                .unwrap(),
+            ScalarValue::Boolean(None)
+        );
+        assert_eq!(
+            s.regexp_is_match(&ScalarValue::Utf8(None), None).unwrap(),
+            ScalarValue::Boolean(None)
+        );
+
+        let err = s.regexp_is_match(&utf8("(unclosed"), None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid regular expression '(unclosed'"),
+            "{}",
+            err
+        );
+        assert!(s.regexp_is_match(&utf8("a"), Some("x")).is_err());
+        assert!(ScalarValue::Int32(Some(1))
+            .regexp_is_match(&utf8("1"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_new_list() {
+        let list = ScalarValue::new_list(
+            Some(vec![ScalarValue::Int32(Some(1)), ScalarValue::Int32(None)]),
+            DataType::Int32,
+        );
+        assert_eq!(
+            list,
+            ScalarValue::List(
+                Some(Box::new(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Int32(None)
+                ])),
+                Box::new(DataType::Int32),
+                true
+            )
+        );
+
+        let list = ScalarValue::new_list_from_iter(vec![
+            ScalarValue::Utf8(Some("a".to_string())),
+            ScalarValue::Utf8(None),
+        ])
+        .unwrap();
+        assert_eq!(
+            list.get_datatype(),
+            DataType::List(Box::new(Field::new("item", DataType::Utf8, true)))
+        );
+
+        // empty and null lists need an explicit type
+        let empty = ScalarValue::new_list(Some(vec![]), DataType::Int64);
+        assert_eq!(
+            empty,
+            ScalarValue::List(Some(Box::new(vec![])), Box::new(DataType::Int64), true)
+        );
+        assert!(!empty.is_null());
+        let null = ScalarValue::new_list(None, DataType::Int64);
+        assert!(null.is_null());
+        assert!(ScalarValue::new_list_from_iter(vec![]).is_err());
+
+        let err = ScalarValue::new_list_from_iter(vec![
+            ScalarValue::Int32(Some(1)),
+            ScalarValue::Int64(Some(2)),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("Inconsistent types"), "{}", err);
+    }
+
+    #[test]
+    fn scalar_size() {
+        let shallow = std::mem::size_of::<ScalarValue>();
+        assert_eq!(ScalarValue::Int32(Some(1)).size(), shallow);
+        assert_eq!(ScalarValue::Utf8(None).size(), shallow);
+
+        let short = ScalarValue::Utf8(Some("a".to_string()));
+        let long = ScalarValue::Utf8(Some("a".repeat(1000)));
+        assert!(long.size() > short.size());
+        assert!(long.size() >= shallow + 1000);
+        assert!(ScalarValue::Binary(Some(vec![0; 100])).size() >= shallow + 100);
+
+        // nested values are counted recursively
+        let inner = ScalarValue::new_list(
+            Some(vec![long.clone(), short.clone()]),
+            DataType::Utf8,
+        );
+        let outer = ScalarValue::new_list(
+            Some(vec![inner.clone(), inner.clone()]),
+            DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+        );
+        assert!(inner.size() > long.size() + short.size());
+        assert!(outer.size() > 2 * inner.size());
+
+        let fields = vec![
+            Field::new("a", DataType::Utf8, true),
+            Field::new("b", DataType::Int32, true),
+        ];
+        let value = ScalarValue::Struct(
+            Some(Box::new(vec![long.clone(), ScalarValue::Int32(Some(1))])),
+            Box::new(fields),
+        );
+        assert!(value.size() > long.size() + 2 * std::mem::size_of::<Field>());
+    }
+
+    #[test]
+    fn scalar_list_of_struct_round_trip() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let item = |a: Option<i32>, b: Option<&str>| {
+            ScalarValue::Struct(
+                Some(Box::new(vec![
+                    ScalarValue::Int32(a),
+                    ScalarValue::Utf8(b.map(|b| b.to_string())),
+                ])),
+                Box::new(fields.clone()),
+            )
+        };
+        let null_item = ScalarValue::Struct(None, Box::new(fields.clone()));
+        let element_type = DataType::Struct(fields.clone());
+
+        let lists = vec![
+            ScalarValue::new_list(
+                Some(vec![
+                    item(Some(1), Some("x")),
+                    null_item.clone(),
+                    item(None, Some("y")),
+                ]),
+                element_type.clone(),
+            ),
+            ScalarValue::new_list(None, element_type.clone()),
+            ScalarValue::new_list(
+                Some(vec![null_item.clone(), item(Some(2), None)]),
+                element_type.clone(),
+            ),
+            ScalarValue::new_list(Some(vec![]), element_type.clone()),
+        ];
+
+        for list in &lists {
+            let array = list.to_array_of_size(4);
+            assert_eq!(array.len(), 4);
+            for i in 0..4 {
+                assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), list);
+            }
+        }
+
+        let array = ScalarValue::iter_to_array(lists.clone()).unwrap();
+        assert_eq!(array.len(), lists.len());
+        assert_eq!(array.null_count(), 1);
+        for (i, list) in lists.iter().enumerate() {
+            assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), list);
+        }
+
+        // null structs outside of lists are kept as well
+        let array =
+            ScalarValue::iter_to_array(vec![item(Some(1), None), null_item.clone()])
+                .unwrap();
+        assert!(array.is_valid(0));
+        assert!(array.is_null(1));
+        assert_eq!(ScalarValue::try_from_array(&array, 1).unwrap(), null_item);
+        let array = null_item.to_array_of_size(2);
+        assert_eq!(array.null_count(), 2);
+        assert_eq!(ScalarValue::try_from_array(&array, 0).unwrap(), null_item);
+    }
+
+    #[test]
+    fn scalar_initcap() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        assert_eq!(
+            utf8("hello wORLD foo").initcap().unwrap(),
+            utf8("Hello World Foo")
+        );
+        // any non-alphanumeric character separates words
+        assert_eq!(
+            utf8("o'neil-smith,jr.a1b 2nd").initcap().unwrap(),
+            utf8("O'Neil-Smith,Jr.A1b 2nd")
+        );
+        // multi-byte first letters
+        assert_eq!(
+            utf8("élan ÇA ñandú").initcap().unwrap(),
+            utf8("Élan Ça Ñandú")
+        );
+        assert_eq!(utf8("").initcap().unwrap(), utf8(""));
+
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("aBC".to_string()))
+                .initcap()
+                .unwrap(),
+            ScalarValue::LargeUtf8(Some("Abc".to_string()))
+        );
+        assert_eq!(
+            ScalarValue::Utf8(None).initcap().unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(ScalarValue::Int32(Some(1)).initcap().is_err());
+    }
+
+    #[test]
+    fn scalar_interval_month_day_nano_list_to_array() {
+        let data_type = DataType::Interval(IntervalUnit::MonthDayNano);
+        let values = vec![
+            // 1 month, 2 days and 3 nanoseconds
+            ScalarValue::IntervalMonthDayNano(Some((1 << 96) | (2 << 64) | 3)),
+            ScalarValue::IntervalMonthDayNano(None),
+            // values using all 128 bits
+            ScalarValue::IntervalMonthDayNano(Some(i128::MIN | i64::MAX as i128)),
+        ];
+        let list =
+            ScalarValue::List(Some(Box::new(values)), Box::new(data_type.clone()), true);
+
+        let array = list.to_array_of_size(2);
+        assert_eq!(array.len(), 2);
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(list_array.value_type(), data_type);
+        for i in 0..2 {
+            assert_eq!(ScalarValue::try_from_array(&array, i).unwrap(), list);
+        }
+
+        let null_list = ScalarValue::List(None, Box::new(data_type), true);
+        let array = null_list.to_array_of_size(2);
+        assert_eq!(array.null_count(), 2);
+        assert_eq!(ScalarValue::try_from_array(&array, 1).unwrap(), null_list);
+    }
+
+    #[cfg(feature = "crypto_expressions")]
+    #[test]
+    fn scalar_digest() {
+        let utf8 = |s: &str| ScalarValue::Utf8(Some(s.to_string()));
+
+        let cases = vec![
+            (DigestAlgorithm::Md5, "", "d41d8cd98f00b204e9800998ecf8427e"),
+            (
+                DigestAlgorithm::Md5,
+                "abc",
+                "900150983cd24fb0d6963f7d28e17f72",
+            ),
+            (
+                DigestAlgorithm::Sha256,
+                "",
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ),
+            (
+                DigestAlgorithm::Sha256,
+                "abc",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                DigestAlgorithm::Sha224,
+                "abc",
+                "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+            ),
+        ];
+        for (algorithm, input, expected) in cases {
+            assert_eq!(utf8(input).digest(algorithm).unwrap(), utf8(expected));
+            assert_eq!(
+                ScalarValue::LargeUtf8(Some(input.to_string()))
+                    .digest(algorithm)
+                    .unwrap(),
+                utf8(expected)
+            );
+            assert_eq!(
+                ScalarValue::Binary(Some(input.as_bytes().to_vec()))
+                    .digest(algorithm)
+                    .unwrap(),
+                utf8(expected)
+            );
+        }
+        assert_eq!(
+            utf8("abc").digest(DigestAlgorithm::Sha384).unwrap(),
+            utf8(
+                "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+                  8086072ba1e7cc2358baeca134c825a7"
+            )
+        );
+        assert_eq!(
+            utf8("abc").digest(DigestAlgorithm::Sha512).unwrap(),
+            utf8(
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                  2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+            )
+        );
+
+        assert_eq!(
+            ScalarValue::Utf8(None)
+                .digest(DigestAlgorithm::Md5)
+                .unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert_eq!(
+            ScalarValue::Binary(None)
+                .digest(DigestAlgorithm::Sha256)
+                .unwrap(),
+            ScalarValue::Utf8(None)
+        );
+        assert!(ScalarValue::Int32(Some(1))
+            .digest(DigestAlgorithm::Md5)
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_boolean_list_to_array_keeps_nulls() {
+        let list = ScalarValue::List(
+            Some(Box::new(vec![
+                ScalarValue::Boolean(Some(true)),
+                ScalarValue::Boolean(None),
+                ScalarValue::Boolean(Some(false)),
+            ])),
+            Box::new(DataType::Boolean),
+            true,
+        );
+
+        let array = list.to_array_of_size(2);
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        for i in 0..2 {
+            let values = list_array.value(i);
+            let values = values.as_any().downcast_ref::<BooleanArray>().unwrap();
+            assert_eq!(values.null_count(), 1);
+            assert!(values.is_null(1));
+
+            match ScalarValue::try_from_array(&array, i).unwrap() {
+                ScalarValue::List(Some(values), _, _) => {
+                    assert_eq!(values[1], ScalarValue::Boolean(None));
+                }
+                other => panic!("expected a list, got {:?}", other),
+            }
+            assert_eq!(ScalarValue::try_from_array(&array, i).unwrap(), list);
+        }
+    }
+
+    #[test]
+    fn scalar_to_array_cached() {
+        let mut cache = ScalarArrayCache::new();
+        let value = ScalarValue::Utf8(Some("group".to_string()));
+
+        let first = value.to_array_cached(3, &mut cache);
+        let second = value.to_array_cached(3, &mut cache);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.len(), 3);
+        assert_eq!(ScalarValue::try_from_array(&first, 2).unwrap(), value);
+        assert_eq!(cache.len(), 1);
+
+        // other sizes and values are materialized separately
+        let other_size = value.to_array_cached(4, &mut cache);
+        assert!(!Arc::ptr_eq(&first, &other_size));
+        assert_eq!(other_size.len(), 4);
+        let other_value =
+            ScalarValue::Utf8(Some("other".to_string())).to_array_cached(3, &mut cache);
+        assert!(!Arc::ptr_eq(&first, &other_value));
+        assert_eq!(cache.len(), 3);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(!Arc::ptr_eq(&first, &value.to_array_cached(3, &mut cache)));
+    }
+
+    #[test]
+    fn scalar_try_from_string() {
+        let parse = |value: &str, data_type: DataType| {
+            ScalarValue::try_from_string(value.to_string(), &data_type)
+        };
+
+        assert_eq!(
+            parse("-42", DataType::Int64).unwrap(),
+            ScalarValue::Int64(Some(-42))
+        );
+        assert_eq!(
+            parse("1.5", DataType::Float64).unwrap(),
+            ScalarValue::Float64(Some(1.5))
+        );
+        assert_eq!(
+            parse("2021-01-01", DataType::Date32).unwrap(),
+            ScalarValue::Date32(Some(18628))
+        );
+        assert_eq!(
+            parse("1970-01-02", DataType::Date64).unwrap(),
+            ScalarValue::Date64(Some(86_400_000))
+        );
+        assert_eq!(
+            parse(
+                "2021-01-01T00:00:01Z",
+                DataType::Timestamp(TimeUnit::Second, None)
+            )
+            .unwrap(),
+            ScalarValue::TimestampSecond(Some(1609459201), None)
+        );
+        // timestamps before the epoch round towards negative infinity
+        let before_epoch = "1969-12-31T23:59:59.9995Z";
+        assert_eq!(
+            parse(before_epoch, DataType::Timestamp(TimeUnit::Second, None)).unwrap(),
+            ScalarValue::TimestampSecond(Some(-1), None)
+        );
+        assert_eq!(
+            parse(
+                before_epoch,
+                DataType::Timestamp(TimeUnit::Millisecond, None)
+            )
+            .unwrap(),
+            ScalarValue::TimestampMillisecond(Some(-1), None)
+        );
+        assert_eq!(
+            parse(
+                before_epoch,
+                DataType::Timestamp(TimeUnit::Microsecond, None)
+            )
+            .unwrap(),
+            ScalarValue::TimestampMicrosecond(Some(-500), None)
+        );
+        assert_eq!(
+            parse("true", DataType::Boolean).unwrap(),
+            ScalarValue::Boolean(Some(true))
+        );
+        assert_eq!(
+            parse("FALSE", DataType::Boolean).unwrap(),
+            ScalarValue::Boolean(Some(false))
+        );
+        assert_eq!(
+            parse("abc", DataType::Utf8).unwrap(),
+            ScalarValue::Utf8(Some("abc".to_string()))
+        );
+
+        let err = parse("12x", DataType::Int64).unwrap_err();
+        assert!(
+            err.to_string().starts_with(
+                "Error during planning: Cannot parse '12x' as Int64: invalid digit"
+            ),
+            "{}",
+            err
+        );
+        assert!(parse("300", DataType::UInt8).is_err());
+        assert!(parse("yes", DataType::Boolean).is_err());
+        assert!(parse("2021-13-01", DataType::Date32).is_err());
+    }
+
+    #[test]
+    fn scalar_eq_array_list_and_struct() {
+        let list = |values: Option<Vec<Option<i32>>>| {
+            ScalarValue::List(
+                values.map(|v| Box::new(v.into_iter().map(ScalarValue::Int32).collect())),
+                Box::new(DataType::Int32),
+                true,
+            )
+        };
+        let array = ScalarValue::iter_to_array(vec![
+            list(Some(vec![Some(1), None, Some(3)])),
+            list(None),
+            list(Some(vec![Some(1), None])),
+            list(Some(vec![])),
+        ])
+        .unwrap();
+
+        let scalar = list(Some(vec![Some(1), None, Some(3)]));
+        assert!(scalar.eq_array(&array, 0));
+        assert!(!scalar.eq_array(&array, 1));
+        assert!(!scalar.eq_array(&array, 2));
+        assert!(!scalar.eq_array(&array, 3));
+        assert!(!list(Some(vec![Some(1), Some(2), Some(3)])).eq_array(&array, 0));
+        assert!(list(None).eq_array(&array, 1));
+        assert!(!list(None).eq_array(&array, 0));
+        assert!(list(Some(vec![])).eq_array(&array, 3));
+
+        let fields = Box::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let row = |a: Option<i32>, b: Option<&str>| {
+            ScalarValue::Struct(
+                Some(Box::new(vec![
+                    ScalarValue::Int32(a),
+                    ScalarValue::Utf8(b.map(|b| b.to_string())),
+                ])),
+                fields.clone(),
+            )
+        };
+        let array = ScalarValue::iter_to_array(vec![
+            row(Some(1), Some("x")),
+            row(Some(2), None),
+            ScalarValue::Struct(None, fields.clone()),
+        ])
+        .unwrap();
+
+        assert!(row(Some(1), Some("x")).eq_array(&array, 0));
+        assert!(!row(Some(1), Some("y")).eq_array(&array, 0));
+        assert!(!row(Some(1), Some("x")).eq_array(&array, 1));
+        assert!(row(Some(2), None).eq_array(&array, 1));
+        assert!(!row(Some(2), None).eq_array(&array, 2));
+        assert!(ScalarValue::Struct(None, fields.clone()).eq_array(&array, 2));
+        assert!(!ScalarValue::Struct(None, fields).eq_array(&array, 0));
+    }
+
+    #[test]
+    fn scalar_fixed_size_list_round_trip() {
+        let mut builder = FixedSizeListBuilder::new(Int32Builder::new(6), 2);
+        builder.values().append_value(1).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value(0).unwrap();
+        builder.values().append_value(0).unwrap();
+        builder.append(false).unwrap();
+        let array: ArrayRef = Arc::new(builder.finish());
+
+        let scalar = ScalarValue::try_from_array(&array, 0).unwrap();
+        assert_eq!(
+            scalar,
+            ScalarValue::FixedSizeList(
+                Some(Box::new(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Int32(None)
+                ])),
+                Box::new(DataType::Int32),
+                2,
+                true
+            )
+        );
+        assert_eq!(scalar.get_datatype(), *array.data_type());
+
+        let expanded = scalar.to_array_of_size(3);
+        assert_eq!(expanded.data_type(), array.data_type());
+        assert_eq!(expanded.len(), 3);
+        for i in 0..3 {
+            assert!(scalar.eq_array(&expanded, i));
+        }
+
+        let null = ScalarValue::try_from_array(&array, 1).unwrap();
+        assert!(null.is_null());
+        assert_eq!(null.to_array_of_size(2).data_type(), array.data_type());
+        assert!(null.eq_array(&array, 1));
+
+        let built = ScalarValue::iter_to_array(vec![scalar.clone(), null]).unwrap();
+        assert_eq!(built.data_type(), array.data_type());
+        assert!(scalar.eq_array(&built, 0));
+        assert!(built.is_null(1));
+
+        let wrong_size = ScalarValue::FixedSizeList(
+            Some(Box::new(vec![ScalarValue::Int32(Some(1))])),
+            Box::new(DataType::Int32),
+            2,
+            true,
+        );
+        assert!(wrong_size.try_to_array_of_size(1).is_err());
+        assert!(ScalarValue::iter_to_array(vec![scalar, wrong_size]).is_err());
+
+        let non_nullable = DataType::FixedSizeList(
+            Box::new(Field::new("item", DataType::Int32, false)),
+            2,
+        );
+        let scalar = ScalarValue::try_from(&non_nullable).unwrap();
+        assert_eq!(scalar.get_datatype(), non_nullable);
+        let scalar = ScalarValue::FixedSizeList(
+            Some(Box::new(vec![
+                ScalarValue::Int32(Some(1)),
+                ScalarValue::Int32(Some(2)),
+            ])),
+            Box::new(DataType::Int32),
+            2,
+            false,
+        );
+        assert_eq!(scalar.to_array_of_size(2).data_type(), &non_nullable);
+    }
+
+    #[test]
+    fn scalar_date_and_decimal_list_to_array() {
+        let scalar = ScalarValue::new_list(
+            Some(vec![
+                ScalarValue::Date32(Some(19000)),
+                ScalarValue::Date32(None),
+            ]),
+            DataType::Date32,
+        );
+        let array = scalar.to_array_of_size(3);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.data_type(), &scalar.get_datatype());
+        for i in 0..3 {
+            assert!(scalar.eq_array(&array, i));
+        }
+
+        let scalar = ScalarValue::new_list(
+            Some(vec![ScalarValue::Date64(Some(86_400_000))]),
+            DataType::Date64,
+        );
+        let array = scalar.to_array_of_size(3);
+        assert_eq!(array.len(), 3);
+        assert!(scalar.eq_array(&array, 2));
+
+        let scalar = ScalarValue::new_list(
+            Some(vec![
+                ScalarValue::Decimal128(Some(123), 10, 2),
+                ScalarValue::Decimal128(None, 10, 2),
+            ]),
+            DataType::Decimal(10, 2),
+        );
+        let array = scalar.to_array_of_size(3);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.data_type(), &scalar.get_datatype());
+        assert_eq!(ScalarValue::try_from_array(&array, 1).unwrap(), scalar);
+
+        let null = ScalarValue::new_list(None, DataType::Date32);
+        let array = null.to_array_of_size(3);
+        assert_eq!(array.null_count(), 3);
+    }
+
+    #[test]
+    fn scalar_dictionary_round_trip() {
+        let array: DictionaryArray<Int8Type> =
+            vec![Some("a"), None, Some("b")].into_iter().collect();
+        let array: ArrayRef = Arc::new(array);
+
+        let scalar = ScalarValue::try_from_array_preserve_dictionary(&array, 2).unwrap();
+        assert_eq!(
+            scalar,
+            ScalarValue::Dictionary(
+                Box::new(DataType::Int8),
+                Box::new(ScalarValue::Utf8(Some("b".to_string())))
+            )
+        );
+        assert_eq!(scalar.get_datatype(), *array.data_type());
+        assert_eq!(format!("{}", scalar), "b");
+        assert!(scalar.eq_array(&array, 2));
+        assert!(!scalar.eq_array(&array, 0));
+        // plain conversion still unwraps the dictionary
+        assert_eq!(
+            ScalarValue::try_from_array(&array, 2).unwrap(),
+            ScalarValue::Utf8(Some("b".to_string()))
+        );
+
+        let expanded = scalar.to_array_of_size(3);
+        assert_eq!(expanded.data_type(), array.data_type());
+        assert_eq!(expanded.len(), 3);
+        for i in 0..3 {
+            assert_eq!(
+                ScalarValue::try_from_array_preserve_dictionary(&expanded, i).unwrap(),
+                scalar
+            );
+        }
+
+        let null = ScalarValue::try_from_array_preserve_dictionary(&array, 1).unwrap();
+        assert!(null.is_null());
+        let expanded = null.to_array_of_size(2);
+        assert_eq!(expanded.data_type(), array.data_type());
+        assert_eq!(expanded.null_count(), 2);
+
+        let invalid = ScalarValue::Dictionary(
+            Box::new(DataType::Utf8),
+            Box::new(ScalarValue::Utf8(Some("b".to_string()))),
+        );
+        match invalid.try_to_array_of_size(1) {
+            Err(DataFusionError::Internal(msg)) => {
+                assert_eq!(msg, "Invalid dictionary keys type: Utf8")
+            }
+            other => panic!("expected an internal error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn scalar_try_as_str() {
+        assert_eq!(
+            ScalarValue::Utf8(Some("foo".to_string())).try_as_str(),
+            Some("foo")
+        );
+        assert_eq!(ScalarValue::Utf8(None).try_as_str(), None);
+        assert_eq!(
+            ScalarValue::LargeUtf8(Some("bar".to_string())).try_as_str(),
+            Some("bar")
+        );
+        assert_eq!(ScalarValue::Int32(Some(1)).try_as_str(), None);
+    }
+
+    #[test]
+    fn scalar_iter_to_array_date_lists() {
+        let scalars = vec![
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Date32(Some(1)),
+                    ScalarValue::Date32(None),
+                ]),
+                DataType::Date32,
+            ),
+            ScalarValue::new_list(None, DataType::Date32),
+            ScalarValue::new_list(Some(vec![]), DataType::Date32),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Date32(Some(3))]),
+                DataType::Date32,
+            ),
+        ];
+
+        let array = ScalarValue::iter_to_array(scalars.clone()).unwrap();
+        let list_array = array.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(list_array.len(), 4);
+        assert_eq!(list_array.value_type(), DataType::Date32);
+        assert!(list_array.is_null(1));
+        for (i, scalar) in scalars.iter().enumerate() {
+            assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), scalar);
+        }
+
+        let scalars = vec![
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Date64(None)]),
+                DataType::Date64,
+            ),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Date64(Some(86_400_000))]),
+                DataType::Date64,
+            ),
+        ];
+        let array = ScalarValue::iter_to_array(scalars.clone()).unwrap();
+        for (i, scalar) in scalars.iter().enumerate() {
+            assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), scalar);
+        }
+
+        let scalars = vec![
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Decimal128(Some(123), 10, 2),
+                    ScalarValue::Decimal128(None, 10, 2),
+                ]),
+                DataType::Decimal(10, 2),
+            ),
+            ScalarValue::new_list(None, DataType::Decimal(10, 2)),
+        ];
+        let array = ScalarValue::iter_to_array(scalars.clone()).unwrap();
+        assert_eq!(array.data_type(), &scalars[0].get_datatype());
+        for (i, scalar) in scalars.iter().enumerate() {
+            assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), scalar);
+        }
+    }
+
+    #[test]
+    fn scalar_to_sql_string() {
+        assert_eq!(
+            ScalarValue::Utf8(Some("it's".to_string()))
+                .to_sql_string()
+                .unwrap(),
+            "'it''s'"
+        );
+        assert_eq!(ScalarValue::Utf8(None).to_sql_string().unwrap(), "NULL");
+        assert_eq!(ScalarValue::Int64(None).to_sql_string().unwrap(), "NULL");
+        assert_eq!(
+            ScalarValue::Date32(Some(18_628)).to_sql_string().unwrap(),
+            "DATE '2021-01-01'"
+        );
+        assert_eq!(
+            ScalarValue::TimestampSecond(Some(1_609_459_200), None)
+                .to_sql_string()
+                .unwrap(),
+            "TIMESTAMP '2021-01-01T00:00:00'"
+        );
+        assert_eq!(
+            ScalarValue::Boolean(Some(true)).to_sql_string().unwrap(),
+            "TRUE"
+        );
+        assert_eq!(
+            ScalarValue::Boolean(Some(false)).to_sql_string().unwrap(),
+            "FALSE"
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(-1234), 10, 2)
+                .to_sql_string()
+                .unwrap(),
+            "-12.34"
+        );
+        assert_eq!(
+            ScalarValue::Binary(Some(vec![0xde, 0xad]))
+                .to_sql_string()
+                .unwrap(),
+            "X'DEAD'"
+        );
+
+        let fields = vec![Field::new("a", DataType::Int32, true)];
+        let err = ScalarValue::Struct(
+            Some(Box::new(vec![ScalarValue::Int32(Some(1))])),
+            Box::new(fields),
+        )
+        .to_sql_string()
+        .unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)));
+
+        let err = ScalarValue::TimestampSecond(Some(0), Some("Europe/Paris".to_string()))
+            .to_sql_string()
+            .unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)));
+    }
+
+    #[test]
+    fn scalar_try_cmp() {
+        assert_eq!(
+            ScalarValue::Int32(Some(2))
+                .try_cmp(&ScalarValue::Int64(Some(10)))
+                .unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(10))
+                .try_cmp(&ScalarValue::Int32(Some(10)))
+                .unwrap(),
+            Ordering::Equal
+        );
+        assert_eq!(
+            ScalarValue::Float32(Some(1.5))
+                .try_cmp(&ScalarValue::Float64(Some(1.25)))
+                .unwrap(),
+            Ordering::Greater
+        );
+        // 1.50 vs 1.5000 and 1.50 vs 1.5001
+        assert_eq!(
+            ScalarValue::Decimal128(Some(150), 10, 2)
+                .try_cmp(&ScalarValue::Decimal128(Some(15000), 10, 4))
+                .unwrap(),
+            Ordering::Equal
+        );
+        assert_eq!(
+            ScalarValue::Decimal128(Some(150), 10, 2)
+                .try_cmp(&ScalarValue::Decimal128(Some(15001), 10, 4))
+                .unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(
+            ScalarValue::Int32(None)
+                .try_cmp(&ScalarValue::Int64(Some(0)))
+                .unwrap(),
+            Ordering::Less
+        );
+        assert!(ScalarValue::Int32(Some(1))
+            .try_cmp(&ScalarValue::Utf8(Some("1".to_string())))
+            .is_err());
+
+        // integers are compared exactly at the i64/u64 boundary
+        let cases = vec![
+            (
+                ScalarValue::Int64(Some(i64::MAX)),
+                ScalarValue::UInt64(Some(1 << 63)),
+                Ordering::Less,
+            ),
+            (
+                ScalarValue::UInt64(Some(u64::MAX)),
+                ScalarValue::Int64(Some(i64::MIN)),
+                Ordering::Greater,
+            ),
+            (
+                ScalarValue::Int64(Some(i64::MAX)),
+                ScalarValue::Decimal128(Some(i64::MAX as i128 * 100 + 1), 38, 2),
+                Ordering::Less,
+            ),
+            (
+                ScalarValue::Decimal128(Some(i64::MAX as i128 * 100), 38, 2),
+                ScalarValue::Int64(Some(i64::MAX)),
+                Ordering::Equal,
+            ),
+            (
+                ScalarValue::Int64(Some(-1)),
+                ScalarValue::Decimal128(Some(-5), 38, 38),
+                Ordering::Less,
+            ),
+            // 2^63 - 1 rounds to 2^63 as a float
+            (
+                ScalarValue::Int64(Some(i64::MAX)),
+                ScalarValue::Float32(Some(9.223372e18)),
+                Ordering::Less,
+            ),
+            (
+                ScalarValue::Float64(Some(9.223372036854775807e18)),
+                ScalarValue::Int64(Some(i64::MAX)),
+                Ordering::Greater,
+            ),
+            (
+                ScalarValue::UInt64(Some(u64::MAX)),
+                ScalarValue::Float64(Some(1.8446744073709552e19)),
+                Ordering::Less,
+            ),
+            (
+                ScalarValue::Int32(Some(-2)),
+                ScalarValue::Float64(Some(-1.5)),
+                Ordering::Less,
+            ),
+            (
+                ScalarValue::Int32(Some(3)),
+                ScalarValue::Float32(Some(3.0)),
+                Ordering::Equal,
+            ),
+            (
+                ScalarValue::UInt64(None),
+                ScalarValue::Float64(Some(0.0)),
+                Ordering::Less,
+            ),
+        ];
+        for (left, right, expected) in cases {
+            assert_eq!(
+                left.try_cmp(&right).unwrap(),
+                expected,
+                "{:?} vs {:?}",
+                left,
+                right
+            );
+        }
+        assert!(ScalarValue::Int64(Some(1))
+            .try_cmp(&ScalarValue::Float64(Some(f64::NAN)))
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_numeric_series() {
+        assert_eq!(
+            ScalarValue::numeric_series(
+                ScalarValue::Int32(Some(1)),
+                ScalarValue::Int32(Some(7)),
+                ScalarValue::Int32(Some(2)),
+            )
+            .unwrap(),
+            vec![
+                ScalarValue::Int32(Some(1)),
+                ScalarValue::Int32(Some(3)),
+                ScalarValue::Int32(Some(5)),
+            ]
+        );
+        assert_eq!(
+            ScalarValue::numeric_series(
+                ScalarValue::Int64(Some(3)),
+                ScalarValue::Int64(Some(-1)),
+                ScalarValue::Int64(Some(-2)),
+            )
+            .unwrap(),
+            vec![ScalarValue::Int64(Some(3)), ScalarValue::Int64(Some(1))]
+        );
+        assert_eq!(
+            ScalarValue::numeric_series(
+                ScalarValue::Float64(Some(0.0)),
+                ScalarValue::Float64(Some(1.0)),
+                ScalarValue::Float64(Some(0.25)),
+            )
+            .unwrap(),
+            vec![
+                ScalarValue::Float64(Some(0.0)),
+                ScalarValue::Float64(Some(0.25)),
+                ScalarValue::Float64(Some(0.5)),
+                ScalarValue::Float64(Some(0.75)),
+            ]
+        );
+        // an empty series when stop is on the wrong side of start
+        assert!(ScalarValue::numeric_series(
+            ScalarValue::UInt8(Some(5)),
+            ScalarValue::UInt8(Some(1)),
+            ScalarValue::UInt8(Some(1)),
+        )
+        .unwrap()
+        .is_empty());
+
+        let err = ScalarValue::numeric_series(
+            ScalarValue::Int32(Some(1)),
+            ScalarValue::Int32(Some(7)),
+            ScalarValue::Int32(Some(0)),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("step cannot be zero"), "{}", err);
+
+        // the length of the series is bounded
+        assert_eq!(
+            ScalarValue::numeric_series(
+                ScalarValue::Int64(Some(0)),
+                ScalarValue::Int64(Some(MAX_NUMERIC_SERIES_LEN as i64)),
+                ScalarValue::Int64(Some(1)),
+            )
+            .unwrap()
+            .len(),
+            MAX_NUMERIC_SERIES_LEN
+        );
+        let err = ScalarValue::numeric_series(
+            ScalarValue::Int64(Some(i64::MIN)),
+            ScalarValue::Int64(Some(i64::MAX)),
+            ScalarValue::Int64(Some(1)),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("more than the maximum"), "{}", err);
+        let err = ScalarValue::numeric_series(
+            ScalarValue::Float64(Some(1.0)),
+            ScalarValue::Float64(Some(0.0)),
+            ScalarValue::Float64(Some(-1e-9)),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("more than the maximum"), "{}", err);
+
+        let err = ScalarValue::numeric_series(
+            ScalarValue::Int32(Some(1)),
+            ScalarValue::Int64(Some(7)),
+            ScalarValue::Int32(Some(1)),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requires arguments of the same type"),
+            "{}",
+            err
+        );
+
+        assert!(ScalarValue::numeric_series(
+            ScalarValue::Utf8(Some("a".to_string())),
+            ScalarValue::Utf8(Some("z".to_string())),
+            ScalarValue::Utf8(Some("b".to_string())),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn scalar_checked_add() {
+        assert_eq!(
+            ScalarValue::Int64(Some(i64::MAX)).checked_add(&ScalarValue::Int64(Some(1))),
+            None
+        );
+        assert_eq!(
+            ScalarValue::Int64(Some(1)).checked_add(&ScalarValue::Int64(Some(2))),
+            Some(ScalarValue::Int64(Some(3)))
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(1.5)).checked_add(&ScalarValue::Float64(Some(2.0))),
+            Some(ScalarValue::Float64(Some(3.5)))
+        );
+        assert_eq!(
+            ScalarValue::Int32(None).checked_add(&ScalarValue::Int32(Some(2))),
+            Some(ScalarValue::Int32(None))
+        );
+        assert_eq!(
+            ScalarValue::Int32(Some(1)).checked_add(&ScalarValue::Int64(Some(2))),
+            None
+        );
+    }
+
+    #[test]
+    fn scalar_list_element_nullability() {
+        let list_type =
+            DataType::List(Box::new(Field::new("item", DataType::Int32, false)));
+        let values = Int32Array::from_slice(&[1, 2, 3]);
+        let data = ArrayData::builder(list_type.clone())
+            .len(2)
+            .add_buffer(arrow::buffer::Buffer::from_slice_ref(&[0i32, 2, 3]))
+            .add_child_data(values.data().clone())
+            .build()
+            .unwrap();
+        let array: ArrayRef = Arc::new(ListArray::from(data));
+
+        // the element nullability of the source field is preserved
+        let scalar = ScalarValue::try_from_array(&array, 0).unwrap();
+        assert_eq!(scalar.get_datatype(), list_type);
+        let round_trip = scalar.to_array_of_size(2);
+        assert_eq!(round_trip.data_type(), &list_type);
+        assert_eq!(ScalarValue::try_from_array(&round_trip, 1).unwrap(), scalar);
+
+        let array = ScalarValue::iter_to_array(vec![scalar.clone(), scalar]).unwrap();
+        assert_eq!(array.data_type(), &list_type);
+
+        // lists built from values default to nullable elements
+        let list = ScalarValue::new_list(
+            Some(vec![ScalarValue::Int32(Some(1)), ScalarValue::Int32(None)]),
+            DataType::Int32,
+        );
+        assert_eq!(
+            list.get_datatype(),
+            DataType::List(Box::new(Field::new("item", DataType::Int32, true)))
+        );
+        let null_list = ScalarValue::try_from(&DataType::List(Box::new(Field::new(
+            "item",
+            DataType::Int32,
+            true,
+        ))))
+        .unwrap();
+        assert_eq!(
+            null_list,
+            ScalarValue::List(None, Box::new(DataType::Int32), true)
+        );
+    }
+
+    #[test]
+    fn scalar_list_nullability_not_compared() {
+        let values = vec![ScalarValue::Int32(Some(1)), ScalarValue::Int32(Some(2))];
+        let nullable = ScalarValue::new_list(Some(values.clone()), DataType::Int32);
+        let non_nullable =
+            ScalarValue::List(Some(Box::new(values)), Box::new(DataType::Int32), false);
+
+        assert_eq!(nullable, non_nullable);
+        assert_eq!(nullable.partial_cmp(&non_nullable), Some(Ordering::Equal));
+
+        let mut set = HashSet::new();
+        set.insert(nullable);
+        assert!(set.contains(&non_nullable));
+    }
+
+    #[test]
+    fn scalar_as_f64() {
+        assert_eq!(ScalarValue::Int64(Some(-42)).as_f64(), Some(-42.0));
+        assert_eq!(ScalarValue::Float32(Some(1.5)).as_f64(), Some(1.5));
+        assert_eq!(
+            ScalarValue::Decimal128(Some(12345), 10, 2).as_f64(),
+            Some(123.45)
+        );
+        assert_eq!(ScalarValue::Int64(None).as_f64(), None);
+        assert_eq!(ScalarValue::Utf8(Some("1".to_string())).as_f64(), None);
+    }
+
+    #[test]
+    fn scalar_map_round_trip() {
+        let entry_fields = vec![
+            Field::new("keys", DataType::Utf8, false),
+            Field::new("values", DataType::Int32, true),
+        ];
+        let entries = StructArray::from(vec![
+            (
+                entry_fields[0].clone(),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+            (
+                entry_fields[1].clone(),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+        ]);
+        let entries_field = Box::new(Field::new(
+            "entries",
+            DataType::Struct(entry_fields.clone()),
+            false,
+        ));
+        let map_type = DataType::Map(entries_field.clone(), true);
+        let data = ArrayData::builder(map_type.clone())
+            .len(2)
+            .add_buffer(arrow::buffer::Buffer::from_slice_ref(&[0i32, 2, 3]))
+            .add_child_data(entries.data().clone())
+            .build()
+            .unwrap();
+        let array: ArrayRef = Arc::new(MapArray::from(data));
+
+        let scalar = ScalarValue::try_from_array(&array, 0).unwrap();
+        let entry = |k: &str, v: Option<i32>| {
+            ScalarValue::Struct(
+                Some(Box::new(vec![
+                    ScalarValue::Utf8(Some(k.to_string())),
+                    ScalarValue::Int32(v),
+                ])),
+                Box::new(entry_fields.clone()),
+            )
+        };
+        assert_eq!(
+            scalar,
+            ScalarValue::Map(
+                Some(Box::new(vec![entry("a", Some(1)), entry("b", None)])),
+                entries_field.clone(),
+                true
+            )
+        );
+        assert_eq!(scalar.get_datatype(), map_type);
+
+        let round_trip = scalar.to_array_of_size(1);
+        assert_eq!(round_trip.data_type(), &map_type);
+        assert_eq!(round_trip.len(), 1);
+        assert!(round_trip.as_any().downcast_ref::<MapArray>().is_some());
+        assert!(scalar.eq_array(&round_trip, 0));
+        assert_eq!(ScalarValue::try_from_array(&round_trip, 0).unwrap(), scalar);
+
+        let null = ScalarValue::try_from(&map_type).unwrap();
+        assert!(null.is_null());
+        let nulls = null.to_array_of_size(2);
+        assert_eq!(nulls.data_type(), &map_type);
+        assert_eq!(nulls.null_count(), 2);
+
+        // the offsets of the repeated entries must fit in an i32
+        let err = scalar.try_to_array_of_size(usize::MAX / 2).unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::Execution(_)),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn scalar_hash_value() {
+        let scalars = vec![
+            ScalarValue::Int64(Some(42)),
+            ScalarValue::Float64(Some(1.5)),
+            ScalarValue::Utf8(Some("foo".to_string())),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Int32(Some(1))]),
+                DataType::Int32,
+            ),
+        ];
+        for scalar in scalars {
+            assert_eq!(scalar.hash_value(7), scalar.clone().hash_value(7));
+            assert_ne!(scalar.hash_value(7), scalar.hash_value(8));
+        }
+
+        assert_ne!(
+            ScalarValue::Int64(Some(1)).hash_value(0),
+            ScalarValue::Int64(Some(2)).hash_value(0)
+        );
+
+        // values of different types with the same bits hash differently
+        assert_ne!(
+            ScalarValue::Int64(Some(1)).hash_value(0),
+            ScalarValue::Date64(Some(1)).hash_value(0)
+        );
+        assert_eq!(
+            ScalarValue::Float64(Some(0.0)).hash_value(0),
+            ScalarValue::Float64(Some(-0.0)).hash_value(0)
+        );
+
+        // the hash does not depend on the platform or the Rust release
+        assert_eq!(
+            ScalarValue::Int64(Some(42)).hash_value(0),
+            3901461057749958857
+        );
+        assert_eq!(
+            ScalarValue::Utf8(Some("foo".to_string())).hash_value(0),
+            7849491029728521899
+        );
+    }
+
+    #[test]
+    fn scalar_negate_intervals() {
+        assert_eq!(
+            ScalarValue::IntervalYearMonth(Some(14)).arithmetic_negate(),
+            ScalarValue::IntervalYearMonth(Some(-14))
+        );
+
+        // 3 days and -500 milliseconds
+        let day_time = (3i64 << 32) | (-500i32 as u32 as i64);
+        match ScalarValue::IntervalDayTime(Some(day_time)).arithmetic_negate() {
+            ScalarValue::IntervalDayTime(Some(v)) => {
+                assert_eq!((v >> 32) as i32, -3);
+                assert_eq!(v as i32, 500);
+            }
+            other => panic!("expected an IntervalDayTime, got {:?}", other),
+        }
+
+        // 1 month, -2 days and 3 nanoseconds
+        let month_day_nano =
+            (1i128 << 96) | ((-2i32 as u32 as i128) << 64) | (3i64 as u64 as i128);
+        match ScalarValue::IntervalMonthDayNano(Some(month_day_nano)).arithmetic_negate()
+        {
+            ScalarValue::IntervalMonthDayNano(Some(v)) => {
+                assert_eq!((v >> 96) as i32, -1);
+                assert_eq!((v >> 64) as i32, 2);
+                assert_eq!(v as i64, -3);
+            }
+            other => panic!("expected an IntervalMonthDayNano, got {:?}", other),
+        }
+
+        assert_eq!(
+            ScalarValue::IntervalDayTime(None).arithmetic_negate(),
+            ScalarValue::IntervalDayTime(None)
+        );
+    }
+
+    #[test]
+    fn scalar_try_from_array_all() {
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(-3)])),
+            Arc::new(StringArray::from(vec![Some("foo"), None, Some("")])),
+            // falls back to converting each row
+            Arc::new(
+                vec![Some(1), None]
+                    .into_iter()
+                    .collect::<TimestampSecondArray>(),
+            ),
+        ];
+        for array in arrays {
+            let expected = (0..array.len())
+                .map(|i| ScalarValue::try_from_array(&array, i).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(ScalarValue::try_from_array_all(&array).unwrap(), expected);
+        }
+
+        let empty: ArrayRef = Arc::new(Int32Array::from(Vec::<i32>::new()));
+        assert!(ScalarValue::try_from_array_all(&empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn scalar_new_zero_and_one() {
+        assert_eq!(
+            ScalarValue::new_zero(&DataType::Int32).unwrap(),
+            ScalarValue::Int32(Some(0))
+        );
+        assert_eq!(
+            ScalarValue::new_one(&DataType::Int32).unwrap(),
+            ScalarValue::Int32(Some(1))
+        );
+        assert_eq!(
+            ScalarValue::new_zero(&DataType::Float64).unwrap(),
+            ScalarValue::Float64(Some(0.0))
+        );
+        assert_eq!(
+            ScalarValue::new_one(&DataType::Float64).unwrap(),
+            ScalarValue::Float64(Some(1.0))
+        );
+        assert_eq!(
+            ScalarValue::new_zero(&DataType::Decimal(10, 2)).unwrap(),
+            ScalarValue::Decimal128(Some(0), 10, 2)
+        );
+        assert_eq!(
+            ScalarValue::new_one(&DataType::Decimal(10, 2)).unwrap(),
+            ScalarValue::Decimal128(Some(100), 10, 2)
+        );
+
+        // one has no integer digit to fit in
+        assert!(ScalarValue::new_one(&DataType::Decimal(2, 2)).is_err());
+        assert!(ScalarValue::new_zero(&DataType::Utf8).is_err());
+    }
+
+    #[test]
+    fn scalar_try_cmp_list() {
+        let int32_list = |values: Vec<i32>| {
+            ScalarValue::new_list(
+                Some(
+                    values
+                        .into_iter()
+                        .map(|v| ScalarValue::Int32(Some(v)))
+                        .collect(),
+                ),
+                DataType::Int32,
+            )
+        };
+        let int64_list = |values: Vec<i64>| {
+            ScalarValue::new_list(
+                Some(
+                    values
+                        .into_iter()
+                        .map(|v| ScalarValue::Int64(Some(v)))
+                        .collect(),
+                ),
+                DataType::Int64,
+            )
+        };
+
+        let cmp = |l: &ScalarValue, r: &ScalarValue| l.try_cmp_list(r).unwrap();
+        assert_eq!(
+            cmp(&int32_list(vec![1, 2, 3]), &int64_list(vec![1, 5])),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp(&int32_list(vec![2]), &int64_list(vec![1, 5])),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp(&int32_list(vec![1, 2]), &int64_list(vec![1, 2])),
+            Ordering::Equal
+        );
+        // a prefix orders first
+        assert_eq!(
+            cmp(&int32_list(vec![1, 2]), &int64_list(vec![1, 2, 0])),
+            Ordering::Less
+        );
+        assert_eq!(
+            cmp(&int64_list(vec![]), &int32_list(vec![])),
+            Ordering::Equal
+        );
+
+        let null_list = ScalarValue::new_list(None, DataType::Int64);
+        assert_eq!(cmp(&null_list, &int32_list(vec![])), Ordering::Less);
+        assert_eq!(cmp(&int32_list(vec![]), &null_list), Ordering::Greater);
+
+        let utf8_list = ScalarValue::new_list(Some(vec![]), DataType::Utf8);
+        assert!(int32_list(vec![]).try_cmp_list(&utf8_list).is_err());
+        assert!(ScalarValue::Int32(Some(1))
+            .try_cmp_list(&int32_list(vec![1]))
+            .is_err());
+    }
+
+    #[test]
+    fn scalar_rem() {
+        let rem = |l: i32, r: i32| {
+            ScalarValue::Int32(Some(l))
+                .rem(&ScalarValue::Int32(Some(r)))
+                .unwrap()
+        };
+        assert_eq!(rem(7, 3), ScalarValue::Int32(Some(1)));
+        // the remainder has the sign of the dividend
+        assert_eq!(rem(-7, 3), ScalarValue::Int32(Some(-1)));
+        assert_eq!(rem(7, -3), ScalarValue::Int32(Some(1)));
+        assert_eq!(
+            ScalarValue::Float64(Some(5.5))
+                .rem(&ScalarValue::Float64(Some(2.0)))
+                .unwrap(),
+            ScalarValue::Float64(Some(1.5))
+        );
+        assert_eq!(
+            ScalarValue::UInt8(None)
+                .rem(&ScalarValue::UInt8(Some(0)))
+                .unwrap(),
+            ScalarValue::UInt8(None)
+        );
+
+        let err = ScalarValue::Int64(Some(1))
+            .rem(&ScalarValue::Int64(Some(0)))
+            .unwrap_err();
+        assert!(err.to_string().contains("Divide by zero"), "{}", err);
+    }
+
+    #[test]
+    fn scalar_coerce_pair() {
+        let (a, b) = ScalarValue::coerce_pair(
+            ScalarValue::Int32(Some(2)),
+            ScalarValue::Float64(Some(1.5)),
+        )
+        .unwrap();
+        assert_eq!(a, ScalarValue::Float64(Some(2.0)));
+        assert_eq!(b, ScalarValue::Float64(Some(1.5)));
+
+        let (a, b) = ScalarValue::coerce_pair(
+            ScalarValue::Float64(None),
+            ScalarValue::Int32(Some(2)),
+        )
+        .unwrap();
+        assert_eq!(a, ScalarValue::Float64(None));
+        assert_eq!(b, ScalarValue::Float64(Some(2.0)));
+
+        let err = ScalarValue::coerce_pair(
+            ScalarValue::Utf8(Some("a".to_string())),
+            ScalarValue::Int32(Some(1)),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("No common type for Utf8 and Int32"));
+    }
+
+    #[test]
+    fn scalar_try_to_array_of_size_list_error() {
+        // elements that do not match the list type cannot be built
+        let scalar = ScalarValue::List(
+            Some(Box::new(vec![
+                ScalarValue::Binary(Some(vec![1])),
+                ScalarValue::Int32(Some(1)),
+            ])),
+            Box::new(DataType::Binary),
+            true,
+        );
+        assert!(scalar.try_to_array_of_size(2).is_err());
+
+        let scalar = ScalarValue::List(
+            Some(Box::new(vec![ScalarValue::Binary(Some(vec![1]))])),
+            Box::new(DataType::Binary),
+            true,
+        );
+        let array = scalar.try_to_array_of_size(2).unwrap();
+        assert_eq!(array.len(), 2);
+        assert!(scalar.eq_array(&array, 1));
+    }
 }
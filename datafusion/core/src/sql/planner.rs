@@ -1980,6 +1980,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                                 partition_by,
                                 order_by,
                                 window_frame,
+                                distinct: false,
                             });
                         }
                         WindowFunction::BuiltInWindowFunction(
@@ -1993,6 +1994,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                                 partition_by,
                                 order_by,
                                 window_frame,
+                                distinct: false,
                             });
                         }
                     }
@@ -2434,6 +2436,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(Expr::Literal(ScalarValue::List(
                 None,
                 Box::new(DataType::Utf8),
+                true,
             )))
         } else if data_types.len() > 1 {
             Err(DataFusionError::NotImplemented(format!(
@@ -2446,6 +2449,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(Expr::Literal(ScalarValue::List(
                 Some(Box::new(values)),
                 Box::new(data_type),
+                true,
             )))
         }
     }
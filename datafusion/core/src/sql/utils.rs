@@ -330,6 +330,7 @@ where
                 partition_by,
                 order_by,
                 window_frame,
+                distinct,
             } => Ok(Expr::WindowFunction {
                 fun: fun.clone(),
                 args: args
@@ -345,6 +346,7 @@ where
                     .map(|e| clone_with_replacement(e, replacement_fn))
                     .collect::<Result<Vec<_>>>()?,
                 window_frame: *window_frame,
+                distinct: *distinct,
             }),
             Expr::AggregateUDF { fun, args } => Ok(Expr::AggregateUDF {
                 fun: fun.clone(),
@@ -701,6 +703,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let max2 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Max),
@@ -708,6 +711,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let min3 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Min),
@@ -715,6 +719,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let sum4 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -722,6 +727,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let exprs = &[max1.clone(), max2.clone(), min3.clone(), sum4.clone()];
         let result = group_window_expr_by_sort_keys(exprs)?;
@@ -755,6 +761,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![age_asc.clone(), name_desc.clone()],
             window_frame: None,
+            distinct: false,
         };
         let max2 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Max),
@@ -762,6 +769,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![],
             window_frame: None,
+            distinct: false,
         };
         let min3 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Min),
@@ -769,6 +777,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![age_asc.clone(), name_desc.clone()],
             window_frame: None,
+            distinct: false,
         };
         let sum4 = Expr::WindowFunction {
             fun: WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -776,6 +785,7 @@ mod tests {
             partition_by: vec![],
             order_by: vec![name_desc.clone(), age_asc.clone(), created_at_desc.clone()],
             window_frame: None,
+            distinct: false,
         };
         // FIXME use as_ref
         let exprs = &[max1.clone(), max2.clone(), min3.clone(), sum4.clone()];
@@ -814,6 +824,7 @@ mod tests {
                     },
                 ],
                 window_frame: None,
+                distinct: false,
             },
             Expr::WindowFunction {
                 fun: WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -837,6 +848,7 @@ mod tests {
                     },
                 ],
                 window_frame: None,
+                distinct: false,
             },
         ];
         let expected = vec![
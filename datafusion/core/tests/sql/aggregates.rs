@@ -701,7 +701,8 @@ async fn csv_query_array_agg_distinct() -> Result<()> {
     let column = actual[0].column(0);
     assert_eq!(column.len(), 1);
 
-    if let ScalarValue::List(Some(mut v), _) = ScalarValue::try_from_array(column, 0)? {
+    if let ScalarValue::List(Some(mut v), _, _) = ScalarValue::try_from_array(column, 0)?
+    {
         // workaround lack of Ord of ScalarValue
         let cmp = |a: &ScalarValue, b: &ScalarValue| {
             a.partial_cmp(b).expect("Can compare ScalarValues")
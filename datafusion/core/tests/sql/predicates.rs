@@ -370,6 +370,40 @@ async fn test_expect_distinct() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn except_with_duplicates() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    // EXCEPT ALL subtracts multiplicities
+    let sql = "SELECT * FROM (VALUES (1), (1), (1), (2), (3)) AS t1 \
+               EXCEPT ALL SELECT * FROM (VALUES (1), (2), (2)) AS t2";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---------+",
+        "| column1 |",
+        "+---------+",
+        "| 1       |",
+        "| 1       |",
+        "| 3       |",
+        "+---------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    // EXCEPT returns each remaining row once
+    let sql = "SELECT * FROM (VALUES (1), (1), (1), (2), (3), (3)) AS t1 \
+               EXCEPT SELECT * FROM (VALUES (1), (2), (2)) AS t2";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---------+",
+        "| column1 |",
+        "+---------+",
+        "| 3       |",
+        "+---------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_in_set_test() -> Result<()> {
     let ctx = SessionContext::new();
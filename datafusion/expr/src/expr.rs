@@ -210,6 +210,8 @@ pub enum Expr {
         order_by: Vec<Expr>,
         /// Window frame
         window_frame: Option<window_frame::WindowFrame>,
+        /// Whether this is a DISTINCT aggregation or not
+        distinct: bool,
     },
     /// aggregate function
     AggregateUDF {
@@ -518,8 +520,9 @@ impl fmt::Debug for Expr {
                 partition_by,
                 order_by,
                 window_frame,
+                distinct,
             } => {
-                fmt_function(f, &fun.to_string(), false, args, false)?;
+                fmt_function(f, &fun.to_string(), *distinct, args, false)?;
                 if !partition_by.is_empty() {
                     write!(f, " PARTITION BY {:?}", partition_by)?;
                 }
@@ -742,10 +745,11 @@ fn create_name(e: &Expr, input_schema: &DFSchema) -> Result<String> {
             window_frame,
             partition_by,
             order_by,
+            distinct,
         } => {
             let mut parts: Vec<String> = vec![create_function_name(
                 &fun.to_string(),
-                false,
+                *distinct,
                 args,
                 input_schema,
             )?];
@@ -197,12 +197,14 @@ impl ExprRewritable for Expr {
                 partition_by,
                 order_by,
                 window_frame,
+                distinct,
             } => Expr::WindowFunction {
                 args: rewrite_vec(args, rewriter)?,
                 fun,
                 partition_by: rewrite_vec(partition_by, rewriter)?,
                 order_by: rewrite_vec(order_by, rewriter)?,
                 window_frame,
+                distinct,
             },
             Expr::AggregateFunction {
                 args,
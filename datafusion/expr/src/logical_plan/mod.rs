@@ -20,11 +20,12 @@ mod extension;
 mod plan;
 
 pub use plan::{
-    Aggregate, Analyze, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
-    CreateMemoryTable, CreateView, CrossJoin, DropTable, EmptyRelation, Explain,
-    Extension, FileType, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
-    Partitioning, PlanType, PlanVisitor, Projection, Repartition, Sort, StringifiedPlan,
-    Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union, Values, Window,
+    collect_columns, first_table_scan, Aggregate, Analyze, CreateCatalog,
+    CreateCatalogSchema, CreateExternalTable, CreateMemoryTable, CreateView, CrossJoin,
+    DropTable, EmptyRelation, EstimatedRows, Explain, Extension, FileType, Filter, Join,
+    JoinConstraint, JoinHint, JoinType, Limit, LogicalPlan, Partitioning, PlanType,
+    PlanVisitor, Projection, Repartition, Sort, StringifiedPlan, Subquery, SubqueryAlias,
+    TableScan, ToStringifiedPlan, Union, Values, Window,
 };
 
 pub use display::display_schema;
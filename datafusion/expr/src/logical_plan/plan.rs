@@ -15,8 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
 use crate::logical_plan::display::{GraphvizVisitor, IndentVisitor};
 use crate::logical_plan::extension::UserDefinedLogicalNode;
+use crate::utils::expr_to_columns;
 use crate::{Expr, TableProviderFilterPushDown, TableSource};
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion_common::{Column, DFSchemaRef, DataFusionError};
@@ -73,6 +75,9 @@ pub enum LogicalPlan {
     SubqueryAlias(SubqueryAlias),
     /// Produces the first `n` tuples from its input and discards the rest.
     Limit(Limit),
+    /// Annotates its input with an estimate of the number of rows it
+    /// produces, without changing its output.
+    EstimatedRows(EstimatedRows),
     /// Creates an external table.
     CreateExternalTable(CreateExternalTable),
     /// Creates an in memory table.
@@ -117,6 +122,7 @@ impl LogicalPlan {
             LogicalPlan::CrossJoin(CrossJoin { schema, .. }) => schema,
             LogicalPlan::Repartition(Repartition { input, .. }) => input.schema(),
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
+            LogicalPlan::EstimatedRows(EstimatedRows { input, .. }) => input.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
             LogicalPlan::CreateExternalTable(CreateExternalTable { schema, .. }) => {
@@ -183,6 +189,7 @@ impl LogicalPlan {
                 vec![schema]
             }
             LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::EstimatedRows(EstimatedRows { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::CreateMemoryTable(CreateMemoryTable { input, .. })
@@ -229,12 +236,15 @@ impl LogicalPlan {
                 .collect(),
             LogicalPlan::Sort(Sort { expr, .. }) => expr.clone(),
             LogicalPlan::Extension(extension) => extension.node.expressions(),
+            LogicalPlan::TableScan(TableScan {
+                partition_filters, ..
+            }) => partition_filters.clone(),
             // plans without expressions
-            LogicalPlan::TableScan { .. }
-            | LogicalPlan::EmptyRelation(_)
+            LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::EstimatedRows(_)
             | LogicalPlan::CreateExternalTable(_)
             | LogicalPlan::CreateMemoryTable(_)
             | LogicalPlan::CreateView(_)
@@ -263,6 +273,7 @@ impl LogicalPlan {
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
+            LogicalPlan::EstimatedRows(EstimatedRows { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
@@ -368,6 +379,69 @@ pub trait PlanVisitor {
     }
 }
 
+/// Returns every [Column] referenced by the expressions of `plan` and of
+/// all its inputs, including the plans of subquery expressions.
+pub fn collect_columns(plan: &LogicalPlan) -> Result<HashSet<Column>, DataFusionError> {
+    struct ColumnCollector {
+        columns: HashSet<Column>,
+    }
+
+    impl PlanVisitor for ColumnCollector {
+        type Error = DataFusionError;
+
+        fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool, Self::Error> {
+            for expr in plan.expressions() {
+                expr_to_columns(&expr, &mut self.columns)?;
+                for subquery in find_subqueries(&expr)? {
+                    subquery.accept(self)?;
+                }
+            }
+            Ok(true)
+        }
+    }
+
+    let mut collector = ColumnCollector {
+        columns: HashSet::new(),
+    };
+    plan.accept(&mut collector)?;
+    Ok(collector.columns)
+}
+
+/// Returns the first [TableScan] found in a pre-order traversal of `plan`
+/// and its inputs, in the order in which [LogicalPlan::accept] visits them,
+/// or `None` if there is no table scan. The plans of subquery expressions
+/// are not searched.
+pub fn first_table_scan(plan: &LogicalPlan) -> Option<&TableScan> {
+    match plan {
+        LogicalPlan::TableScan(scan) => Some(scan),
+        _ => plan.inputs().into_iter().find_map(first_table_scan),
+    }
+}
+
+/// Returns the plans of all subquery expressions within `expr`
+fn find_subqueries(expr: &Expr) -> Result<Vec<Arc<LogicalPlan>>, DataFusionError> {
+    struct SubqueryFinder {
+        subqueries: Vec<Arc<LogicalPlan>>,
+    }
+
+    impl ExpressionVisitor for SubqueryFinder {
+        fn pre_visit(mut self, expr: &Expr) -> Result<Recursion<Self>, DataFusionError> {
+            match expr {
+                Expr::Exists { subquery, .. }
+                | Expr::InSubquery { subquery, .. }
+                | Expr::ScalarSubquery(subquery) => {
+                    self.subqueries.push(subquery.subquery.clone())
+                }
+                _ => {}
+            }
+            Ok(Recursion::Continue(self))
+        }
+    }
+
+    let finder = expr.accept(SubqueryFinder { subqueries: vec![] })?;
+    Ok(finder.subqueries)
+}
+
 impl LogicalPlan {
     /// returns all inputs in the logical plan. Returns Ok(true) if
     /// all nodes were visited, and Ok(false) if any call to
@@ -403,6 +477,9 @@ impl LogicalPlan {
                 true
             }
             LogicalPlan::Limit(Limit { input, .. }) => input.accept(visitor)?,
+            LogicalPlan::EstimatedRows(EstimatedRows { input, .. }) => {
+                input.accept(visitor)?
+            }
             LogicalPlan::Subquery(Subquery { subquery, .. }) => {
                 subquery.accept(visitor)?
             }
@@ -641,6 +718,7 @@ impl LogicalPlan {
                         ref table_name,
                         ref projection,
                         ref filters,
+                        ref partition_filters,
                         ref limit,
                         ..
                     }) => {
@@ -686,6 +764,10 @@ impl LogicalPlan {
                             }
                         }
 
+                        if !partition_filters.is_empty() {
+                            write!(f, ", partition_filters={:?}", partition_filters)?;
+                        }
+
                         if let Some(n) = limit {
                             write!(f, ", limit={}", n)?;
                         }
@@ -725,7 +807,7 @@ impl LogicalPlan {
                         "Aggregate: groupBy=[{:?}], aggr=[{:?}]",
                         group_expr, aggr_expr
                     ),
-                    LogicalPlan::Sort(Sort { expr, .. }) => {
+                    LogicalPlan::Sort(Sort { expr, fetch, .. }) => {
                         write!(f, "Sort: ")?;
                         for (i, expr_item) in expr.iter().enumerate() {
                             if i > 0 {
@@ -733,12 +815,16 @@ impl LogicalPlan {
                             }
                             write!(f, "{:?}", expr_item)?;
                         }
+                        if let Some(fetch) = fetch {
+                            write!(f, ", fetch={}", fetch)?;
+                        }
                         Ok(())
                     }
                     LogicalPlan::Join(Join {
                         on: ref keys,
                         join_constraint,
                         join_type,
+                        hint,
                         ..
                     }) => {
                         let join_expr: Vec<String> =
@@ -755,7 +841,11 @@ impl LogicalPlan {
                                     join_expr.join(", ")
                                 )
                             }
+                        }?;
+                        if *hint != JoinHint::None {
+                            write!(f, ", hint={:?}", hint)?;
                         }
+                        Ok(())
                     }
                     LogicalPlan::CrossJoin(_) => {
                         write!(f, "CrossJoin:")
@@ -780,7 +870,15 @@ impl LogicalPlan {
                             )
                         }
                     },
+                    LogicalPlan::Limit(Limit {
+                        skip: Some(skip),
+                        ref n,
+                        ..
+                    }) => write!(f, "Limit: skip={}, fetch={}", skip, n),
                     LogicalPlan::Limit(Limit { ref n, .. }) => write!(f, "Limit: {}", n),
+                    LogicalPlan::EstimatedRows(EstimatedRows { ref rows, .. }) => {
+                        write!(f, "EstimatedRows: {}", rows)
+                    }
                     LogicalPlan::Subquery(Subquery { subquery, .. }) => {
                         write!(f, "Subquery: {:?}", subquery)
                     }
@@ -871,6 +969,24 @@ impl Display for JoinType {
     }
 }
 
+/// Advisory hint on how a join should be executed, e.g. by a distributed
+/// planner. Physical planners are free to ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinHint {
+    /// No hint
+    None,
+    /// Broadcast the left input to every partition of the right input
+    BroadcastLeft,
+    /// Broadcast the right input to every partition of the left input
+    BroadcastRight,
+}
+
+impl Default for JoinHint {
+    fn default() -> Self {
+        JoinHint::None
+    }
+}
+
 /// Join constraint
 #[derive(Debug, Clone, Copy)]
 pub enum JoinConstraint {
@@ -998,6 +1114,9 @@ pub struct TableScan {
     pub projected_schema: DFSchemaRef,
     /// Optional expressions to be used as filters by the table provider
     pub filters: Vec<Expr>,
+    /// Optional partition-level expressions the table provider can use
+    /// to prune whole partitions before reading any data
+    pub partition_filters: Vec<Expr>,
     /// Optional limit to skip reading
     pub limit: Option<usize>,
 }
@@ -1125,12 +1244,24 @@ pub struct Extension {
 /// Produces the first `n` tuples from its input and discards the rest.
 #[derive(Clone)]
 pub struct Limit {
+    /// Number of rows to skip before the limit is applied
+    pub skip: Option<usize>,
     /// The limit
     pub n: usize,
     /// The logical plan
     pub input: Arc<LogicalPlan>,
 }
 
+/// Annotates its input with an estimate of the number of rows it produces.
+/// The estimate is a hint only, and does not change the output.
+#[derive(Clone)]
+pub struct EstimatedRows {
+    /// The estimated number of rows
+    pub rows: usize,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+}
+
 /// Aggregates its input based on a set of grouping and aggregate
 /// expressions (e.g. SUM).
 #[derive(Clone)]
@@ -1152,6 +1283,9 @@ pub struct Sort {
     pub expr: Vec<Expr>,
     /// The incoming logical plan
     pub input: Arc<LogicalPlan>,
+    /// Optional maximum number of rows to return, turning the sort into a
+    /// top-k
+    pub fetch: Option<usize>,
 }
 
 /// Join two logical plans on one or more join columns
@@ -1171,6 +1305,8 @@ pub struct Join {
     pub schema: DFSchemaRef,
     /// If null_equals_null is true, null == null else null != null
     pub null_equals_null: bool,
+    /// Advisory hint on how the join should be executed
+    pub hint: JoinHint,
 }
 
 /// Subquery
@@ -132,7 +132,7 @@ impl Accumulator for ArrayAggAccumulator {
         let arr = &states[0];
         (0..arr.len()).try_for_each(|index| {
             let scalar = ScalarValue::try_from_array(arr, index)?;
-            if let ScalarValue::List(Some(values), _) = scalar {
+            if let ScalarValue::List(Some(values), _, _) = scalar {
                 self.values.extend(*values);
                 Ok(())
             } else {
@@ -151,6 +151,7 @@ impl Accumulator for ArrayAggAccumulator {
         Ok(ScalarValue::List(
             Some(Box::new(self.values.clone())),
             Box::new(self.datatype.clone()),
+            true,
         ))
     }
 }
@@ -180,6 +181,7 @@ mod tests {
                 ScalarValue::Int32(Some(5)),
             ])),
             Box::new(DataType::Int32),
+            true,
         );
 
         generic_test_op!(a, DataType::Int32, ArrayAgg, list, DataType::Int32)
@@ -196,6 +198,7 @@ mod tests {
                         ScalarValue::from(3i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -203,6 +206,7 @@ mod tests {
                         ScalarValue::from(5i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -210,6 +214,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let l2 = ScalarValue::List(
@@ -217,6 +222,7 @@ mod tests {
                 ScalarValue::List(
                     Some(Box::new(vec![ScalarValue::from(6i32)])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -224,6 +230,7 @@ mod tests {
                         ScalarValue::from(8i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -231,18 +238,21 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let l3 = ScalarValue::List(
             Some(Box::new(vec![ScalarValue::List(
                 Some(Box::new(vec![ScalarValue::from(9i32)])),
                 Box::new(DataType::Int32),
+                true,
             )])),
             Box::new(DataType::List(Box::new(Field::new(
                 "item",
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let list = ScalarValue::List(
@@ -252,6 +262,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let array = ScalarValue::iter_to_array(vec![l1, l2, l3]).unwrap();
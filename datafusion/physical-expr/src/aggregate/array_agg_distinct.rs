@@ -123,6 +123,7 @@ impl Accumulator for DistinctArrayAggAccumulator {
         Ok(vec![ScalarValue::List(
             Some(Box::new(self.values.clone().into_iter().collect())),
             Box::new(self.datatype.clone()),
+            true,
         )])
     }
 
@@ -154,6 +155,7 @@ impl Accumulator for DistinctArrayAggAccumulator {
         Ok(ScalarValue::List(
             Some(Box::new(self.values.clone().into_iter().collect())),
             Box::new(self.datatype.clone()),
+            true,
         ))
     }
 }
@@ -183,7 +185,10 @@ mod tests {
         let actual = aggregate(&batch, agg)?;
 
         match (expected, actual) {
-            (ScalarValue::List(Some(mut e), _), ScalarValue::List(Some(mut a), _)) => {
+            (
+                ScalarValue::List(Some(mut e), _, _),
+                ScalarValue::List(Some(mut a), _, _),
+            ) => {
                 // workaround lack of Ord of ScalarValue
                 let cmp = |a: &ScalarValue, b: &ScalarValue| {
                     a.partial_cmp(b).expect("Can compare ScalarValues")
@@ -215,6 +220,7 @@ mod tests {
                 ScalarValue::Int32(Some(5)),
             ])),
             Box::new(DataType::Int32),
+            true,
         );
 
         check_distinct_array_agg(col, out, DataType::Int32)
@@ -232,6 +238,7 @@ mod tests {
                         ScalarValue::from(3i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -239,6 +246,7 @@ mod tests {
                         ScalarValue::from(5i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -246,6 +254,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         // [[6], [7, 8]]
@@ -254,6 +263,7 @@ mod tests {
                 ScalarValue::List(
                     Some(Box::new(vec![ScalarValue::from(6i32)])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
                 ScalarValue::List(
                     Some(Box::new(vec![
@@ -261,6 +271,7 @@ mod tests {
                         ScalarValue::from(8i32),
                     ])),
                     Box::new(DataType::Int32),
+                    true,
                 ),
             ])),
             Box::new(DataType::List(Box::new(Field::new(
@@ -268,6 +279,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         // [[9]]
@@ -275,12 +287,14 @@ mod tests {
             Some(Box::new(vec![ScalarValue::List(
                 Some(Box::new(vec![ScalarValue::from(9i32)])),
                 Box::new(DataType::Int32),
+                true,
             )])),
             Box::new(DataType::List(Box::new(Field::new(
                 "item",
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         let list = ScalarValue::List(
@@ -290,6 +304,7 @@ mod tests {
                 DataType::Int32,
                 true,
             )))),
+            true,
         );
 
         // Duplicate l1 in the input array and check that it is deduped in the output.
@@ -143,7 +143,7 @@ impl DistinctCountAccumulator {
         let col_values = states
             .iter()
             .map(|state| match state {
-                ScalarValue::List(Some(values), _) => Ok(values),
+                ScalarValue::List(Some(values), _, _) => Ok(values),
                 _ => Err(DataFusionError::Internal(format!(
                     "Unexpected accumulator state {:?}",
                     state
@@ -193,14 +193,14 @@ impl Accumulator for DistinctCountAccumulator {
             .map(|state_data_type| {
                 let values = Box::new(Vec::new());
                 let data_type = Box::new(state_data_type.clone());
-                ScalarValue::List(Some(values), data_type)
+                ScalarValue::List(Some(values), data_type, true)
             })
             .collect::<Vec<_>>();
 
         let mut cols_vec = cols_out
             .iter_mut()
             .map(|c| match c {
-                ScalarValue::List(Some(ref mut v), _) => v,
+                ScalarValue::List(Some(ref mut v), _, _) => v,
                 _ => unreachable!(),
             })
             .collect::<Vec<_>>();
@@ -241,7 +241,7 @@ mod tests {
     macro_rules! state_to_vec {
         ($LIST:expr, $DATA_TYPE:ident, $PRIM_TY:ty) => {{
             match $LIST {
-                ScalarValue::List(_, data_type) => match data_type.as_ref() {
+                ScalarValue::List(_, data_type, _) => match data_type.as_ref() {
                     &DataType::$DATA_TYPE => (),
                     _ => panic!("Unexpected DataType for list"),
                 },
@@ -249,8 +249,8 @@ mod tests {
             }
 
             match $LIST {
-                ScalarValue::List(None, _) => None,
-                ScalarValue::List(Some(scalar_values), _) => {
+                ScalarValue::List(None, _, _) => None,
+                ScalarValue::List(Some(scalar_values), _, _) => {
                     let vec = scalar_values
                         .iter()
                         .map(|scalar_value| match scalar_value {
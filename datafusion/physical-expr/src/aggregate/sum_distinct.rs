@@ -118,7 +118,7 @@ impl DistinctSumAccumulator {
         }
 
         states.iter().try_for_each(|state| match state {
-            ScalarValue::List(Some(values), _) => self.update(values.as_ref()),
+            ScalarValue::List(Some(values), _, _) => self.update(values.as_ref()),
             _ => Err(DataFusionError::Internal(format!(
                 "Unexpected accumulator state {:?}",
                 state
@@ -137,7 +137,7 @@ impl Accumulator for DistinctSumAccumulator {
             self.hash_values
                 .iter()
                 .for_each(|distinct_value| distinct_values.push(distinct_value.clone()));
-            vec![ScalarValue::List(Some(distinct_values), data_type)]
+            vec![ScalarValue::List(Some(distinct_values), data_type, true)]
         };
         Ok(state_out)
     }
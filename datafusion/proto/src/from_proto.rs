@@ -601,7 +601,12 @@ impl TryFrom<&protobuf::scalar_value::Value> for ScalarValue {
             Value::Float64Value(v) => ScalarValue::Float64(Some(*v)),
             Value::Date32Value(v) => ScalarValue::Date32(Some(*v)),
             Value::ListValue(v) => v.try_into()?,
-            Value::NullListValue(v) => ScalarValue::List(None, Box::new(v.try_into()?)),
+            Value::NullListValue(v) => {
+                ScalarValue::List(None, Box::new(v.try_into()?), true)
+            }
+            Value::NonNullableNullListValue(v) => {
+                ScalarValue::List(None, Box::new(v.try_into()?), false)
+            }
             Value::NullValue(null_enum) => {
                 let primitive = PrimitiveScalarType::try_from(null_enum)?;
                 (&primitive).try_into()?
@@ -653,7 +658,12 @@ impl TryFrom<&protobuf::ScalarListValue> for ScalarValue {
     ) -> Result<Self, Self::Error> {
         use protobuf::{scalar_type::Datatype, PrimitiveScalarType};
 
-        let protobuf::ScalarListValue { datatype, values } = scalar_list_value;
+        let protobuf::ScalarListValue {
+            datatype,
+            values,
+            non_nullable_elements,
+        } = scalar_list_value;
+        let nullable = !non_nullable_elements;
         let pb_scalar_type = datatype
             .as_ref()
             .ok_or_else(|| Error::required("datatype"))?;
@@ -677,6 +687,7 @@ impl TryFrom<&protobuf::ScalarListValue> for ScalarValue {
                 ScalarValue::List(
                     Some(Box::new(typechecked_values)),
                     Box::new(leaf_scalar_type.into()),
+                    nullable,
                 )
             }
             Datatype::List(list_type) => {
@@ -713,6 +724,7 @@ impl TryFrom<&protobuf::ScalarListValue> for ScalarValue {
                         _ => Some(Box::new(typechecked_values)),
                     },
                     Box::new((list_type).try_into()?),
+                    nullable,
                 )
             }
         };
@@ -851,6 +863,7 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
                 let protobuf::ScalarListValue {
                     values,
                     datatype: opt_scalar_type,
+                    non_nullable_elements,
                 } = &scalar_list;
 
                 let scalar_type = opt_scalar_type.as_ref().required("datatype")?;
@@ -861,11 +874,15 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
                     .map(|val| val.try_into())
                     .collect::<Result<Vec<_>, _>>()?;
 
-                Self::List(Some(Box::new(typechecked_values)), scalar_type)
+                Self::List(
+                    Some(Box::new(typechecked_values)),
+                    scalar_type,
+                    !non_nullable_elements,
+                )
             }
-            Value::NullListValue(v) => {
-                let datatype = v.datatype.as_ref().required("datatype")?;
-                Self::List(None, Box::new(datatype))
+            Value::NullListValue(v) => Self::List(None, Box::new(v.try_into()?), true),
+            Value::NonNullableNullListValue(v) => {
+                Self::List(None, Box::new(v.try_into()?), false)
             }
             Value::NullValue(v) => {
                 let null_type_enum = protobuf::PrimitiveScalarType::try_from(v)?;
@@ -978,6 +995,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        distinct: false,
                     })
                 }
                 window_expr_node::WindowFunction::BuiltInFunction(i) => {
@@ -993,6 +1011,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        distinct: false,
                     })
                 }
             }
@@ -30,7 +30,7 @@ mod roundtrip_tests {
     use super::from_proto::parse_expr;
     use super::protobuf;
     use datafusion::arrow::array::ArrayRef;
-    use datafusion::logical_plan::create_udaf;
+    use datafusion::logical_plan::{assert_unique_udf, create_udaf};
     use datafusion::physical_plan::functions::make_scalar_function;
     use datafusion::physical_plan::Accumulator;
     use datafusion::{
@@ -72,6 +72,7 @@ mod roundtrip_tests {
                     ScalarValue::Float32(Some(32.0)),
                 ])),
                 Box::new(DataType::List(new_box_field("item", DataType::Int16, true))),
+                true,
             ),
             ScalarValue::List(
                 Some(Box::new(vec![
@@ -79,6 +80,7 @@ mod roundtrip_tests {
                     ScalarValue::Float32(Some(32.0)),
                 ])),
                 Box::new(DataType::List(new_box_field("item", DataType::Int16, true))),
+                true,
             ),
             ScalarValue::List(
                 Some(Box::new(vec![
@@ -89,6 +91,7 @@ mod roundtrip_tests {
                             DataType::Float32,
                             true,
                         ))),
+                        true,
                     ),
                     ScalarValue::List(
                         Some(Box::new(vec![
@@ -103,6 +106,7 @@ mod roundtrip_tests {
                             DataType::Float32,
                             true,
                         ))),
+                        true,
                     ),
                     ScalarValue::List(
                         None,
@@ -111,6 +115,7 @@ mod roundtrip_tests {
                             DataType::Int16,
                             true,
                         ))),
+                        true,
                     ),
                 ])),
                 Box::new(DataType::List(new_box_field(
@@ -118,6 +123,7 @@ mod roundtrip_tests {
                     DataType::List(new_box_field("level2", DataType::Float32, true)),
                     true,
                 ))),
+                true,
             ),
         ];
 
@@ -150,7 +156,7 @@ mod roundtrip_tests {
             ScalarValue::UInt64(None),
             ScalarValue::Utf8(None),
             ScalarValue::LargeUtf8(None),
-            ScalarValue::List(None, Box::new(DataType::Boolean)),
+            ScalarValue::List(None, Box::new(DataType::Boolean), true),
             ScalarValue::Date32(None),
             ScalarValue::Boolean(Some(true)),
             ScalarValue::Boolean(Some(false)),
@@ -219,6 +225,7 @@ mod roundtrip_tests {
                     DataType::Float32,
                     true,
                 ))),
+                true,
             ),
             ScalarValue::List(
                 Some(Box::new(vec![
@@ -229,6 +236,7 @@ mod roundtrip_tests {
                             DataType::Float32,
                             true,
                         ))),
+                        true,
                     ),
                     ScalarValue::List(
                         Some(Box::new(vec![
@@ -243,6 +251,7 @@ mod roundtrip_tests {
                             DataType::Float32,
                             true,
                         ))),
+                        true,
                     ),
                 ])),
                 Box::new(DataType::List(new_box_field(
@@ -250,6 +259,7 @@ mod roundtrip_tests {
                     DataType::List(new_box_field("level2", DataType::Float32, true)),
                     true,
                 ))),
+                true,
             ),
         ];
 
@@ -259,6 +269,70 @@ mod roundtrip_tests {
         }
     }
 
+    #[test]
+    fn round_trip_scalar_list_nullability() {
+        let values = vec![
+            ScalarValue::List(
+                Some(Box::new(vec![ScalarValue::Float32(Some(1.0))])),
+                Box::new(DataType::List(new_box_field(
+                    "level1",
+                    DataType::Float32,
+                    false,
+                ))),
+                false,
+            ),
+            ScalarValue::List(None, Box::new(DataType::Boolean), false),
+            ScalarValue::List(None, Box::new(DataType::Boolean), true),
+        ];
+
+        for test_case in values.into_iter() {
+            let proto: super::protobuf::ScalarValue = (&test_case).try_into().unwrap();
+            let roundtrip: ScalarValue = (&proto).try_into().unwrap();
+            match (&test_case, &roundtrip) {
+                (
+                    ScalarValue::List(_, _, expected),
+                    ScalarValue::List(_, _, nullable),
+                ) => assert_eq!(expected, nullable, "{:?}", test_case),
+                _ => panic!("expected a list, got {:?}", roundtrip),
+            }
+        }
+    }
+
+    #[test]
+    fn decode_scalar_list_without_nullability() {
+        use prost::Message;
+
+        // messages written before `non_nullable_elements` existed have
+        // nullable elements
+        let list_type = DataType::List(new_box_field("item", DataType::Int32, true));
+        let old_messages = vec![
+            protobuf::ScalarValue {
+                value: Some(protobuf::scalar_value::Value::ListValue(
+                    protobuf::ScalarListValue {
+                        datatype: Some((&list_type).try_into().unwrap()),
+                        values: vec![(&ScalarValue::Int32(Some(1))).try_into().unwrap()],
+                        ..Default::default()
+                    },
+                )),
+            },
+            protobuf::ScalarValue {
+                value: Some(protobuf::scalar_value::Value::NullListValue(
+                    (&DataType::Int32).try_into().unwrap(),
+                )),
+            },
+        ];
+
+        for message in old_messages {
+            let bytes = message.encode_to_vec();
+            let decoded = protobuf::ScalarValue::decode(bytes.as_slice()).unwrap();
+            let scalar: ScalarValue = (&decoded).try_into().unwrap();
+            match scalar {
+                ScalarValue::List(_, _, nullable) => assert!(nullable),
+                other => panic!("expected a list, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn round_trip_scalar_types() {
         let should_pass: Vec<DataType> = vec![
@@ -791,4 +865,16 @@ mod roundtrip_tests {
 
         roundtrip_expr_test!(test_expr, ctx);
     }
+
+    #[test]
+    fn roundtrip_assert_unique_udf() {
+        let test_expr = Expr::ScalarUDF {
+            fun: Arc::new(assert_unique_udf()),
+            args: vec![col("count"), lit("duplicate values")],
+        };
+
+        // the function is registered without calling register_udf
+        let ctx = SessionContext::new();
+        roundtrip_expr_test!(test_expr, ctx);
+    }
 }
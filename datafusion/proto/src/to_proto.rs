@@ -462,7 +462,13 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                 ref partition_by,
                 ref order_by,
                 ref window_frame,
+                distinct,
             } => {
+                if *distinct {
+                    return Err(Error::General(
+                        "DISTINCT window functions cannot be serialized".to_string(),
+                    ));
+                }
                 let window_function = match fun {
                     WindowFunction::AggregateFunction(fun) => {
                         protobuf::window_expr_node::WindowFunction::AggrFunction(
@@ -793,7 +799,7 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                     Value::LargeUtf8Value(s.to_owned())
                 })
             }
-            scalar::ScalarValue::List(value, datatype) => {
+            scalar::ScalarValue::List(value, datatype, nullable) => {
                 println!("Current datatype of list: {:?}", datatype);
                 match value {
                     Some(values) => {
@@ -803,6 +809,7 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                                     protobuf::ScalarListValue {
                                         datatype: Some(datatype.as_ref().try_into()?),
                                         values: Vec::new(),
+                                        non_nullable_elements: !*nullable,
                                     },
                                 )),
                             }
@@ -816,7 +823,7 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                                 .iter()
                                 .map(|scalar| match (scalar, scalar_type) {
                                     (
-                                        scalar::ScalarValue::List(_, list_type),
+                                        scalar::ScalarValue::List(_, list_type, _),
                                         DataType::List(field),
                                     ) => {
                                         if let DataType::List(list_field) =
@@ -897,16 +904,24 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                                     protobuf::ScalarListValue {
                                         datatype: Some(datatype.as_ref().try_into()?),
                                         values: type_checked_values,
+                                        non_nullable_elements: !*nullable,
                                     },
                                 )),
                             }
                         }
                     }
-                    None => protobuf::ScalarValue {
-                        value: Some(protobuf::scalar_value::Value::NullListValue(
-                            datatype.as_ref().try_into()?,
-                        )),
-                    },
+                    None => {
+                        let datatype = datatype.as_ref().try_into()?;
+                        protobuf::ScalarValue {
+                            value: Some(if *nullable {
+                                protobuf::scalar_value::Value::NullListValue(datatype)
+                            } else {
+                                protobuf::scalar_value::Value::NonNullableNullListValue(
+                                    datatype,
+                                )
+                            }),
+                        }
+                    }
                 }
             }
             datafusion::scalar::ScalarValue::Date32(val) => {